@@ -0,0 +1,550 @@
+use core::{cmp::Ordering, fmt, marker::PhantomData};
+
+use super::Compare;
+use crate::sealed::binary_heap::Kind;
+use crate::Vec;
+
+// end of the free list
+const NONE: usize = usize::MAX;
+
+/// A stable reference to an item of an [`IndexedBinaryHeap`]
+///
+/// A handle stays valid while its item is in the heap, however the heap is reordered. Once the
+/// item has been popped or removed the handle is *stale*: it no longer refers to any item, even
+/// after its slot has been reused by a newer item.
+///
+/// Passing the handle of one heap to another one is a logic error: it may refer to any item of the
+/// other heap, or to none.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Handle {
+    slot: usize,
+    generation: u32,
+}
+
+#[derive(Clone, Copy)]
+struct Slot {
+    // bumped every time the slot is freed, so stale handles can't match it
+    generation: u32,
+    // position of the item in `data` while the slot is in use, next free slot otherwise
+    index: usize,
+}
+
+#[derive(Clone)]
+struct Node<T> {
+    item: T,
+    slot: usize,
+}
+
+/// A priority queue whose items can be reprioritized or removed through a [`Handle`]
+///
+/// `push` returns a handle to the item, which [`change_priority`](Self::change_priority),
+/// [`remove`](Self::remove), [`contains`](Self::contains) and [`get`](Self::get) accept. Next to
+/// the heap it keeps a table of `N` slots that maps handles to heap positions, so all these
+/// operations are *O*(log N).
+///
+/// The handles carry a 32-bit generation, checked against the one of their slot: a handle to an
+/// item that has left the heap is reported as stale instead of reaching the item that took its
+/// slot. The check could only be fooled by a handle kept while its slot was reused 2<sup>32</sup>
+/// times.
+///
+/// # Examples
+///
+/// Dijkstra's shortest paths, decreasing the distance of queued nodes:
+///
+/// ```
+/// use heapless::binary_heap::{Handle, IndexedBinaryHeap, Min};
+///
+/// // (from, to, cost)
+/// let edges = [(0, 1, 7), (0, 2, 2), (2, 1, 3), (1, 3, 1), (2, 3, 8)];
+///
+/// let mut dist = [u32::MAX; 4];
+/// let mut queued: [Option<Handle>; 4] = [None; 4];
+/// // (distance, node)
+/// let mut queue = IndexedBinaryHeap::<(u32, usize), Min, 4>::new();
+///
+/// dist[0] = 0;
+/// queued[0] = Some(queue.push((0, 0)).unwrap());
+/// while let Some((d, node)) = queue.pop() {
+///     queued[node] = None;
+///     for &(_, to, cost) in edges.iter().filter(|edge| edge.0 == node) {
+///         if d + cost < dist[to] {
+///             dist[to] = d + cost;
+///             match queued[to] {
+///                 Some(handle) => {
+///                     queue.change_priority(handle, (dist[to], to)).unwrap();
+///                 }
+///                 None => queued[to] = Some(queue.push((dist[to], to)).unwrap()),
+///             }
+///         }
+///     }
+/// }
+///
+/// assert_eq!(dist, [0, 5, 2, 6]);
+/// ```
+pub struct IndexedBinaryHeap<T, K, const N: usize> {
+    cmp: K,
+    data: Vec<Node<T>, N>,
+    slots: [Slot; N],
+    // head of the list of freed slots
+    free: usize,
+    // slots past this one have never been used
+    fresh: usize,
+}
+
+impl<T, K, const N: usize> IndexedBinaryHeap<T, K, N>
+where
+    PhantomData<K>: Kind<K>,
+{
+    /// Creates an empty IndexedBinaryHeap as a $K-heap
+    ///
+    /// ```
+    /// use heapless::binary_heap::{IndexedBinaryHeap, Max};
+    ///
+    /// // allocate the binary heap on the stack
+    /// let mut heap: IndexedBinaryHeap<_, Max, 8> = IndexedBinaryHeap::new();
+    /// heap.push(4).unwrap();
+    ///
+    /// // allocate the binary heap in a static variable
+    /// static mut HEAP: IndexedBinaryHeap<i32, Max, 8> = IndexedBinaryHeap::new();
+    /// ```
+    pub const fn new() -> Self {
+        Self::with_cmp(<PhantomData<K> as Kind<K>>::INSTANCE)
+    }
+}
+
+impl<T, K, const N: usize> IndexedBinaryHeap<T, K, N> {
+    /// Creates an empty IndexedBinaryHeap that orders its items with the comparator `cmp`
+    pub const fn with_cmp(cmp: K) -> Self {
+        Self {
+            cmp,
+            data: Vec::new(),
+            slots: [Slot {
+                generation: 0,
+                index: 0,
+            }; N],
+            free: NONE,
+            fresh: 0,
+        }
+    }
+
+    /// Returns the comparator of the binary heap
+    pub fn cmp(&self) -> &K {
+        &self.cmp
+    }
+
+    /// Returns the capacity of the binary heap
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the length of the binary heap
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Checks if the binary heap is empty
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns an iterator visiting all the items and their handles, in arbitrary order
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (Handle, &T)> + '_ {
+        self.data
+            .iter()
+            .map(move |node| (self.handle(node.slot), &node.item))
+    }
+
+    /// Returns the *top* item of the binary heap, or `None` if it is empty
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first().map(|node| &node.item)
+    }
+
+    /// Returns the handle of the *top* item of the binary heap, or `None` if it is empty
+    pub fn peek_handle(&self) -> Option<Handle> {
+        self.data.first().map(|node| self.handle(node.slot))
+    }
+
+    /// Returns `true` if the item `handle` refers to is still in the binary heap
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.position(handle).is_some()
+    }
+
+    /// Returns the item `handle` refers to, or `None` if the handle is stale
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        let pos = self.position(handle)?;
+        Some(&self.data[pos].item)
+    }
+
+    /// Drops all items from the binary heap, making all their handles stale
+    pub fn clear(&mut self) {
+        for node in self.data.iter() {
+            let slot = &mut self.slots[node.slot];
+            slot.generation = slot.generation.wrapping_add(1);
+        }
+        self.data.clear();
+        self.free = NONE;
+        self.fresh = 0;
+    }
+
+    fn handle(&self, slot: usize) -> Handle {
+        Handle {
+            slot,
+            generation: self.slots[slot].generation,
+        }
+    }
+
+    // position in `data` of the item `handle` refers to
+    fn position(&self, handle: Handle) -> Option<usize> {
+        let slot = self.slots.get(handle.slot)?;
+        let node = self.data.get(slot.index)?;
+        if slot.generation == handle.generation && node.slot == handle.slot {
+            Some(slot.index)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, K, const N: usize> IndexedBinaryHeap<T, K, N>
+where
+    K: Compare<T>,
+{
+    /// Pushes an item onto the binary heap and returns its handle
+    ///
+    /// Returns back the item if the binary heap is full.
+    ///
+    /// ```
+    /// use heapless::binary_heap::{IndexedBinaryHeap, Max};
+    ///
+    /// let mut heap: IndexedBinaryHeap<_, Max, 8> = IndexedBinaryHeap::new();
+    /// let three = heap.push(3).unwrap();
+    /// heap.push(5).unwrap();
+    ///
+    /// assert_eq!(heap.get(three), Some(&3));
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    pub fn push(&mut self, item: T) -> Result<Handle, T> {
+        if self.data.is_full() {
+            return Err(item);
+        }
+
+        let slot = if self.free != NONE {
+            let slot = self.free;
+            self.free = self.slots[slot].index;
+            slot
+        } else {
+            self.fresh += 1;
+            self.fresh - 1
+        };
+
+        let pos = self.data.len();
+        self.slots[slot].index = pos;
+        // NOTE(unwrap) the heap is not full
+        self.data.push(Node { item, slot }).ok().unwrap();
+        self.sift_up(pos);
+
+        Ok(self.handle(slot))
+    }
+
+    /// Removes the *top* item from the binary heap and returns it, or `None` if it is empty
+    ///
+    /// The handle of the item becomes stale.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.remove_at(0))
+        }
+    }
+
+    /// Removes the item `handle` refers to from the binary heap and returns it, or `None` if the
+    /// handle is stale
+    ///
+    /// ```
+    /// use heapless::binary_heap::{IndexedBinaryHeap, Min};
+    ///
+    /// let mut heap: IndexedBinaryHeap<_, Min, 8> = IndexedBinaryHeap::new();
+    /// heap.push(3).unwrap();
+    /// let one = heap.push(1).unwrap();
+    ///
+    /// assert_eq!(heap.remove(one), Some(1));
+    /// assert_eq!(heap.remove(one), None);
+    /// assert_eq!(heap.peek(), Some(&3));
+    /// ```
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        let pos = self.position(handle)?;
+        Some(self.remove_at(pos))
+    }
+
+    /// Replaces the item `handle` refers to with `new` and moves it to its new place in the
+    /// binary heap
+    ///
+    /// Returns the replaced item, or gives back `new` if the handle is stale. The handle stays
+    /// valid.
+    ///
+    /// ```
+    /// use heapless::binary_heap::{IndexedBinaryHeap, Min};
+    ///
+    /// let mut heap: IndexedBinaryHeap<_, Min, 8> = IndexedBinaryHeap::new();
+    /// heap.push(3).unwrap();
+    /// let five = heap.push(5).unwrap();
+    ///
+    /// // decrease-key
+    /// assert_eq!(heap.change_priority(five, 1), Ok(5));
+    /// assert_eq!(heap.peek(), Some(&1));
+    ///
+    /// heap.pop();
+    /// assert_eq!(heap.change_priority(five, 0), Err(0));
+    /// ```
+    pub fn change_priority(&mut self, handle: Handle, new: T) -> Result<T, T> {
+        let pos = match self.position(handle) {
+            Some(pos) => pos,
+            None => return Err(new),
+        };
+
+        let old = core::mem::replace(&mut self.data[pos].item, new);
+        let pos = self.sift_up(pos);
+        self.sift_down(pos);
+        Ok(old)
+    }
+
+    fn remove_at(&mut self, pos: usize) -> T {
+        let node = self.data.swap_remove(pos);
+
+        let slot = &mut self.slots[node.slot];
+        slot.generation = slot.generation.wrapping_add(1);
+        slot.index = self.free;
+        self.free = node.slot;
+
+        // restore the heap around the item that took its place
+        if pos < self.data.len() {
+            self.slots[self.data[pos].slot].index = pos;
+            let pos = self.sift_up(pos);
+            self.sift_down(pos);
+        }
+
+        node.item
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.data.swap(a, b);
+        self.slots[self.data[a].slot].index = a;
+        self.slots[self.data[b].slot].index = b;
+    }
+
+    fn greater(&self, a: usize, b: usize) -> bool {
+        self.cmp.compare(&self.data[a].item, &self.data[b].item) == Ordering::Greater
+    }
+
+    // moves the item at `pos` up until its parent is not smaller, returns its final position
+    fn sift_up(&mut self, mut pos: usize) -> usize {
+        while pos > 0 {
+            let parent = (pos - 1) / 2;
+            if !self.greater(pos, parent) {
+                break;
+            }
+            self.swap(pos, parent);
+            pos = parent;
+        }
+        pos
+    }
+
+    // moves the item at `pos` down until none of its children is greater
+    fn sift_down(&mut self, mut pos: usize) {
+        let end = self.data.len();
+        loop {
+            let mut child = 2 * pos + 1;
+            if child >= end {
+                break;
+            }
+            if child + 1 < end && self.greater(child + 1, child) {
+                child += 1;
+            }
+            if !self.greater(child, pos) {
+                break;
+            }
+            self.swap(pos, child);
+            pos = child;
+        }
+    }
+}
+
+impl<T, K, const N: usize> Clone for IndexedBinaryHeap<T, K, N>
+where
+    K: Clone,
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            cmp: self.cmp.clone(),
+            data: self.data.clone(),
+            slots: self.slots,
+            free: self.free,
+            fresh: self.fresh,
+        }
+    }
+}
+
+impl<T, K, const N: usize> Default for IndexedBinaryHeap<T, K, N>
+where
+    K: Default,
+{
+    fn default() -> Self {
+        Self::with_cmp(K::default())
+    }
+}
+
+impl<T, K, const N: usize> fmt::Debug for IndexedBinaryHeap<T, K, N>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.data.iter().map(|node| &node.item))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use super::IndexedBinaryHeap;
+    use crate::binary_heap::{Max, Min};
+
+    #[test]
+    fn static_new() {
+        static mut _H: IndexedBinaryHeap<i32, Min, 16> = IndexedBinaryHeap::new();
+    }
+
+    #[test]
+    fn push_pop() {
+        let mut heap = IndexedBinaryHeap::<_, Max, 8>::new();
+        for x in [3, 1, 4, 1, 5, 9, 2, 6] {
+            heap.push(x).unwrap();
+        }
+        assert_eq!(heap.push(5), Err(5));
+
+        let mut popped = Vec::new();
+        while let Some(x) = heap.pop() {
+            popped.push(x);
+        }
+        assert_eq!(popped, [9, 6, 5, 4, 3, 2, 1, 1]);
+    }
+
+    #[test]
+    fn change_priority() {
+        let mut heap = IndexedBinaryHeap::<_, Min, 8>::new();
+        let handles: Vec<_> = [50, 40, 30, 20, 10]
+            .iter()
+            .map(|&x| heap.push(x).unwrap())
+            .collect();
+
+        // decrease
+        assert_eq!(heap.change_priority(handles[0], 5), Ok(50));
+        assert_eq!(heap.peek_handle(), Some(handles[0]));
+        // increase
+        assert_eq!(heap.change_priority(handles[0], 60), Ok(5));
+        assert_eq!(heap.change_priority(handles[4], 35), Ok(10));
+        assert_eq!(heap.peek(), Some(&20));
+
+        assert!(handles.iter().all(|&handle| heap.contains(handle)));
+        assert_eq!(heap.get(handles[4]), Some(&35));
+
+        let mut popped = Vec::new();
+        while let Some(x) = heap.pop() {
+            popped.push(x);
+        }
+        assert_eq!(popped, [20, 30, 35, 40, 60]);
+    }
+
+    #[test]
+    fn stale_handles() {
+        let mut heap = IndexedBinaryHeap::<_, Max, 2>::new();
+        let a = heap.push('a').unwrap();
+        let b = heap.push('b').unwrap();
+
+        assert_eq!(heap.pop(), Some('b'));
+        assert!(!heap.contains(b));
+        assert_eq!(heap.get(b), None);
+
+        // `c` reuses the slot of `b`
+        let c = heap.push('c').unwrap();
+        assert_ne!(b, c);
+        assert_eq!(heap.get(b), None);
+        assert_eq!(heap.remove(b), None);
+        assert_eq!(heap.change_priority(b, 'z'), Err('z'));
+        assert_eq!(heap.get(c), Some(&'c'));
+
+        heap.clear();
+        assert!(heap.is_empty());
+        assert!(!heap.contains(a) && !heap.contains(c));
+
+        let d = heap.push('d').unwrap();
+        assert_ne!(d, a);
+        assert!(!heap.contains(a));
+        assert_eq!(heap.get(d), Some(&'d'));
+    }
+
+    #[test]
+    fn remove() {
+        let mut heap = IndexedBinaryHeap::<_, Max, 16>::new();
+        let mut live: Vec<_> = (0..16)
+            .map(|i| {
+                let x = (i * 7) % 16;
+                (heap.push(x).unwrap(), x)
+            })
+            .collect();
+
+        // remove from the middle, the top and the bottom of the heap
+        for i in [5, 0, 9, 3, 11, 6, 0, 1] {
+            let (handle, x) = live.remove(i % live.len());
+            assert_eq!(heap.remove(handle), Some(x));
+            assert!(!heap.contains(handle));
+
+            for &(handle, x) in &live {
+                assert_eq!(heap.get(handle), Some(&x));
+            }
+            assert_eq!(heap.peek(), live.iter().map(|(_, x)| x).max());
+        }
+
+        assert_eq!(heap.len(), live.len());
+        assert_eq!(heap.iter().count(), live.len());
+    }
+
+    #[test]
+    fn drop() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(PartialEq, Eq, PartialOrd, Ord)]
+        struct Droppable(u8);
+
+        impl Droppable {
+            fn new(x: u8) -> Self {
+                COUNT.fetch_add(1, Ordering::Relaxed);
+                Droppable(x)
+            }
+        }
+
+        impl Drop for Droppable {
+            fn drop(&mut self) {
+                COUNT.fetch_sub(1, Ordering::Relaxed);
+            }
+        }
+
+        {
+            let mut heap = IndexedBinaryHeap::<_, Max, 4>::new();
+            let handle = heap.push(Droppable::new(1)).ok().unwrap();
+            heap.push(Droppable::new(2)).ok().unwrap();
+            heap.push(Droppable::new(3)).ok().unwrap();
+            heap.change_priority(handle, Droppable::new(4))
+                .ok()
+                .unwrap();
+            heap.pop().unwrap();
+        }
+
+        assert_eq!(COUNT.load(Ordering::Relaxed), 0);
+    }
+}
@@ -0,0 +1,460 @@
+use core::{fmt, mem};
+
+use super::Hole;
+use crate::Vec;
+
+/// A double-ended priority queue implemented with a min-max heap
+///
+/// The levels of the heap alternate between min levels, whose items are smaller than all their
+/// descendants, and max levels, whose items are greater than all their descendants. The root is
+/// on a min level, so both the smallest and the greatest items are found in `O(1)` and removed in
+/// `O(log n)`.
+///
+/// It is a logic error for an item to be modified in such a way that the item's ordering relative
+/// to any other item, as determined by the `Ord` trait, changes while it is in the heap.
+///
+/// # Examples
+///
+/// A packet queue that serves the most important packet and, when full, drops the least
+/// important one:
+///
+/// ```
+/// use heapless::binary_heap::MinMaxHeap;
+///
+/// // (priority, id)
+/// let mut packets: MinMaxHeap<(u8, u16), 4> = MinMaxHeap::new();
+///
+/// for packet in [(2, 0), (7, 1), (1, 2), (5, 3), (3, 4)] {
+///     if let Some(dropped) = packets.push_pop_min(packet) {
+///         assert_eq!(dropped, (1, 2));
+///     }
+/// }
+///
+/// assert_eq!(packets.pop_max(), Some((7, 1)));
+/// assert_eq!(packets.peek_min(), Some(&(2, 0)));
+/// assert_eq!(packets.peek_max(), Some(&(5, 3)));
+/// ```
+pub struct MinMaxHeap<T, const N: usize> {
+    data: Vec<T, N>,
+}
+
+impl<T, const N: usize> MinMaxHeap<T, N> {
+    /// Creates an empty MinMaxHeap
+    ///
+    /// ```
+    /// use heapless::binary_heap::MinMaxHeap;
+    ///
+    /// // allocate the heap on the stack
+    /// let mut heap: MinMaxHeap<_, 8> = MinMaxHeap::new();
+    /// heap.push(4).unwrap();
+    ///
+    /// // allocate the heap in a static variable
+    /// static mut HEAP: MinMaxHeap<i32, 8> = MinMaxHeap::new();
+    /// ```
+    pub const fn new() -> Self {
+        Self { data: Vec::new() }
+    }
+
+    /// Returns the capacity of the heap
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the length of the heap
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Checks if the heap is empty
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Checks if the heap is full
+    pub fn is_full(&self) -> bool {
+        self.data.is_full()
+    }
+
+    /// Drops all items from the heap
+    pub fn clear(&mut self) {
+        self.data.clear()
+    }
+
+    /// Returns an iterator visiting all values in the underlying vector, in arbitrary order
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    /// Consumes the heap and returns the underlying vector, in arbitrary order
+    pub fn into_vec(self) -> Vec<T, N> {
+        self.data
+    }
+}
+
+impl<T, const N: usize> MinMaxHeap<T, N>
+where
+    T: Ord,
+{
+    /// Returns the smallest item in the heap, or `None` if it is empty
+    pub fn peek_min(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Returns the greatest item in the heap, or `None` if it is empty
+    pub fn peek_max(&self) -> Option<&T> {
+        self.max_index().map(|i| &self.data[i])
+    }
+
+    /// Pushes an item onto the heap
+    ///
+    /// Returns back the item if the heap is full.
+    ///
+    /// ```
+    /// use heapless::binary_heap::MinMaxHeap;
+    ///
+    /// let mut heap: MinMaxHeap<_, 8> = MinMaxHeap::new();
+    /// heap.push(3).unwrap();
+    /// heap.push(5).unwrap();
+    /// heap.push(1).unwrap();
+    ///
+    /// assert_eq!(heap.peek_min(), Some(&1));
+    /// assert_eq!(heap.peek_max(), Some(&5));
+    /// ```
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        self.data.push(item)?;
+        self.bubble_up(self.data.len() - 1);
+        Ok(())
+    }
+
+    /// Pushes an item onto the heap, evicting the smallest item if the heap is full
+    ///
+    /// Returns the evicted item, which is `item` itself if it is not greater than the smallest
+    /// item of a full heap.
+    ///
+    /// ```
+    /// use heapless::binary_heap::MinMaxHeap;
+    ///
+    /// let mut heap: MinMaxHeap<_, 2> = MinMaxHeap::new();
+    /// assert_eq!(heap.push_pop_min(3), None);
+    /// assert_eq!(heap.push_pop_min(5), None);
+    /// assert_eq!(heap.push_pop_min(4), Some(3));
+    /// assert_eq!(heap.push_pop_min(1), Some(1));
+    /// ```
+    pub fn push_pop_min(&mut self, item: T) -> Option<T> {
+        if !self.data.is_full() {
+            // NOTE(unwrap) the heap is not full
+            self.push(item).ok().unwrap();
+            return None;
+        }
+
+        match self.data.first_mut() {
+            Some(min) if item > *min => {
+                let min = mem::replace(min, item);
+                self.trickle_down(0);
+                Some(min)
+            }
+            _ => Some(item),
+        }
+    }
+
+    /// Removes the smallest item from the heap and returns it, or `None` if it is empty
+    ///
+    /// ```
+    /// use heapless::binary_heap::MinMaxHeap;
+    ///
+    /// let mut heap: MinMaxHeap<_, 8> = MinMaxHeap::new();
+    /// heap.push(1).unwrap();
+    /// heap.push(3).unwrap();
+    ///
+    /// assert_eq!(heap.pop_min(), Some(1));
+    /// assert_eq!(heap.pop_min(), Some(3));
+    /// assert_eq!(heap.pop_min(), None);
+    /// ```
+    pub fn pop_min(&mut self) -> Option<T> {
+        self.remove_at(0)
+    }
+
+    /// Removes the greatest item from the heap and returns it, or `None` if it is empty
+    ///
+    /// ```
+    /// use heapless::binary_heap::MinMaxHeap;
+    ///
+    /// let mut heap: MinMaxHeap<_, 8> = MinMaxHeap::new();
+    /// heap.push(1).unwrap();
+    /// heap.push(3).unwrap();
+    ///
+    /// assert_eq!(heap.pop_max(), Some(3));
+    /// assert_eq!(heap.pop_max(), Some(1));
+    /// assert_eq!(heap.pop_max(), None);
+    /// ```
+    pub fn pop_max(&mut self) -> Option<T> {
+        self.remove_at(self.max_index()?)
+    }
+
+    fn max_index(&self) -> Option<usize> {
+        match self.data.len() {
+            0 => None,
+            1 => Some(0),
+            2 => Some(1),
+            _ => Some(if self.data[2] > self.data[1] { 2 } else { 1 }),
+        }
+    }
+
+    // removes the item at `pos`, which must be on one of the first two levels
+    fn remove_at(&mut self, pos: usize) -> Option<T> {
+        let mut item = self.data.pop()?;
+
+        if pos < self.data.len() {
+            mem::swap(&mut item, &mut self.data[pos]);
+            self.trickle_down(pos);
+        }
+        Some(item)
+    }
+
+    /* Private API */
+    fn bubble_up(&mut self, pos: usize) {
+        if pos == 0 {
+            return;
+        }
+
+        unsafe {
+            // Take out the value at `pos` and create a hole.
+            let mut hole = Hole::new(self.data.as_mut_slice(), pos);
+
+            // an item that belongs to the other kind of level than the one of `pos` goes to the
+            // parent's
+            let mut min = is_min_level(pos);
+            let parent = (pos - 1) / 2;
+            if before(!min, hole.element(), hole.get(parent)) {
+                hole.move_to(parent);
+                min = !min;
+            }
+
+            // then climbs the levels of its kind
+            while hole.pos() > 2 {
+                let grandparent = ((hole.pos() - 1) / 2 - 1) / 2;
+                if !before(min, hole.element(), hole.get(grandparent)) {
+                    break;
+                }
+                hole.move_to(grandparent);
+            }
+        }
+    }
+
+    fn trickle_down(&mut self, mut pos: usize) {
+        let end = self.len();
+        let min = is_min_level(pos);
+
+        loop {
+            unsafe {
+                // Take out the value at `pos` and create a hole.
+                let mut hole = Hole::new(self.data.as_mut_slice(), pos);
+
+                loop {
+                    let child = 2 * hole.pos() + 1;
+                    if child >= end {
+                        return;
+                    }
+
+                    // the first among the children and grandchildren, which are contiguous
+                    let grandchildren = 2 * child + 1..(2 * child + 5).min(end);
+                    let mut next = child;
+                    for i in (child + 1..(child + 2).min(end)).chain(grandchildren) {
+                        if before(min, hole.get(i), hole.get(next)) {
+                            next = i;
+                        }
+                    }
+
+                    if !before(min, hole.get(next), hole.element()) {
+                        return;
+                    }
+                    hole.move_to(next);
+
+                    // a child has no descendants on a level of the same kind
+                    if next <= child + 1 {
+                        return;
+                    }
+
+                    // the item may belong to the level of its new parent, in which case the
+                    // parent's item comes down and is the one that keeps trickling
+                    let parent = (next - 1) / 2;
+                    if before(!min, hole.element(), hole.get(parent)) {
+                        hole.move_to(parent);
+                        pos = next;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+// whether `a` comes before `b` on a min level, or on a max level
+fn before<T>(min: bool, a: &T, b: &T) -> bool
+where
+    T: Ord,
+{
+    if min {
+        a < b
+    } else {
+        a > b
+    }
+}
+
+fn is_min_level(pos: usize) -> bool {
+    // the depth of `pos` is `usize::BITS - 1 - (pos + 1).leading_zeros()`, with an even number of
+    // bits in a `usize`
+    (pos + 1).leading_zeros() & 1 == 1
+}
+
+impl<T, const N: usize> Clone for MinMaxHeap<T, N>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl<T, const N: usize> Default for MinMaxHeap<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for MinMaxHeap<T, N>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.data.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use super::{is_min_level, MinMaxHeap};
+
+    #[test]
+    fn static_new() {
+        static mut _H: MinMaxHeap<i32, 16> = MinMaxHeap::new();
+    }
+
+    #[test]
+    fn levels() {
+        let levels: Vec<_> = (0..16).map(is_min_level).collect();
+        assert_eq!(
+            levels,
+            [
+                true, false, false, true, true, true, true, false, false, false, false, false,
+                false, false, false, true
+            ]
+        );
+    }
+
+    fn is_min_max_heap(heap: &MinMaxHeap<u32, 64>) -> bool {
+        let data = heap.data.as_slice();
+        (1..data.len()).all(|i| {
+            // every item is within the bounds set by its ancestors
+            let mut ancestor = i;
+            while ancestor > 0 {
+                ancestor = (ancestor - 1) / 2;
+                let ok = if is_min_level(ancestor) {
+                    data[ancestor] <= data[i]
+                } else {
+                    data[ancestor] >= data[i]
+                };
+                if !ok {
+                    return false;
+                }
+            }
+            true
+        })
+    }
+
+    #[test]
+    fn random() {
+        let mut heap = MinMaxHeap::<u32, 64>::new();
+        let mut model = Vec::new();
+        let mut state = 0x2545_f491_u32;
+
+        for _ in 0..2000 {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            let x = state % 100;
+
+            match state >> 30 {
+                0 => assert_eq!(
+                    heap.pop_min(),
+                    model.first().copied().map(|_| model.remove(0))
+                ),
+                1 => assert_eq!(heap.pop_max(), model.pop()),
+                _ => {
+                    let evicted = heap.push_pop_min(x);
+                    let pos = model.partition_point(|&y| y < x);
+                    model.insert(pos, x);
+                    if model.len() > 64 {
+                        assert_eq!(evicted, Some(model.remove(0)));
+                    } else {
+                        assert_eq!(evicted, None);
+                    }
+                }
+            }
+
+            assert!(is_min_max_heap(&heap));
+            assert_eq!(heap.len(), model.len());
+            assert_eq!(heap.peek_min(), model.first());
+            assert_eq!(heap.peek_max(), model.last());
+        }
+    }
+
+    #[test]
+    fn push_full() {
+        let mut heap = MinMaxHeap::<_, 2>::new();
+        heap.push(1).unwrap();
+        heap.push(2).unwrap();
+        assert_eq!(heap.push(3), Err(3));
+        assert!(heap.is_full());
+    }
+
+    #[test]
+    fn drop() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(PartialEq, Eq, PartialOrd, Ord)]
+        struct Droppable(u8);
+
+        impl Droppable {
+            fn new(x: u8) -> Self {
+                COUNT.fetch_add(1, Ordering::Relaxed);
+                Droppable(x)
+            }
+        }
+
+        impl Drop for Droppable {
+            fn drop(&mut self) {
+                COUNT.fetch_sub(1, Ordering::Relaxed);
+            }
+        }
+
+        {
+            let mut heap = MinMaxHeap::<_, 4>::new();
+            for x in [3, 1, 4, 1] {
+                heap.push(Droppable::new(x)).ok().unwrap();
+            }
+            heap.push_pop_min(Droppable::new(5)).unwrap();
+            heap.push_pop_min(Droppable::new(0)).unwrap();
+            heap.pop_max().unwrap();
+            heap.pop_min().unwrap();
+        }
+
+        assert_eq!(COUNT.load(Ordering::Relaxed), 0);
+    }
+}
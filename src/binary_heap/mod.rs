@@ -0,0 +1,1550 @@
+//! A priority queue implemented with a binary heap.
+//!
+//! Insertion and popping the largest element have `O(log n)` time complexity. Checking the largest
+//! / smallest element is `O(1)`.
+//!
+//! Converting a vector to a binary heap can be done in-place, and has `O(n)` complexity. A binary
+//! heap can also be converted to a sorted vector in-place, allowing it to be used for an `O(n log
+//! n)` in-place heapsort.
+
+use core::{
+    cmp::Ordering,
+    fmt,
+    iter::FusedIterator,
+    marker::PhantomData,
+    mem::{self, ManuallyDrop},
+    ops::{Deref, DerefMut},
+    ptr, slice,
+};
+
+use crate::sealed::binary_heap::Kind;
+use crate::vec::{self, Vec};
+use crate::{CapacityError, TryFromIterator};
+
+pub use indexed::{Handle, IndexedBinaryHeap};
+pub use min_max::MinMaxHeap;
+pub use stable::StableBinaryHeap;
+
+mod indexed;
+mod min_max;
+mod stable;
+
+/// Min-heap
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Min;
+
+/// Max-heap
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Max;
+
+/// The ordering of the items in a [`BinaryHeap`]
+///
+/// The heap keeps the *greatest* item according to `compare` at the top. [`Max`] and [`Min`]
+/// order the items by their `Ord` implementation; any `Fn(&T, &T) -> Ordering`, including `fn`
+/// pointers, and [`ByKey`] are comparators as well.
+///
+/// A comparator that isn't a total order doesn't cause undefined behavior, but leaves the items
+/// in an unspecified order.
+///
+/// # Examples
+///
+/// A zero-sized comparator, usable in a `static`:
+///
+/// ```
+/// use core::cmp::Ordering;
+/// use heapless::binary_heap::{BinaryHeap, Compare};
+///
+/// struct Task {
+///     deadline: u32,
+///     id: u8,
+/// }
+///
+/// // earliest deadline first
+/// struct EarliestDeadline;
+///
+/// impl Compare<Task> for EarliestDeadline {
+///     fn compare(&self, a: &Task, b: &Task) -> Ordering {
+///         b.deadline.cmp(&a.deadline)
+///     }
+/// }
+///
+/// static mut TASKS: BinaryHeap<Task, EarliestDeadline, 8> = BinaryHeap::with_cmp(EarliestDeadline);
+///
+/// let tasks = unsafe { &mut TASKS };
+/// tasks.push(Task { deadline: 30, id: 0 }).ok().unwrap();
+/// tasks.push(Task { deadline: 10, id: 1 }).ok().unwrap();
+/// tasks.push(Task { deadline: 20, id: 2 }).ok().unwrap();
+///
+/// assert_eq!(tasks.pop().map(|task| task.id), Some(1));
+/// ```
+pub trait Compare<T: ?Sized> {
+    /// Compares two items; the greater one comes out of the heap first
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+}
+
+impl<T> Compare<T> for Max
+where
+    T: Ord + ?Sized,
+{
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+impl<T> Compare<T> for Min
+where
+    T: Ord + ?Sized,
+{
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        b.cmp(a)
+    }
+}
+
+impl<T, F> Compare<T> for F
+where
+    T: ?Sized,
+    F: Fn(&T, &T) -> Ordering,
+{
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self(a, b)
+    }
+}
+
+/// A comparator that orders the items by a key, the greatest key coming out of the heap first
+///
+/// Wrap the key in [`Reverse`](core::cmp::Reverse) to pop the smallest key first.
+///
+/// # Examples
+///
+/// ```
+/// use core::cmp::Reverse;
+/// use heapless::binary_heap::{BinaryHeap, ByKey};
+///
+/// struct Timer {
+///     expires_at: u64,
+/// }
+///
+/// fn expires_at(timer: &Timer) -> Reverse<u64> {
+///     Reverse(timer.expires_at)
+/// }
+///
+/// static mut TIMERS: BinaryHeap<Timer, ByKey<fn(&Timer) -> Reverse<u64>>, 4> =
+///     BinaryHeap::with_cmp(ByKey(expires_at));
+///
+/// let timers = unsafe { &mut TIMERS };
+/// timers.push(Timer { expires_at: 200 }).ok().unwrap();
+/// timers.push(Timer { expires_at: 100 }).ok().unwrap();
+///
+/// assert_eq!(timers.peek().map(|timer| timer.expires_at), Some(100));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ByKey<F>(pub F);
+
+impl<T, F, K> Compare<T> for ByKey<F>
+where
+    F: Fn(&T) -> K,
+    K: Ord,
+{
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        (self.0)(a).cmp(&(self.0)(b))
+    }
+}
+
+/// A priority queue implemented with a binary heap.
+///
+/// This can be either a min-heap or a max-heap, or use any other [`Compare`]ator.
+///
+/// It is a logic error for an item to be modified in such a way that the item's ordering relative
+/// to any other item, as determined by the comparator, changes while it is in the heap. This is
+/// normally only possible through `Cell`, `RefCell`, global state, I/O, or unsafe code.
+///
+/// ```
+/// use heapless::binary_heap::{BinaryHeap, Max};
+///
+/// let mut heap: BinaryHeap<_, Max, 8> = BinaryHeap::new();
+///
+/// // We can use peek to look at the next item in the heap. In this case,
+/// // there's no items in there yet so we get None.
+/// assert_eq!(heap.peek(), None);
+///
+/// // Let's add some scores...
+/// heap.push(1).unwrap();
+/// heap.push(5).unwrap();
+/// heap.push(2).unwrap();
+///
+/// // Now peek shows the most important item in the heap.
+/// assert_eq!(heap.peek(), Some(&5));
+///
+/// // We can check the length of a heap.
+/// assert_eq!(heap.len(), 3);
+///
+/// // We can iterate over the items in the heap, although they are returned in
+/// // a random order.
+/// for x in &heap {
+///     println!("{}", x);
+/// }
+///
+/// // If we instead pop these scores, they should come back in order.
+/// assert_eq!(heap.pop(), Some(5));
+/// assert_eq!(heap.pop(), Some(2));
+/// assert_eq!(heap.pop(), Some(1));
+/// assert_eq!(heap.pop(), None);
+///
+/// // We can clear the heap of any remaining items.
+/// heap.clear();
+///
+/// // The heap should now be empty.
+/// assert!(heap.is_empty())
+/// ```
+
+pub struct BinaryHeap<T, K, const N: usize> {
+    pub(crate) cmp: K,
+    pub(crate) data: Vec<T, N>,
+}
+
+impl<T, K, const N: usize> BinaryHeap<T, K, N>
+where
+    PhantomData<K>: Kind<K>,
+{
+    /* Constructors */
+    /// Creates an empty BinaryHeap as a $K-heap.
+    ///
+    /// ```
+    /// use heapless::binary_heap::{BinaryHeap, Max};
+    ///
+    /// // allocate the binary heap on the stack
+    /// let mut heap: BinaryHeap<_, Max, 8> = BinaryHeap::new();
+    /// heap.push(4).unwrap();
+    ///
+    /// // allocate the binary heap in a static variable
+    /// static mut HEAP: BinaryHeap<i32, Max, 8> = BinaryHeap::new();
+    /// ```
+    pub const fn new() -> Self {
+        Self::with_cmp(<PhantomData<K> as Kind<K>>::INSTANCE)
+    }
+}
+
+impl<T, K, const N: usize> BinaryHeap<T, K, N> {
+    /// Creates an empty BinaryHeap that orders its items with the comparator `cmp`
+    ///
+    /// See [`Compare`] for examples.
+    pub const fn with_cmp(cmp: K) -> Self {
+        Self {
+            cmp,
+            data: Vec::new(),
+        }
+    }
+
+    /// Creates a BinaryHeap that orders the items of `vec` with the comparator `cmp`, in `O(n)`
+    /// time
+    pub fn from_vec_with_cmp(vec: Vec<T, N>, cmp: K) -> Self
+    where
+        K: Compare<T>,
+    {
+        let mut heap = Self { cmp, data: vec };
+        heap.rebuild();
+        heap
+    }
+
+    /// Returns the comparator of the binary heap
+    pub fn cmp(&self) -> &K {
+        &self.cmp
+    }
+}
+
+impl<T, K, const N: usize> BinaryHeap<T, K, N>
+where
+    K: Compare<T>,
+{
+    /* Public API */
+    /// Returns the capacity of the binary heap.
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    /// Drops all items from the binary heap.
+    ///
+    /// ```
+    /// use heapless::binary_heap::{BinaryHeap, Max};
+    ///
+    /// let mut heap: BinaryHeap<_, Max, 8> = BinaryHeap::new();
+    /// heap.push(1).unwrap();
+    /// heap.push(3).unwrap();
+    ///
+    /// assert!(!heap.is_empty());
+    ///
+    /// heap.clear();
+    ///
+    /// assert!(heap.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        self.data.clear()
+    }
+
+    /// Returns the length of the binary heap.
+    ///
+    /// ```
+    /// use heapless::binary_heap::{BinaryHeap, Max};
+    ///
+    /// let mut heap: BinaryHeap<_, Max, 8> = BinaryHeap::new();
+    /// heap.push(1).unwrap();
+    /// heap.push(3).unwrap();
+    ///
+    /// assert_eq!(heap.len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Checks if the binary heap is empty.
+    ///
+    /// ```
+    /// use heapless::binary_heap::{BinaryHeap, Max};
+    ///
+    /// let mut heap: BinaryHeap<_, Max, 8> = BinaryHeap::new();
+    ///
+    /// assert!(heap.is_empty());
+    ///
+    /// heap.push(3).unwrap();
+    /// heap.push(5).unwrap();
+    /// heap.push(1).unwrap();
+    ///
+    /// assert!(!heap.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator visiting all values in the underlying vector, in arbitrary order.
+    ///
+    /// ```
+    /// use heapless::binary_heap::{BinaryHeap, Max};
+    ///
+    /// let mut heap: BinaryHeap<_, Max, 8> = BinaryHeap::new();
+    /// heap.push(1).unwrap();
+    /// heap.push(2).unwrap();
+    /// heap.push(3).unwrap();
+    /// heap.push(4).unwrap();
+    ///
+    /// // Print 1, 2, 3, 4 in arbitrary order
+    /// for x in heap.iter() {
+    ///     println!("{}", x);
+    ///
+    /// }
+    /// ```
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.data.as_slice().iter()
+    }
+
+    /// Returns a mutable iterator visiting all values in the underlying vector, in arbitrary order.
+    ///
+    /// **WARNING** Mutating the items in the binary heap can leave the heap in an inconsistent
+    /// state.
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.data.as_mut_slice().iter_mut()
+    }
+
+    /// Returns the *top* (greatest if max-heap, smallest if min-heap) item in the binary heap, or
+    /// None if it is empty.
+    ///
+    /// ```
+    /// use heapless::binary_heap::{BinaryHeap, Max};
+    ///
+    /// let mut heap: BinaryHeap<_, Max, 8> = BinaryHeap::new();
+    /// assert_eq!(heap.peek(), None);
+    ///
+    /// heap.push(1).unwrap();
+    /// heap.push(5).unwrap();
+    /// heap.push(2).unwrap();
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    pub fn peek(&self) -> Option<&T> {
+        self.data.as_slice().get(0)
+    }
+
+    /// Returns a mutable reference to the greatest item in the binary heap, or
+    /// `None` if it is empty.
+    ///
+    /// Note: If the `PeekMut` value is leaked, the heap may be in an
+    /// inconsistent state.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use heapless::binary_heap::{BinaryHeap, Max};
+    ///
+    /// let mut heap: BinaryHeap<_, Max, 8> = BinaryHeap::new();
+    /// assert!(heap.peek_mut().is_none());
+    ///
+    /// heap.push(1);
+    /// heap.push(5);
+    /// heap.push(2);
+    /// {
+    ///     let mut val = heap.peek_mut().unwrap();
+    ///     *val = 0;
+    /// }
+    ///
+    /// assert_eq!(heap.peek(), Some(&2));
+    /// ```
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T, K, N>> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(PeekMut {
+                heap: self,
+                sift: true,
+            })
+        }
+    }
+
+    /// Removes the *top* (greatest if max-heap, smallest if min-heap) item from the binary heap and
+    /// returns it, or None if it is empty.
+    ///
+    /// ```
+    /// use heapless::binary_heap::{BinaryHeap, Max};
+    ///
+    /// let mut heap: BinaryHeap<_, Max, 8> = BinaryHeap::new();
+    /// heap.push(1).unwrap();
+    /// heap.push(3).unwrap();
+    ///
+    /// assert_eq!(heap.pop(), Some(3));
+    /// assert_eq!(heap.pop(), Some(1));
+    /// assert_eq!(heap.pop(), None);
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(unsafe { self.pop_unchecked() })
+        }
+    }
+
+    /// Removes the *top* (greatest if max-heap, smallest if min-heap) item from the binary heap and
+    /// returns it, without checking if the binary heap is empty.
+    pub unsafe fn pop_unchecked(&mut self) -> T {
+        let mut item = self.data.pop_unchecked();
+
+        if !self.is_empty() {
+            mem::swap(&mut item, self.data.as_mut_slice().get_unchecked_mut(0));
+            self.sift_down_to_bottom(0);
+        }
+        item
+    }
+
+    /// Pushes an item onto the binary heap.
+    ///
+    /// ```
+    /// use heapless::binary_heap::{BinaryHeap, Max};
+    ///
+    /// let mut heap: BinaryHeap<_, Max, 8> = BinaryHeap::new();
+    /// heap.push(3).unwrap();
+    /// heap.push(5).unwrap();
+    /// heap.push(1).unwrap();
+    ///
+    /// assert_eq!(heap.len(), 3);
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        if self.data.is_full() {
+            return Err(item);
+        }
+
+        unsafe { self.push_unchecked(item) }
+        Ok(())
+    }
+
+    /// Pushes an item onto the binary heap without first checking if it's full.
+    pub unsafe fn push_unchecked(&mut self, item: T) {
+        let old_len = self.len();
+        self.data.push_unchecked(item);
+        self.sift_up(0, old_len);
+    }
+
+    /// Pushes all the items of an iterator onto the binary heap, stopping at the first one that
+    /// doesn't fit
+    ///
+    /// On error the items that were pushed stay in the heap and the rejected item is returned
+    /// inside the error; the rest of the iterator is not consumed.
+    ///
+    /// ```
+    /// use heapless::binary_heap::{BinaryHeap, Max};
+    ///
+    /// let mut heap: BinaryHeap<_, Max, 4> = BinaryHeap::new();
+    /// heap.try_extend([3, 1, 4]).unwrap();
+    ///
+    /// let err = heap.try_extend([1, 5, 9]).unwrap_err();
+    /// assert_eq!(err.into_inner(), 5);
+    /// assert_eq!(heap.into_sorted_vec(), [1, 1, 3, 4]);
+    /// ```
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), CapacityError<T>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let guard = RebuildOnDrop {
+            rebuild_from: self.len(),
+            heap: self,
+        };
+
+        for item in iter {
+            guard.heap.data.push(item).map_err(CapacityError::new)?;
+        }
+
+        Ok(())
+    }
+
+    /// Moves all the items of `other` into this binary heap, leaving `other` empty
+    ///
+    /// Returns an error, and leaves both heaps untouched, if the items don't fit.
+    ///
+    /// ```
+    /// use heapless::binary_heap::{BinaryHeap, Max};
+    ///
+    /// let mut a: BinaryHeap<_, Max, 8> = BinaryHeap::new();
+    /// a.try_extend([3, 1]).unwrap();
+    /// let mut b: BinaryHeap<_, Max, 4> = BinaryHeap::new();
+    /// b.try_extend([5, 2, 4]).unwrap();
+    ///
+    /// a.append(&mut b).unwrap();
+    ///
+    /// assert!(b.is_empty());
+    /// assert_eq!(a.into_sorted_vec(), [1, 2, 3, 4, 5]);
+    /// ```
+    pub fn append<const M: usize>(
+        &mut self,
+        other: &mut BinaryHeap<T, K, M>,
+    ) -> Result<(), CapacityError> {
+        if self.len() + other.len() > self.capacity() {
+            return Err(CapacityError::new(()));
+        }
+
+        let guard = RebuildOnDrop {
+            rebuild_from: self.len(),
+            heap: self,
+        };
+
+        for item in other.data.drain(..) {
+            // NOTE(unsafe) we checked above that all the items fit
+            unsafe { guard.heap.data.push_unchecked(item) }
+        }
+
+        Ok(())
+    }
+
+    /// Retains only the items specified by the predicate
+    ///
+    /// In other words, removes all the items `e` for which `f(&e)` returns `false`. The items are
+    /// visited in arbitrary order.
+    ///
+    /// ```
+    /// use heapless::binary_heap::{BinaryHeap, Max};
+    ///
+    /// let mut heap: BinaryHeap<_, Max, 8> = BinaryHeap::new();
+    /// heap.try_extend([-10, -5, 1, 2, 4, 13]).unwrap();
+    ///
+    /// heap.retain(|x| x % 2 == 0);
+    ///
+    /// assert_eq!(heap.into_sorted_vec(), [-10, 2, 4]);
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let len = self.len();
+        let mut guard = RebuildOnDrop {
+            rebuild_from: len,
+            heap: self,
+        };
+        let RebuildOnDrop { heap, rebuild_from } = &mut guard;
+
+        // only the items after the first removed one have moved
+        let mut i = 0;
+        heap.data.retain(|item| {
+            let keep = f(item);
+            if !keep && i < *rebuild_from {
+                *rebuild_from = i;
+            }
+            i += 1;
+            keep
+        });
+    }
+
+    /// Clears the binary heap, returning an iterator over the removed items
+    ///
+    /// The items are removed in arbitrary order; the heap is empty once the iterator is dropped.
+    ///
+    /// ```
+    /// use heapless::binary_heap::{BinaryHeap, Max};
+    ///
+    /// let mut heap: BinaryHeap<_, Max, 8> = BinaryHeap::new();
+    /// heap.try_extend([1, 3]).unwrap();
+    ///
+    /// for x in heap.drain() {
+    ///     println!("{}", x);
+    /// }
+    ///
+    /// assert!(heap.is_empty());
+    /// ```
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        Drain {
+            iter: self.data.drain(..),
+        }
+    }
+
+    /// Clears the binary heap, returning an iterator over the removed items in heap order
+    ///
+    /// That is from the greatest to the smallest for a max-heap, and from the smallest to the
+    /// greatest for a min-heap. The remaining items are removed when the iterator is dropped.
+    ///
+    /// ```
+    /// use heapless::binary_heap::{BinaryHeap, Min};
+    ///
+    /// let mut heap: BinaryHeap<_, Min, 8> = BinaryHeap::new();
+    /// heap.try_extend([4, 1, 3, 2]).unwrap();
+    ///
+    /// let mut drain = heap.drain_sorted();
+    /// assert_eq!(drain.next(), Some(1));
+    /// assert_eq!(drain.next(), Some(2));
+    /// drop(drain);
+    ///
+    /// assert!(heap.is_empty());
+    /// ```
+    pub fn drain_sorted(&mut self) -> DrainSorted<'_, T, K, N> {
+        DrainSorted { heap: self }
+    }
+
+    /// Consumes the binary heap and returns the underlying vector, in arbitrary order
+    ///
+    /// ```
+    /// use heapless::binary_heap::{BinaryHeap, Max};
+    ///
+    /// let mut heap: BinaryHeap<_, Max, 8> = BinaryHeap::new();
+    /// heap.try_extend([1, 2, 3]).unwrap();
+    ///
+    /// let vec = heap.into_vec();
+    /// assert_eq!(vec.len(), 3);
+    /// ```
+    pub fn into_vec(self) -> Vec<T, N> {
+        self.data
+    }
+
+    /// Consumes the binary heap and returns a vector of its items in ascending order according to
+    /// the comparator, which is the reverse of the order `pop` would return them in
+    ///
+    /// This is an in-place heapsort, in `O(n log n)` time.
+    ///
+    /// ```
+    /// use heapless::binary_heap::{BinaryHeap, Max, Min};
+    ///
+    /// let mut heap: BinaryHeap<_, Max, 8> = BinaryHeap::new();
+    /// heap.try_extend([1, 4, 2, 3]).unwrap();
+    /// assert_eq!(heap.into_sorted_vec(), [1, 2, 3, 4]);
+    ///
+    /// let mut heap: BinaryHeap<_, Min, 8> = BinaryHeap::new();
+    /// heap.try_extend([1, 4, 2, 3]).unwrap();
+    /// assert_eq!(heap.into_sorted_vec(), [4, 3, 2, 1]);
+    /// ```
+    pub fn into_sorted_vec(mut self) -> Vec<T, N> {
+        let mut end = self.len();
+        while end > 1 {
+            end -= 1;
+            // move the top item to the end and restore the heap on the items before it
+            self.data.swap(0, end);
+            self.sift_down_range(0, end);
+        }
+
+        self.into_vec()
+    }
+
+    /* Private API */
+    fn sift_down_to_bottom(&mut self, mut pos: usize) {
+        let end = self.len();
+        let start = pos;
+        unsafe {
+            let mut hole = Hole::new(self.data.as_mut_slice(), pos);
+            let mut child = 2 * pos + 1;
+            while child < end {
+                let right = child + 1;
+                // compare with the greater of the two children
+                if right < end
+                    && self.cmp.compare(hole.get(child), hole.get(right)) != Ordering::Greater
+                {
+                    child = right;
+                }
+                hole.move_to(child);
+                child = 2 * hole.pos() + 1;
+            }
+            pos = hole.pos;
+        }
+        self.sift_up(start, pos);
+    }
+
+    // moves the item at `pos` down until the items in `pos..end` form a heap again
+    fn sift_down_range(&mut self, pos: usize, end: usize) {
+        unsafe {
+            let mut hole = Hole::new(self.data.as_mut_slice(), pos);
+            let mut child = 2 * hole.pos() + 1;
+            while child < end {
+                let right = child + 1;
+                // compare with the greater of the two children
+                if right < end
+                    && self.cmp.compare(hole.get(child), hole.get(right)) != Ordering::Greater
+                {
+                    child = right;
+                }
+                // stop if the item is already in order
+                if self.cmp.compare(hole.get(child), hole.element()) != Ordering::Greater {
+                    break;
+                }
+                hole.move_to(child);
+                child = 2 * hole.pos() + 1;
+            }
+        }
+    }
+
+    // turns the whole vector into a heap, in `O(n)`
+    fn rebuild(&mut self) {
+        let len = self.len();
+        let mut n = len / 2;
+        while n > 0 {
+            n -= 1;
+            self.sift_down_range(n, len);
+        }
+    }
+
+    // restores the heap after items were added or moved starting at `start`
+    fn rebuild_tail(&mut self, start: usize) {
+        let len = self.len();
+        if start >= len {
+            return;
+        }
+
+        // sifting each new item up costs `O(tail_len * log(start))` while rebuilding costs `O(len)`
+        let tail_len = len - start;
+        let better_to_rebuild = start < tail_len || {
+            let log2_start = mem::size_of::<usize>() * 8 - start.leading_zeros() as usize - 1;
+            2 * len < tail_len * log2_start
+        };
+
+        if better_to_rebuild {
+            self.rebuild();
+        } else {
+            for i in start..len {
+                self.sift_up(0, i);
+            }
+        }
+    }
+
+    fn sift_up(&mut self, start: usize, pos: usize) -> usize {
+        unsafe {
+            // Take out the value at `pos` and create a hole.
+            let mut hole = Hole::new(self.data.as_mut_slice(), pos);
+
+            while hole.pos() > start {
+                let parent = (hole.pos() - 1) / 2;
+                if self.cmp.compare(hole.element(), hole.get(parent)) != Ordering::Greater {
+                    break;
+                }
+                hole.move_to(parent);
+            }
+            hole.pos()
+        }
+    }
+}
+
+// restores the heap on drop, so that it stays consistent even if a closure or an iterator panics
+struct RebuildOnDrop<'a, T, K, const N: usize>
+where
+    K: Compare<T>,
+{
+    heap: &'a mut BinaryHeap<T, K, N>,
+    rebuild_from: usize,
+}
+
+impl<T, K, const N: usize> Drop for RebuildOnDrop<'_, T, K, N>
+where
+    K: Compare<T>,
+{
+    fn drop(&mut self) {
+        self.heap.rebuild_tail(self.rebuild_from);
+    }
+}
+
+/// Hole represents a hole in a slice i.e. an index without valid value
+/// (because it was moved from or duplicated).
+/// In drop, `Hole` will restore the slice by filling the hole
+/// position with the value that was originally removed.
+struct Hole<'a, T> {
+    data: &'a mut [T],
+    /// `elt` is always `Some` from new until drop.
+    elt: ManuallyDrop<T>,
+    pos: usize,
+}
+
+impl<'a, T> Hole<'a, T> {
+    /// Create a new Hole at index `pos`.
+    ///
+    /// Unsafe because pos must be within the data slice.
+    #[inline]
+    unsafe fn new(data: &'a mut [T], pos: usize) -> Self {
+        debug_assert!(pos < data.len());
+        let elt = ptr::read(data.get_unchecked(pos));
+        Hole {
+            data,
+            elt: ManuallyDrop::new(elt),
+            pos,
+        }
+    }
+
+    #[inline]
+    fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns a reference to the element removed.
+    #[inline]
+    fn element(&self) -> &T {
+        &self.elt
+    }
+
+    /// Returns a reference to the element at `index`.
+    ///
+    /// Unsafe because index must be within the data slice and not equal to pos.
+    #[inline]
+    unsafe fn get(&self, index: usize) -> &T {
+        debug_assert!(index != self.pos);
+        debug_assert!(index < self.data.len());
+        self.data.get_unchecked(index)
+    }
+
+    /// Move hole to new location
+    ///
+    /// Unsafe because index must be within the data slice and not equal to pos.
+    #[inline]
+    unsafe fn move_to(&mut self, index: usize) {
+        debug_assert!(index != self.pos);
+        debug_assert!(index < self.data.len());
+        // both pointers must derive from the same borrow of `data`
+        let ptr = self.data.as_mut_ptr();
+        let index_ptr: *const _ = ptr.add(index);
+        let hole_ptr = ptr.add(self.pos);
+        ptr::copy_nonoverlapping(index_ptr, hole_ptr, 1);
+        self.pos = index;
+    }
+}
+
+/// Structure wrapping a mutable reference to the greatest item on a
+/// `BinaryHeap`.
+///
+/// This `struct` is created by the [`peek_mut`] method on [`BinaryHeap`]. See
+/// its documentation for more.
+///
+/// [`peek_mut`]: struct.BinaryHeap.html#method.peek_mut
+/// [`BinaryHeap`]: struct.BinaryHeap.html
+pub struct PeekMut<'a, T, K, const N: usize>
+where
+    K: Compare<T>,
+{
+    heap: &'a mut BinaryHeap<T, K, N>,
+    sift: bool,
+}
+
+impl<T, K, const N: usize> Drop for PeekMut<'_, T, K, N>
+where
+    K: Compare<T>,
+{
+    fn drop(&mut self) {
+        if self.sift {
+            self.heap.sift_down_to_bottom(0);
+        }
+    }
+}
+
+impl<T, K, const N: usize> Deref for PeekMut<'_, T, K, N>
+where
+    K: Compare<T>,
+{
+    type Target = T;
+    fn deref(&self) -> &T {
+        debug_assert!(!self.heap.is_empty());
+        // SAFE: PeekMut is only instantiated for non-empty heaps
+        unsafe { self.heap.data.as_slice().get_unchecked(0) }
+    }
+}
+
+impl<T, K, const N: usize> DerefMut for PeekMut<'_, T, K, N>
+where
+    K: Compare<T>,
+{
+    fn deref_mut(&mut self) -> &mut T {
+        debug_assert!(!self.heap.is_empty());
+        // SAFE: PeekMut is only instantiated for non-empty heaps
+        unsafe { self.heap.data.as_mut_slice().get_unchecked_mut(0) }
+    }
+}
+
+impl<'a, T, K, const N: usize> PeekMut<'a, T, K, N>
+where
+    K: Compare<T>,
+{
+    /// Removes the peeked value from the heap and returns it.
+    pub fn pop(mut this: PeekMut<'a, T, K, N>) -> T {
+        let value = this.heap.pop().unwrap();
+        this.sift = false;
+        value
+    }
+}
+
+impl<'a, T> Drop for Hole<'a, T> {
+    #[inline]
+    fn drop(&mut self) {
+        // fill the hole again
+        unsafe {
+            let pos = self.pos;
+            ptr::write(self.data.get_unchecked_mut(pos), ptr::read(&*self.elt));
+        }
+    }
+}
+
+impl<T, K, const N: usize> Default for BinaryHeap<T, K, N>
+where
+    K: Default,
+{
+    fn default() -> Self {
+        Self::with_cmp(K::default())
+    }
+}
+
+impl<T, K, const N: usize> TryFromIterator<T> for BinaryHeap<T, K, N>
+where
+    K: Compare<T> + Default,
+{
+    fn try_from_iter<I>(iter: I) -> Result<Self, CapacityError<T>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut heap = BinaryHeap::with_cmp(K::default());
+        for item in iter {
+            heap.push(item).map_err(CapacityError::new)?;
+        }
+        Ok(heap)
+    }
+}
+
+impl<T, K, const N: usize> Clone for BinaryHeap<T, K, N>
+where
+    K: Clone,
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            cmp: self.cmp.clone(),
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl<T, K, const N: usize> fmt::Debug for BinaryHeap<T, K, N>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.data.iter()).finish()
+    }
+}
+
+impl<'a, T, K, const N: usize> IntoIterator for &'a BinaryHeap<T, K, N>
+where
+    K: Compare<T>,
+{
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, K, const N: usize> IntoIterator for BinaryHeap<T, K, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            iter: self.data.into_iter(),
+        }
+    }
+}
+
+impl<T, K, const N: usize> From<Vec<T, N>> for BinaryHeap<T, K, N>
+where
+    K: Compare<T> + Default,
+{
+    /// Turns the vector into a binary heap, in `O(n)` time
+    fn from(vec: Vec<T, N>) -> Self {
+        Self::from_vec_with_cmp(vec, K::default())
+    }
+}
+
+impl<T, K, const N: usize> From<BinaryHeap<T, K, N>> for Vec<T, N> {
+    fn from(heap: BinaryHeap<T, K, N>) -> Self {
+        heap.data
+    }
+}
+
+impl<T, K, const N: usize> Extend<T> for BinaryHeap<T, K, N>
+where
+    K: Compare<T>,
+{
+    /// Pushes all the items of an iterator onto the binary heap
+    ///
+    /// # Panics
+    ///
+    /// Panics if the heap cannot hold all the items; use
+    /// [`try_extend`](BinaryHeap::try_extend) to handle that case.
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.try_extend(iter).unwrap()
+    }
+}
+
+impl<'a, T, K, const N: usize> Extend<&'a T> for BinaryHeap<T, K, N>
+where
+    K: Compare<T>,
+    T: 'a + Copy,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+impl<T, K, const N: usize, const M: usize> PartialEq<BinaryHeap<T, K, M>> for BinaryHeap<T, K, N>
+where
+    T: PartialEq,
+{
+    /// Two binary heaps are equal if they hold the same items, however they are laid out
+    ///
+    /// This takes `O(n²)` comparisons.
+    fn eq(&self, other: &BinaryHeap<T, K, M>) -> bool {
+        let (this, other) = (self.data.as_slice(), other.data.as_slice());
+        this.len() == other.len()
+            && this.iter().all(|item| {
+                let count = |items: &[T]| items.iter().filter(|x| *x == item).count();
+                count(this) == count(other)
+            })
+    }
+}
+
+impl<T, K, const N: usize> Eq for BinaryHeap<T, K, N> where T: Eq {}
+
+/// An owning iterator over the items of a [`BinaryHeap`], in arbitrary order
+///
+/// This struct is created by the `into_iter` method on [`BinaryHeap`].
+pub struct IntoIter<T, const N: usize> {
+    iter: vec::IntoIter<T, N>,
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
+
+impl<T, const N: usize> FusedIterator for IntoIter<T, N> {}
+
+impl<T, const N: usize> Clone for IntoIter<T, N>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+/// A draining iterator over the items of a [`BinaryHeap`], in arbitrary order
+///
+/// This struct is created by [`BinaryHeap::drain`].
+pub struct Drain<'a, T> {
+    iter: vec::Drain<'a, T>,
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Drain<'_, T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.iter.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+
+impl<T> FusedIterator for Drain<'_, T> {}
+
+impl<T> fmt::Debug for Drain<'_, T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Drain").field(&self.iter.as_ref()).finish()
+    }
+}
+
+/// A draining iterator over the items of a [`BinaryHeap`], in heap order
+///
+/// This struct is created by [`BinaryHeap::drain_sorted`].
+pub struct DrainSorted<'a, T, K, const N: usize>
+where
+    K: Compare<T>,
+{
+    heap: &'a mut BinaryHeap<T, K, N>,
+}
+
+impl<T, K, const N: usize> Iterator for DrainSorted<'_, T, K, N>
+where
+    K: Compare<T>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.heap.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.len(), Some(self.heap.len()))
+    }
+}
+
+impl<T, K, const N: usize> ExactSizeIterator for DrainSorted<'_, T, K, N> where K: Compare<T> {}
+
+impl<T, K, const N: usize> FusedIterator for DrainSorted<'_, T, K, N> where K: Compare<T> {}
+
+impl<T, K, const N: usize> Drop for DrainSorted<'_, T, K, N>
+where
+    K: Compare<T>,
+{
+    fn drop(&mut self) {
+        self.heap.clear();
+    }
+}
+
+impl<T, K, const N: usize> fmt::Debug for DrainSorted<'_, T, K, N>
+where
+    T: fmt::Debug,
+    K: Compare<T>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DrainSorted").field(&self.heap).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use core::cmp::{Ordering, Reverse};
+
+    use crate::binary_heap::{BinaryHeap, ByKey, Compare, Max, Min};
+    use crate::CapacityError;
+
+    #[test]
+    fn static_new() {
+        static mut _B: BinaryHeap<i32, Min, 16> = BinaryHeap::new();
+        static mut _C: BinaryHeap<i32, fn(&i32, &i32) -> Ordering, 16> =
+            BinaryHeap::with_cmp(i32::cmp);
+    }
+
+    #[test]
+    fn min() {
+        let mut heap = BinaryHeap::<_, Min, 16>::new();
+        heap.push(1).unwrap();
+        heap.push(2).unwrap();
+        heap.push(3).unwrap();
+        heap.push(17).unwrap();
+        heap.push(19).unwrap();
+        heap.push(36).unwrap();
+        heap.push(7).unwrap();
+        heap.push(25).unwrap();
+        heap.push(100).unwrap();
+
+        assert_eq!(
+            heap.iter().cloned().collect::<Vec<_>>(),
+            [1, 2, 3, 17, 19, 36, 7, 25, 100]
+        );
+
+        assert_eq!(heap.pop(), Some(1));
+
+        assert_eq!(
+            heap.iter().cloned().collect::<Vec<_>>(),
+            [2, 17, 3, 25, 19, 36, 7, 100]
+        );
+
+        assert_eq!(heap.pop(), Some(2));
+        assert_eq!(heap.pop(), Some(3));
+        assert_eq!(heap.pop(), Some(7));
+        assert_eq!(heap.pop(), Some(17));
+        assert_eq!(heap.pop(), Some(19));
+        assert_eq!(heap.pop(), Some(25));
+        assert_eq!(heap.pop(), Some(36));
+        assert_eq!(heap.pop(), Some(100));
+        assert_eq!(heap.pop(), None);
+
+        assert!(heap.peek_mut().is_none());
+
+        heap.push(1).unwrap();
+        heap.push(2).unwrap();
+        heap.push(10).unwrap();
+
+        {
+            let mut val = heap.peek_mut().unwrap();
+            *val = 7;
+        }
+
+        assert_eq!(heap.pop(), Some(2));
+        assert_eq!(heap.pop(), Some(7));
+        assert_eq!(heap.pop(), Some(10));
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn max() {
+        let mut heap = BinaryHeap::<_, Max, 16>::new();
+        heap.push(1).unwrap();
+        heap.push(2).unwrap();
+        heap.push(3).unwrap();
+        heap.push(17).unwrap();
+        heap.push(19).unwrap();
+        heap.push(36).unwrap();
+        heap.push(7).unwrap();
+        heap.push(25).unwrap();
+        heap.push(100).unwrap();
+
+        assert_eq!(
+            heap.iter().cloned().collect::<Vec<_>>(),
+            [100, 36, 19, 25, 3, 2, 7, 1, 17]
+        );
+
+        assert_eq!(heap.pop(), Some(100));
+
+        assert_eq!(
+            heap.iter().cloned().collect::<Vec<_>>(),
+            [36, 25, 19, 17, 3, 2, 7, 1]
+        );
+
+        assert_eq!(heap.pop(), Some(36));
+        assert_eq!(heap.pop(), Some(25));
+        assert_eq!(heap.pop(), Some(19));
+        assert_eq!(heap.pop(), Some(17));
+        assert_eq!(heap.pop(), Some(7));
+        assert_eq!(heap.pop(), Some(3));
+        assert_eq!(heap.pop(), Some(2));
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), None);
+
+        assert!(heap.peek_mut().is_none());
+
+        heap.push(1).unwrap();
+        heap.push(9).unwrap();
+        heap.push(10).unwrap();
+
+        {
+            let mut val = heap.peek_mut().unwrap();
+            *val = 7;
+        }
+
+        assert_eq!(heap.pop(), Some(9));
+        assert_eq!(heap.pop(), Some(7));
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), None);
+    }
+
+    fn is_max_heap(heap: &BinaryHeap<i32, Max, 16>) -> bool {
+        let data = heap.data.as_slice();
+        (1..data.len()).all(|i| data[(i - 1) / 2] >= data[i])
+    }
+
+    #[test]
+    fn from_vec() {
+        let vec = crate::Vec::<_, 16>::from_slice(&[3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5]).unwrap();
+
+        let heap = BinaryHeap::<_, Max, 16>::from(vec.clone());
+        assert!(is_max_heap(&heap));
+        assert_eq!(heap.into_sorted_vec(), [1, 1, 2, 3, 3, 4, 5, 5, 5, 6, 9]);
+
+        let mut heap = BinaryHeap::<_, Min, 16>::from(vec);
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), Some(2));
+        assert_eq!(heap.into_sorted_vec(), [9, 6, 5, 5, 5, 4, 3, 3]);
+
+        let empty = BinaryHeap::<i32, Max, 4>::from(crate::Vec::new());
+        assert!(empty.into_sorted_vec().is_empty());
+    }
+
+    #[test]
+    fn compare() {
+        // orders by absolute value
+        #[derive(Default)]
+        struct Abs;
+
+        impl Compare<i32> for Abs {
+            fn compare(&self, a: &i32, b: &i32) -> Ordering {
+                a.abs().cmp(&b.abs())
+            }
+        }
+
+        let mut heap = BinaryHeap::<_, Abs, 8>::with_cmp(Abs);
+        heap.extend(&[3, -7, 1, -2, 5]);
+        assert_eq!(heap.peek(), Some(&-7));
+        assert_eq!(heap.into_sorted_vec(), [1, -2, 3, 5, -7]);
+
+        let heap: BinaryHeap<_, Abs, 8> = crate::Vec::<_, 8>::from_slice(&[-4, 2, -9, 0])
+            .unwrap()
+            .into();
+        assert_eq!(heap.into_sorted_vec(), [0, 2, -4, -9]);
+    }
+
+    #[test]
+    fn by_key() {
+        let mut heap =
+            BinaryHeap::<(u8, char), _, 8>::with_cmp(ByKey(|&(prio, _): &(u8, char)| {
+                Reverse(prio)
+            }));
+        heap.extend([(3, 'c'), (1, 'a'), (2, 'b')]);
+
+        assert_eq!(heap.pop(), Some((1, 'a')));
+        assert_eq!(heap.pop(), Some((2, 'b')));
+        assert_eq!(heap.pop(), Some((3, 'c')));
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn closure() {
+        let keys = [30, 10, 20];
+        let mut heap =
+            BinaryHeap::<usize, _, 4>::with_cmp(|a: &usize, b: &usize| keys[*a].cmp(&keys[*b]));
+        heap.extend(0..keys.len());
+
+        let mut peek = heap.peek_mut().unwrap();
+        *peek = 1;
+        core::mem::drop(peek);
+
+        assert!(heap.drain_sorted().eq([2, 1, 1]));
+    }
+
+    #[test]
+    fn into_vec() {
+        let mut heap = BinaryHeap::<i32, Max, 4>::new();
+        heap.extend(&[1, 2, 3]);
+
+        let mut vec: crate::Vec<_, 4> = heap.clone().into();
+        vec.sort_unstable();
+        assert_eq!(vec, [1, 2, 3]);
+
+        let mut items: Vec<_> = heap.into_iter().collect();
+        items.sort_unstable();
+        assert_eq!(items, [1, 2, 3]);
+    }
+
+    #[test]
+    fn drain() {
+        let mut heap = BinaryHeap::<_, Max, 8>::new();
+        heap.extend(&[4, 8, 1]);
+
+        let drain = heap.drain();
+        assert_eq!(drain.len(), 3);
+        let mut items: Vec<_> = drain.collect();
+        items.sort_unstable();
+        assert_eq!(items, [1, 4, 8]);
+        assert!(heap.is_empty());
+
+        heap.extend(&[4, 8, 1, 6]);
+        assert!(heap.drain_sorted().eq([8, 6, 4, 1]));
+        assert!(heap.is_empty());
+
+        heap.extend(&[4, 8, 1, 6]);
+        let mut drain = heap.drain_sorted();
+        assert_eq!(drain.next(), Some(8));
+        assert_eq!(drain.len(), 3);
+        core::mem::drop(drain);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn retain() {
+        let mut heap = BinaryHeap::<_, Max, 16>::new();
+        heap.extend(0..16);
+
+        heap.retain(|x| x % 3 != 0);
+        assert!(is_max_heap(&heap));
+        assert_eq!(heap.len(), 10);
+
+        heap.retain(|_| true);
+        assert_eq!(heap.len(), 10);
+        heap.retain(|_| false);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn append() {
+        let mut a = BinaryHeap::<_, Max, 16>::new();
+        a.extend(&[1, 9, 5]);
+        let mut b = BinaryHeap::<_, Max, 16>::new();
+        b.extend(&[7, 2, 8, 3, 10, 4, 6, 0]);
+
+        a.append(&mut b).unwrap();
+        assert!(is_max_heap(&a));
+        assert!(b.is_empty());
+        assert_eq!(a.len(), 11);
+
+        let mut full = BinaryHeap::<_, Max, 8>::new();
+        full.extend(0..8);
+        assert_eq!(a.append(&mut full), Err(CapacityError::new(())));
+        assert_eq!(a.len(), 11);
+        assert_eq!(full.len(), 8);
+    }
+
+    #[test]
+    fn try_extend() {
+        let mut heap = BinaryHeap::<_, Max, 16>::new();
+        heap.push(20).unwrap();
+        assert_eq!(heap.try_extend(0..), Err(CapacityError::new(15)));
+        assert!(is_max_heap(&heap));
+        assert_eq!(heap.peek(), Some(&20));
+        assert_eq!(heap.len(), 16);
+    }
+
+    #[test]
+    #[should_panic]
+    fn extend_overflow() {
+        let mut heap = BinaryHeap::<i32, Max, 2>::new();
+        heap.extend(&[1, 2, 3]);
+    }
+
+    #[test]
+    fn eq() {
+        let mut a = BinaryHeap::<_, Min, 8>::new();
+        a.extend(&[1, 2, 3, 3]);
+        let mut b = BinaryHeap::<_, Min, 4>::new();
+        b.extend(&[3, 3, 2, 1]);
+
+        // same items, different layouts
+        assert_ne!(a.data.as_slice(), b.data.as_slice());
+        assert_eq!(a, b);
+
+        b.pop();
+        b.push(2).unwrap();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn drop() {
+        #[derive(Eq, Ord, PartialEq, PartialOrd)]
+        struct Droppable(i32);
+        impl Droppable {
+            fn new(x: i32) -> Self {
+                unsafe {
+                    COUNT += 1;
+                }
+                Droppable(x)
+            }
+        }
+        impl Drop for Droppable {
+            fn drop(&mut self) {
+                unsafe {
+                    COUNT -= 1;
+                }
+            }
+        }
+
+        static mut COUNT: i32 = 0;
+
+        {
+            let mut heap = BinaryHeap::<_, Max, 4>::new();
+            heap.push(Droppable::new(1)).ok().unwrap();
+            heap.push(Droppable::new(2)).ok().unwrap();
+            heap.pop().unwrap();
+        }
+
+        assert_eq!(unsafe { COUNT }, 0);
+
+        {
+            let mut heap = BinaryHeap::<_, Max, 4>::new();
+            heap.push(Droppable::new(1)).ok().unwrap();
+            heap.push(Droppable::new(2)).ok().unwrap();
+            heap.push(Droppable::new(3)).ok().unwrap();
+            let mut iter = heap.into_iter();
+            iter.next().unwrap();
+        }
+
+        assert_eq!(unsafe { COUNT }, 0);
+    }
+
+    // regression test: `BinaryHeap` used to implement `Drop` by dropping its items in place, after
+    // which its inner `Vec` dropped them a second time
+    #[test]
+    fn drop_items_once() {
+        #[derive(Eq, Ord, PartialEq, PartialOrd)]
+        struct Droppable(i32);
+        impl Drop for Droppable {
+            fn drop(&mut self) {
+                unsafe {
+                    DROPS += 1;
+                }
+            }
+        }
+
+        static mut DROPS: i32 = 0;
+
+        {
+            let mut heap = BinaryHeap::<_, Max, 4>::new();
+            heap.push(Droppable(1)).ok().unwrap();
+            heap.push(Droppable(2)).ok().unwrap();
+            heap.push(Droppable(3)).ok().unwrap();
+        }
+
+        assert_eq!(unsafe { DROPS }, 3);
+    }
+
+    // regression test: `Hole::move_to` used to take the source and destination pointers from two
+    // separate borrows of the data, the second of which invalidated the first under Stacked
+    // Borrows (`cargo miri test`)
+    #[test]
+    fn sift_stacked_borrows() {
+        let mut heap = BinaryHeap::<_, Min, 8>::new();
+        for x in [5, 3, 7, 1, 8, 2, 6, 4].iter() {
+            heap.push(*x).unwrap();
+        }
+
+        let mut sorted = [0; 8];
+        for x in sorted.iter_mut() {
+            *x = heap.pop().unwrap();
+        }
+        assert_eq!(sorted, [1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+}
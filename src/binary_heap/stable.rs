@@ -0,0 +1,276 @@
+use core::{cmp::Ordering, fmt, marker::PhantomData};
+
+use super::{BinaryHeap, Compare};
+use crate::sealed::binary_heap::Kind;
+use crate::Vec;
+
+#[derive(Clone)]
+struct Entry<T> {
+    // insertion order
+    seq: u32,
+    item: T,
+}
+
+// orders the entries by item, then the oldest first
+#[derive(Clone)]
+struct Fifo<K>(K);
+
+impl<T, K> Compare<Entry<T>> for Fifo<K>
+where
+    K: Compare<T>,
+{
+    fn compare(&self, a: &Entry<T>, b: &Entry<T>) -> Ordering {
+        self.0
+            .compare(&a.item, &b.item)
+            .then_with(|| b.seq.cmp(&a.seq))
+    }
+}
+
+/// A priority queue in which items of equal priority are popped in insertion order
+///
+/// It's a [`BinaryHeap`] that stores a sequence number next to each item and breaks ties in favor
+/// of the oldest item. When the sequence numbers run out, after 2<sup>32</sup> pushes without the
+/// queue ever being empty, they are renumbered from zero, which costs *O*(n log n) time and a
+/// temporary `[u32; N]` on the stack.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::binary_heap::StableBinaryHeap;
+///
+/// // (priority, message)
+/// let mut queue = StableBinaryHeap::<(u8, &str), _, 8>::with_cmp(
+///     |a: &(u8, &str), b: &(u8, &str)| a.0.cmp(&b.0),
+/// );
+///
+/// queue.push((1, "first")).unwrap();
+/// queue.push((3, "urgent")).unwrap();
+/// queue.push((1, "second")).unwrap();
+/// queue.push((1, "third")).unwrap();
+///
+/// assert_eq!(queue.pop(), Some((3, "urgent")));
+/// assert_eq!(queue.pop(), Some((1, "first")));
+/// assert_eq!(queue.pop(), Some((1, "second")));
+/// assert_eq!(queue.pop(), Some((1, "third")));
+/// ```
+pub struct StableBinaryHeap<T, K, const N: usize> {
+    heap: BinaryHeap<Entry<T>, Fifo<K>, N>,
+    // sequence number of the next item
+    next: u32,
+}
+
+impl<T, K, const N: usize> StableBinaryHeap<T, K, N>
+where
+    PhantomData<K>: Kind<K>,
+{
+    /// Creates an empty StableBinaryHeap as a $K-heap
+    ///
+    /// ```
+    /// use heapless::binary_heap::{Max, StableBinaryHeap};
+    ///
+    /// // allocate the binary heap on the stack
+    /// let mut heap: StableBinaryHeap<_, Max, 8> = StableBinaryHeap::new();
+    /// heap.push(4).unwrap();
+    ///
+    /// // allocate the binary heap in a static variable
+    /// static mut HEAP: StableBinaryHeap<i32, Max, 8> = StableBinaryHeap::new();
+    /// ```
+    pub const fn new() -> Self {
+        Self::with_cmp(<PhantomData<K> as Kind<K>>::INSTANCE)
+    }
+}
+
+impl<T, K, const N: usize> StableBinaryHeap<T, K, N> {
+    /// Creates an empty StableBinaryHeap that orders its items with the comparator `cmp`
+    pub const fn with_cmp(cmp: K) -> Self {
+        Self {
+            heap: BinaryHeap::with_cmp(Fifo(cmp)),
+            next: 0,
+        }
+    }
+
+    /// Returns the comparator of the binary heap
+    pub fn cmp(&self) -> &K {
+        &self.heap.cmp.0
+    }
+
+    /// Returns the capacity of the binary heap
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the length of the binary heap
+    pub fn len(&self) -> usize {
+        self.heap.data.len()
+    }
+
+    /// Checks if the binary heap is empty
+    pub fn is_empty(&self) -> bool {
+        self.heap.data.is_empty()
+    }
+
+    /// Drops all items from the binary heap
+    pub fn clear(&mut self) {
+        self.heap.data.clear();
+        self.next = 0;
+    }
+
+    /// Returns an iterator visiting all the items in the binary heap, in arbitrary order
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &T> + '_ {
+        self.heap.data.iter().map(|entry| &entry.item)
+    }
+}
+
+impl<T, K, const N: usize> StableBinaryHeap<T, K, N>
+where
+    K: Compare<T>,
+{
+    /// Returns the *top* item of the binary heap, the oldest one among equals, or `None` if it is
+    /// empty
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.peek().map(|entry| &entry.item)
+    }
+
+    /// Pushes an item onto the binary heap
+    ///
+    /// Returns back the item if the binary heap is full.
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        if self.heap.data.is_full() {
+            return Err(item);
+        }
+
+        if self.next == u32::MAX {
+            self.renumber();
+        }
+
+        let seq = self.next;
+        self.next += 1;
+        // NOTE(unwrap) the heap is not full
+        self.heap.push(Entry { seq, item }).ok().unwrap();
+        Ok(())
+    }
+
+    /// Removes the *top* item from the binary heap, the oldest one among equals, and returns it,
+    /// or `None` if it is empty
+    pub fn pop(&mut self) -> Option<T> {
+        let entry = self.heap.pop()?;
+        if self.heap.is_empty() {
+            self.next = 0;
+        }
+        Some(entry.item)
+    }
+
+    // renumbers the items from zero, keeping their order
+    fn renumber(&mut self) {
+        let mut seqs: Vec<u32, N> = self.heap.data.iter().map(|entry| entry.seq).collect();
+        seqs.sort_unstable();
+
+        for entry in self.heap.data.iter_mut() {
+            // NOTE(unwrap) every sequence number is in `seqs`
+            entry.seq = seqs.binary_search(&entry.seq).unwrap() as u32;
+        }
+        self.next = seqs.len() as u32;
+    }
+}
+
+impl<T, K, const N: usize> Clone for StableBinaryHeap<T, K, N>
+where
+    K: Clone,
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            heap: self.heap.clone(),
+            next: self.next,
+        }
+    }
+}
+
+impl<T, K, const N: usize> Default for StableBinaryHeap<T, K, N>
+where
+    K: Default,
+{
+    fn default() -> Self {
+        Self::with_cmp(K::default())
+    }
+}
+
+impl<T, K, const N: usize> fmt::Debug for StableBinaryHeap<T, K, N>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use super::StableBinaryHeap;
+    use crate::binary_heap::{ByKey, Max, Min};
+
+    #[test]
+    fn static_new() {
+        static mut _H: StableBinaryHeap<i32, Min, 16> = StableBinaryHeap::new();
+    }
+
+    #[test]
+    fn fifo() {
+        let mut heap = StableBinaryHeap::<(u8, u8), _, 16>::with_cmp(ByKey(|x: &(u8, u8)| x.0));
+        for i in 0..16 {
+            heap.push((i % 3, i)).unwrap();
+        }
+        assert_eq!(heap.push((0, 0)), Err((0, 0)));
+
+        let mut popped = Vec::new();
+        while let Some(x) = heap.pop() {
+            popped.push(x);
+        }
+
+        let mut expected: Vec<_> = (0..16).map(|i| (i % 3, i)).collect();
+        // a stable sort, by descending priority
+        expected.sort_by_key(|x| core::cmp::Reverse(x.0));
+        assert_eq!(popped, expected);
+    }
+
+    #[test]
+    fn min() {
+        let mut heap = StableBinaryHeap::<u8, Min, 4>::new();
+        heap.push(2).unwrap();
+        heap.push(1).unwrap();
+        assert_eq!(heap.peek(), Some(&1));
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), Some(2));
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn renumber() {
+        let mut heap = StableBinaryHeap::<(bool, u8), _, 8>::with_cmp(ByKey(|x: &(bool, u8)| x.0));
+        heap.push((false, 0)).unwrap();
+        heap.push((true, 1)).unwrap();
+        heap.next = u32::MAX - 2;
+
+        for i in 2..8 {
+            heap.push((i % 2 == 1, i)).unwrap();
+        }
+        assert_eq!(heap.next, 8);
+
+        let popped: Vec<_> = core::iter::from_fn(|| heap.pop()).map(|x| x.1).collect();
+        assert_eq!(popped, [1, 3, 5, 7, 0, 2, 4, 6]);
+        assert_eq!(heap.next, 0);
+    }
+
+    #[test]
+    fn clear() {
+        let mut heap = StableBinaryHeap::<u8, Max, 4>::new();
+        heap.push(2).unwrap();
+        heap.push(2).unwrap();
+        heap.clear();
+        assert!(heap.is_empty());
+        assert_eq!(heap.next, 0);
+        assert_eq!(std::format!("{:?}", heap), "[]");
+    }
+}
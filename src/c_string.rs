@@ -0,0 +1,354 @@
+//! A fixed capacity [`CString`](https://doc.rust-lang.org/std/ffi/struct.CString.html)
+
+use core::{
+    borrow::Borrow,
+    convert::TryFrom,
+    ffi::{c_char, CStr},
+    fmt, hash, ops,
+};
+
+use crate::{CapacityError, String, Vec};
+
+/// A fixed capacity [`CString`](https://doc.rust-lang.org/std/ffi/struct.CString.html)
+///
+/// A `CString` always ends with exactly one nul terminator and never contains interior nul
+/// bytes, so it can be handed to C functions that expect a `const char *`. The terminator is part
+/// of the capacity: a `CString<N>` holds at most `N - 1` bytes of content.
+///
+/// # Examples
+///
+/// ```
+/// use core::convert::TryFrom;
+/// use heapless::CString;
+///
+/// let mut c_string = CString::<16>::try_from("hello").unwrap();
+/// c_string.extend_from_bytes(b", world").unwrap();
+///
+/// assert_eq!(c_string.as_bytes_with_nul(), b"hello, world\0");
+/// assert_eq!(c_string.as_c_str().to_str(), Ok("hello, world"));
+/// ```
+#[derive(Clone)]
+pub struct CString<const N: usize> {
+    // NOTE invariant: ends with a single nul byte and has no other nul bytes
+    inner: Vec<u8, N>,
+}
+
+impl<const N: usize> CString<N> {
+    /// Creates a new, empty C string with a fixed capacity of `N` bytes, terminator included
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::CString;
+    ///
+    /// let empty = CString::<4>::new();
+    /// assert_eq!(empty.as_bytes_with_nul(), b"\0");
+    /// ```
+    pub fn new() -> Self {
+        let mut inner = Vec::new();
+        // NOTE(unsafe) `Vec::new` checks that `N > 0` so there is room for the terminator
+        unsafe { inner.push_unchecked(0) };
+        Self { inner }
+    }
+
+    /// Creates a C string from `bytes` without checking for interior nul bytes
+    ///
+    /// `bytes` may end with a nul terminator; one is appended otherwise. Returns an error if the
+    /// result doesn't fit in `N` bytes.
+    ///
+    /// # Safety
+    ///
+    /// `bytes` must not contain any nul byte other than, optionally, the last one.
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> Result<Self, CapacityError> {
+        let bytes = strip_nul(bytes);
+        if bytes.len() >= N {
+            return Err(CapacityError::new(()));
+        }
+
+        let mut inner = Vec::new();
+        inner.extend_from_slice(bytes)?;
+        inner.push_unchecked(0);
+        Ok(Self { inner })
+    }
+
+    /// Appends `bytes` to the end of this C string, before the terminator
+    ///
+    /// `bytes` may end with a nul terminator, which is dropped. Returns an error, and leaves the
+    /// C string untouched, if `bytes` contains any other nul byte or if the result doesn't fit in
+    /// `N` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{c_string::ExtendError, CString};
+    ///
+    /// let mut c_string = CString::<8>::new();
+    /// c_string.extend_from_bytes(b"foo").unwrap();
+    /// c_string.extend_from_bytes(b"bar\0").unwrap();
+    /// assert_eq!(c_string.as_bytes(), b"foobar");
+    ///
+    /// assert_eq!(
+    ///     c_string.extend_from_bytes(b"a\0b"),
+    ///     Err(ExtendError::InteriorNul { position: 1 })
+    /// );
+    /// assert!(matches!(
+    ///     c_string.extend_from_bytes(b"baz"),
+    ///     Err(ExtendError::Capacity(_))
+    /// ));
+    /// assert_eq!(c_string.as_bytes(), b"foobar");
+    /// ```
+    pub fn extend_from_bytes(&mut self, bytes: &[u8]) -> Result<(), ExtendError> {
+        let bytes = strip_nul(bytes);
+        if let Some(position) = bytes.iter().position(|&b| b == 0) {
+            return Err(ExtendError::InteriorNul { position });
+        }
+
+        // the new bytes take the place of the current terminator
+        if self.inner.len() + bytes.len() > N {
+            return Err(ExtendError::Capacity(CapacityError::new(())));
+        }
+
+        unsafe {
+            // NOTE(unsafe) the string always holds at least the terminator and we just checked
+            // that the bytes plus a new terminator fit
+            self.inner.pop_unchecked();
+            for &b in bytes {
+                self.inner.push_unchecked(b);
+            }
+            self.inner.push_unchecked(0);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the maximum number of bytes the C string can hold, terminator included
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the contents of this C string as a [`CStr`]
+    #[inline]
+    pub fn as_c_str(&self) -> &CStr {
+        // SAFETY: `inner` ends with a nul byte and has no interior nul bytes
+        unsafe { CStr::from_bytes_with_nul_unchecked(&self.inner) }
+    }
+
+    /// Returns a pointer to the nul terminated contents of this C string
+    ///
+    /// The pointer is valid for as long as the C string is neither modified nor moved.
+    #[inline]
+    pub fn as_ptr(&self) -> *const c_char {
+        self.inner.as_ptr() as *const c_char
+    }
+
+    /// Returns the contents of this C string, without the nul terminator
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.inner[..self.inner.len() - 1]
+    }
+
+    /// Returns the contents of this C string, including the nul terminator
+    #[inline]
+    pub fn as_bytes_with_nul(&self) -> &[u8] {
+        &self.inner
+    }
+}
+
+/// Removes a single trailing nul byte, if any
+fn strip_nul(bytes: &[u8]) -> &[u8] {
+    match bytes.split_last() {
+        Some((0, rest)) => rest,
+        _ => bytes,
+    }
+}
+
+/// The error returned when extending a [`CString`] fails
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExtendError {
+    /// The C string doesn't have enough capacity left
+    Capacity(CapacityError),
+    /// The bytes contain a nul byte before their end
+    InteriorNul {
+        /// The position of the nul byte within the bytes that were being appended
+        position: usize,
+    },
+}
+
+impl From<CapacityError> for ExtendError {
+    fn from(error: CapacityError) -> Self {
+        ExtendError::Capacity(error)
+    }
+}
+
+impl fmt::Display for ExtendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExtendError::Capacity(error) => fmt::Display::fmt(error, f),
+            ExtendError::InteriorNul { position } => {
+                write!(f, "interior nul byte found at position {}", position)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ExtendError {}
+
+impl<const N: usize> Default for CString<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> TryFrom<&[u8]> for CString<N> {
+    type Error = ExtendError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let mut c_string = Self::new();
+        c_string.extend_from_bytes(bytes)?;
+        Ok(c_string)
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for CString<N> {
+    type Error = ExtendError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::try_from(s.as_bytes())
+    }
+}
+
+impl<const N: usize, const M: usize> TryFrom<String<M>> for CString<N> {
+    type Error = ExtendError;
+
+    fn try_from(s: String<M>) -> Result<Self, Self::Error> {
+        Self::try_from(s.as_bytes())
+    }
+}
+
+impl<const N: usize> TryFrom<&CStr> for CString<N> {
+    type Error = CapacityError;
+
+    fn try_from(c_str: &CStr) -> Result<Self, Self::Error> {
+        // SAFETY: a `CStr` has no interior nul bytes
+        unsafe { Self::from_bytes_unchecked(c_str.to_bytes()) }
+    }
+}
+
+impl<const N: usize> ops::Deref for CString<N> {
+    type Target = CStr;
+
+    fn deref(&self) -> &CStr {
+        self.as_c_str()
+    }
+}
+
+impl<const N: usize> AsRef<CStr> for CString<N> {
+    fn as_ref(&self) -> &CStr {
+        self.as_c_str()
+    }
+}
+
+impl<const N: usize> Borrow<CStr> for CString<N> {
+    fn borrow(&self) -> &CStr {
+        self.as_c_str()
+    }
+}
+
+impl<const N: usize> fmt::Debug for CString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_c_str(), f)
+    }
+}
+
+impl<const N: usize> hash::Hash for CString<N> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_c_str().hash(state)
+    }
+}
+
+impl<const N1: usize, const N2: usize> PartialEq<CString<N2>> for CString<N1> {
+    fn eq(&self, other: &CString<N2>) -> bool {
+        self.as_c_str() == other.as_c_str()
+    }
+}
+
+impl<const N: usize> PartialEq<CStr> for CString<N> {
+    fn eq(&self, other: &CStr) -> bool {
+        self.as_c_str() == other
+    }
+}
+
+impl<const N: usize> Eq for CString<N> {}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::TryFrom;
+
+    use super::{CString, ExtendError};
+    use crate::{CapacityError, String};
+
+    #[test]
+    fn empty() {
+        let empty = CString::<1>::new();
+        assert_eq!(empty.as_bytes_with_nul(), b"\0");
+        assert_eq!(empty.as_bytes(), b"");
+        assert_eq!(empty.as_c_str().to_bytes(), b"");
+    }
+
+    #[test]
+    fn try_from() {
+        let c = CString::<4>::try_from("abc").unwrap();
+        assert_eq!(c.as_bytes_with_nul(), b"abc\0");
+
+        // the terminator counts against the capacity
+        assert_eq!(
+            CString::<3>::try_from("abc"),
+            Err(ExtendError::Capacity(CapacityError::new(())))
+        );
+
+        let c = CString::<4>::try_from(&b"abc\0"[..]).unwrap();
+        assert_eq!(c.as_bytes_with_nul(), b"abc\0");
+        assert_eq!(
+            CString::<8>::try_from(&b"ab\0\0"[..]),
+            Err(ExtendError::InteriorNul { position: 2 })
+        );
+
+        let s: String<8> = String::from("abc");
+        let c = CString::<4>::try_from(s).unwrap();
+        assert_eq!(c.as_bytes(), b"abc");
+
+        let c2 = CString::<8>::try_from(c.as_c_str()).unwrap();
+        assert_eq!(c, c2);
+        assert!(CString::<3>::try_from(c.as_c_str()).is_err());
+    }
+
+    #[test]
+    fn extend_from_bytes() {
+        let mut c = CString::<6>::new();
+        c.extend_from_bytes(b"ab").unwrap();
+        c.extend_from_bytes(b"").unwrap();
+        c.extend_from_bytes(b"\0").unwrap();
+        c.extend_from_bytes(b"cde").unwrap();
+        assert_eq!(c.as_bytes_with_nul(), b"abcde\0");
+
+        assert!(matches!(
+            c.extend_from_bytes(b"f"),
+            Err(ExtendError::Capacity(_))
+        ));
+        assert_eq!(
+            c.extend_from_bytes(b"\0\0"),
+            Err(ExtendError::InteriorNul { position: 0 })
+        );
+        assert_eq!(c.as_bytes_with_nul(), b"abcde\0");
+    }
+
+    #[test]
+    fn as_ptr() {
+        let c = CString::<8>::try_from("abc").unwrap();
+        let c_str = unsafe { core::ffi::CStr::from_ptr(c.as_ptr()) };
+        assert_eq!(c_str.to_bytes(), b"abc");
+        assert_eq!(c, *c_str);
+    }
+}
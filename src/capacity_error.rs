@@ -0,0 +1,103 @@
+use core::fmt;
+
+/// The error returned when an operation would exceed the capacity of a container
+///
+/// Operations that were handed a single value, like [`String::push`](crate::String::push), give
+/// it back inside the error; use [`into_inner`](CapacityError::into_inner) to recover it. Other
+/// operations return `CapacityError<()>`, which is what `CapacityError` defaults to.
+///
+/// # Migrating from `Result<_, ()>`
+///
+/// The methods that now return a `CapacityError` used to return `Result<_, ()>`. `()` implements
+/// `From<CapacityError<T>>` so `?` keeps working in functions that return `Result<_, ()>`; code
+/// that matched on `Err(())` should match on `Err(_)` instead.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::{CapacityError, String};
+///
+/// let mut s: String<2> = String::new();
+/// s.push_str("ab").unwrap();
+///
+/// assert_eq!(s.push('c'), Err(CapacityError::new('c')));
+/// assert_eq!(s.push_str("c"), Err(CapacityError::new(())));
+/// ```
+#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct CapacityError<T = ()> {
+    element: T,
+}
+
+impl<T> CapacityError<T> {
+    /// Creates a new `CapacityError` that holds the rejected `element`
+    pub const fn new(element: T) -> Self {
+        Self { element }
+    }
+
+    /// Returns a reference to the rejected element
+    pub fn element(&self) -> &T {
+        &self.element
+    }
+
+    /// Consumes the error and returns the rejected element
+    pub fn into_inner(self) -> T {
+        self.element
+    }
+
+    /// Drops the rejected element, turning this into a `CapacityError<()>`
+    pub fn simplify(self) -> CapacityError {
+        CapacityError { element: () }
+    }
+}
+
+impl<T> fmt::Debug for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CapacityError: insufficient capacity")
+    }
+}
+
+impl<T> fmt::Display for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("insufficient capacity")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> std::error::Error for CapacityError<T> {}
+
+impl<T> From<CapacityError<T>> for () {
+    fn from(_: CapacityError<T>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CapacityError, String, Vec};
+
+    #[test]
+    fn into_inner() {
+        let mut s: String<1> = String::new();
+        s.push('a').unwrap();
+
+        let e = s.push('b').unwrap_err();
+        assert_eq!(*e.element(), 'b');
+        assert_eq!(e.simplify(), CapacityError::new(()));
+        assert_eq!(e.into_inner(), 'b');
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            std::format!("{}", CapacityError::new(0u8)),
+            "insufficient capacity"
+        );
+    }
+
+    #[test]
+    fn question_mark_into_unit() {
+        fn f() -> Result<Vec<u8, 2>, ()> {
+            Ok(Vec::from_slice(&[1, 2, 3])?)
+        }
+
+        assert_eq!(f(), Err(()));
+    }
+}
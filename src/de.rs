@@ -1,18 +1,21 @@
 use crate::{
-    sealed::binary_heap::Kind as BinaryHeapKind, BinaryHeap, IndexMap, IndexSet, LinearMap, String,
-    Vec,
+    binary_heap::Compare,
+    histbuf::{Archive, Consolidation, Sample},
+    sealed::{histbuf::Cascade, indexmap::KeyHasher},
+    BinaryHeap, Deque, HistoryBuffer, IndexMap, IndexSet, LinearMap, SortedLinearMap, String, Vec,
 };
 use core::{fmt, marker::PhantomData};
-use hash32::{BuildHasherDefault, Hash, Hasher};
-use serde::de::{self, Deserialize, Deserializer, Error, MapAccess, SeqAccess};
+use serde::de::{
+    self, Deserialize, Deserializer, EnumAccess, Error, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess,
+};
 
 // Sequential containers
 
 impl<'de, T, KIND, const N: usize> Deserialize<'de> for BinaryHeap<T, KIND, N>
 where
-    T: Ord + Deserialize<'de>,
-
-    KIND: BinaryHeapKind,
+    T: Deserialize<'de>,
+    KIND: Compare<T> + Default,
 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -22,8 +25,8 @@ where
 
         impl<'de, T, KIND, const N: usize> de::Visitor<'de> for ValueVisitor<'de, T, KIND, N>
         where
-            T: Ord + Deserialize<'de>,
-            KIND: BinaryHeapKind,
+            T: Deserialize<'de>,
+            KIND: Compare<T> + Default,
         {
             type Value = BinaryHeap<T, KIND, N>;
 
@@ -35,7 +38,7 @@ where
             where
                 A: SeqAccess<'de>,
             {
-                let mut values = BinaryHeap::new();
+                let mut values = BinaryHeap::with_cmp(KIND::default());
 
                 while let Some(value) = seq.next_element()? {
                     if values.push(value).is_err() {
@@ -50,10 +53,10 @@ where
     }
 }
 
-impl<'de, T, S, const N: usize> Deserialize<'de> for IndexSet<T, BuildHasherDefault<S>, N>
+impl<'de, T, S, const N: usize> Deserialize<'de> for IndexSet<T, S, N>
 where
-    T: Eq + Hash + Deserialize<'de>,
-    S: Hasher + Default,
+    T: Eq + Deserialize<'de>,
+    S: KeyHasher<T> + Default,
 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -63,10 +66,10 @@ where
 
         impl<'de, T, S, const N: usize> de::Visitor<'de> for ValueVisitor<'de, T, S, N>
         where
-            T: Eq + Hash + Deserialize<'de>,
-            S: Hasher + Default,
+            T: Eq + Deserialize<'de>,
+            S: KeyHasher<T> + Default,
         {
-            type Value = IndexSet<T, BuildHasherDefault<S>, N>;
+            type Value = IndexSet<T, S, N>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
                 formatter.write_str("a sequence")
@@ -76,7 +79,7 @@ where
             where
                 A: SeqAccess<'de>,
             {
-                let mut values = IndexSet::new();
+                let mut values = IndexSet::with_hasher(S::default());
 
                 while let Some(value) = seq.next_element()? {
                     if values.insert(value).is_err() {
@@ -130,13 +133,349 @@ where
     }
 }
 
+impl<'de, T, const N: usize> Deserialize<'de> for Deque<T, N>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ValueVisitor<'de, T, const N: usize>(PhantomData<(&'de (), T)>);
+
+        impl<'de, T, const N: usize> serde::de::Visitor<'de> for ValueVisitor<'de, T, N>
+        where
+            T: Deserialize<'de>,
+        {
+            type Value = Deque<T, N>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a sequence")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut values = Deque::new();
+
+                while let Some(value) = seq.next_element()? {
+                    if values.push_back(value).is_err() {
+                        return Err(A::Error::invalid_length(values.capacity() + 1, &self))?;
+                    }
+                }
+
+                Ok(values)
+            }
+        }
+        deserializer.deserialize_seq(ValueVisitor(PhantomData))
+    }
+}
+
+impl<'de, T, const N: usize> Deserialize<'de> for HistoryBuffer<T, N>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ValueVisitor<'de, T, const N: usize>(PhantomData<(&'de (), T)>);
+
+        impl<'de, T, const N: usize> de::Visitor<'de> for ValueVisitor<'de, T, N>
+        where
+            T: Deserialize<'de>,
+        {
+            type Value = HistoryBuffer<T, N>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a sequence")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut values = HistoryBuffer::new();
+
+                while let Some(value) = seq.next_element()? {
+                    // writing to a full buffer would drop the oldest values
+                    if values.len() == values.capacity() {
+                        return Err(A::Error::invalid_length(values.capacity() + 1, &self))?;
+                    }
+                    values.write(value);
+                }
+
+                Ok(values)
+            }
+        }
+        deserializer.deserialize_seq(ValueVisitor(PhantomData))
+    }
+}
+
+// Archives
+
+impl<'de> Deserialize<'de> for Consolidation {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        const VARIANTS: &[&str] = &["Average", "Min", "Max", "Last"];
+
+        struct Variant(Consolidation);
+
+        impl<'de> Deserialize<'de> for Variant {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct VariantVisitor;
+
+                impl<'de> de::Visitor<'de> for VariantVisitor {
+                    type Value = Variant;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        formatter.write_str("a variant identifier")
+                    }
+
+                    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+                    where
+                        E: de::Error,
+                    {
+                        match v {
+                            0 => Ok(Variant(Consolidation::Average)),
+                            1 => Ok(Variant(Consolidation::Min)),
+                            2 => Ok(Variant(Consolidation::Max)),
+                            3 => Ok(Variant(Consolidation::Last)),
+                            _ => Err(E::invalid_value(de::Unexpected::Unsigned(v), &self)),
+                        }
+                    }
+
+                    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                    where
+                        E: de::Error,
+                    {
+                        match v {
+                            "Average" => Ok(Variant(Consolidation::Average)),
+                            "Min" => Ok(Variant(Consolidation::Min)),
+                            "Max" => Ok(Variant(Consolidation::Max)),
+                            "Last" => Ok(Variant(Consolidation::Last)),
+                            _ => Err(E::unknown_variant(v, VARIANTS)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(VariantVisitor)
+            }
+        }
+
+        struct ValueVisitor;
+
+        impl<'de> de::Visitor<'de> for ValueVisitor {
+            type Value = Consolidation;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a consolidation function")
+            }
+
+            fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+            where
+                A: EnumAccess<'de>,
+            {
+                let (Variant(consolidation), variant) = data.variant()?;
+                variant.unit_variant()?;
+                Ok(consolidation)
+            }
+        }
+
+        deserializer.deserialize_enum("Consolidation", VARIANTS, ValueVisitor)
+    }
+}
+
+impl<'de, T, Next, const N: usize> Deserialize<'de> for Archive<T, Next, N>
+where
+    T: Sample + Deserialize<'de>,
+    Next: Cascade<T> + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["samples", "consolidation", "steps", "pending", "next"];
+
+        enum Field {
+            Samples,
+            Consolidation,
+            Steps,
+            Pending,
+            Next,
+            Ignore,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct FieldVisitor;
+
+                impl<'de> de::Visitor<'de> for FieldVisitor {
+                    type Value = Field;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        formatter.write_str("a field identifier")
+                    }
+
+                    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+                    where
+                        E: de::Error,
+                    {
+                        Ok(match v {
+                            0 => Field::Samples,
+                            1 => Field::Consolidation,
+                            2 => Field::Steps,
+                            3 => Field::Pending,
+                            4 => Field::Next,
+                            _ => Field::Ignore,
+                        })
+                    }
+
+                    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                    where
+                        E: de::Error,
+                    {
+                        Ok(match v {
+                            "samples" => Field::Samples,
+                            "consolidation" => Field::Consolidation,
+                            "steps" => Field::Steps,
+                            "pending" => Field::Pending,
+                            "next" => Field::Next,
+                            _ => Field::Ignore,
+                        })
+                    }
+                }
+
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
+
+        // the last tier serializes its samples only
+        fn last_tier<'de, T, Next, E, const N: usize>(
+            buffer: HistoryBuffer<T, N>,
+        ) -> Result<Archive<T, Next, N>, E>
+        where
+            T: Sample,
+            Next: Cascade<T> + Deserialize<'de>,
+            E: de::Error,
+        {
+            // `Next` is `()` here
+            let next = Next::deserialize(().into_deserializer())?;
+            Archive::from_parts(buffer, 0, N, Consolidation::Last, next).map_err(E::custom)
+        }
+
+        fn set<V, E>(slot: &mut Option<V>, value: V, field: &'static str) -> Result<(), E>
+        where
+            E: de::Error,
+        {
+            match slot.replace(value) {
+                Some(_) => Err(E::duplicate_field(field)),
+                None => Ok(()),
+            }
+        }
+
+        struct ValueVisitor<'de, T, Next, const N: usize>(PhantomData<(&'de (), T, Next)>);
+
+        impl<'de, T, Next, const N: usize> de::Visitor<'de> for ValueVisitor<'de, T, Next, N>
+        where
+            T: Sample + Deserialize<'de>,
+            Next: Cascade<T> + Deserialize<'de>,
+        {
+            type Value = Archive<T, Next, N>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("an archive")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let buffer = seq
+                    .next_element()?
+                    .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+                if Next::TIERS == 0 {
+                    return last_tier(buffer);
+                }
+
+                let consolidation = seq
+                    .next_element()?
+                    .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+                let steps = seq
+                    .next_element()?
+                    .ok_or_else(|| A::Error::invalid_length(2, &self))?;
+                let pending = seq
+                    .next_element()?
+                    .ok_or_else(|| A::Error::invalid_length(3, &self))?;
+                let next = seq
+                    .next_element()?
+                    .ok_or_else(|| A::Error::invalid_length(4, &self))?;
+
+                Archive::from_parts(buffer, pending, steps, consolidation, next)
+                    .map_err(A::Error::custom)
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut buffer = None;
+                let mut consolidation = None;
+                let mut steps = None;
+                let mut pending = None;
+                let mut next = None;
+
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::Samples => set(&mut buffer, map.next_value()?, "samples")?,
+                        Field::Consolidation => {
+                            set(&mut consolidation, map.next_value()?, "consolidation")?
+                        }
+                        Field::Steps => set(&mut steps, map.next_value()?, "steps")?,
+                        Field::Pending => set(&mut pending, map.next_value()?, "pending")?,
+                        Field::Next => set(&mut next, map.next_value()?, "next")?,
+                        Field::Ignore => {
+                            map.next_value::<de::IgnoredAny>()?;
+                        }
+                    }
+                }
+
+                let buffer = buffer.ok_or_else(|| A::Error::missing_field("samples"))?;
+                if Next::TIERS == 0 {
+                    return last_tier(buffer);
+                }
+
+                Archive::from_parts(
+                    buffer,
+                    pending.ok_or_else(|| A::Error::missing_field("pending"))?,
+                    steps.ok_or_else(|| A::Error::missing_field("steps"))?,
+                    consolidation.ok_or_else(|| A::Error::missing_field("consolidation"))?,
+                    next.ok_or_else(|| A::Error::missing_field("next"))?,
+                )
+                .map_err(A::Error::custom)
+            }
+        }
+
+        deserializer.deserialize_struct("Archive", FIELDS, ValueVisitor(PhantomData))
+    }
+}
+
 // Dictionaries
 
-impl<'de, K, V, S, const N: usize> Deserialize<'de> for IndexMap<K, V, BuildHasherDefault<S>, N>
+impl<'de, K, V, S, const N: usize> Deserialize<'de> for IndexMap<K, V, S, N>
 where
-    K: Eq + Hash + Deserialize<'de>,
+    K: Eq + Deserialize<'de>,
     V: Deserialize<'de>,
-    S: Default + Hasher,
+    S: KeyHasher<K> + Default,
 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -146,11 +485,11 @@ where
 
         impl<'de, K, V, S, const N: usize> de::Visitor<'de> for ValueVisitor<'de, K, V, S, N>
         where
-            K: Eq + Hash + Deserialize<'de>,
+            K: Eq + Deserialize<'de>,
             V: Deserialize<'de>,
-            S: Default + Hasher,
+            S: KeyHasher<K> + Default,
         {
-            type Value = IndexMap<K, V, BuildHasherDefault<S>, N>;
+            type Value = IndexMap<K, V, S, N>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
                 formatter.write_str("a map")
@@ -160,7 +499,7 @@ where
             where
                 A: MapAccess<'de>,
             {
-                let mut values = IndexMap::new();
+                let mut values = IndexMap::with_hasher(S::default());
 
                 while let Some((key, value)) = map.next_entry()? {
                     if values.insert(key, value).is_err() {
@@ -216,6 +555,47 @@ where
     }
 }
 
+impl<'de, K, V, const N: usize> Deserialize<'de> for SortedLinearMap<K, V, N>
+where
+    K: Ord + Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ValueVisitor<'de, K, V, const N: usize>(PhantomData<(&'de (), K, V)>);
+
+        impl<'de, K, V, const N: usize> de::Visitor<'de> for ValueVisitor<'de, K, V, N>
+        where
+            K: Ord + Deserialize<'de>,
+            V: Deserialize<'de>,
+        {
+            type Value = SortedLinearMap<K, V, N>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a map")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut values = SortedLinearMap::new();
+
+                while let Some((key, value)) = map.next_entry()? {
+                    if values.insert(key, value).is_err() {
+                        return Err(A::Error::invalid_length(values.capacity() + 1, &self))?;
+                    }
+                }
+
+                Ok(values)
+            }
+        }
+        deserializer.deserialize_map(ValueVisitor(PhantomData))
+    }
+}
+
 // String containers
 
 impl<'de, const N: usize> Deserialize<'de> for String<N> {
@@ -1,7 +1,7 @@
 //! Defmt implementations for heapless types
 //!
 
-use crate::Vec;
+use crate::{CapacityError, Vec, VecView};
 use defmt::Formatter;
 
 impl<T, const N: usize> defmt::Format for Vec<T, N>
@@ -13,6 +13,49 @@ where
     }
 }
 
+impl<T> defmt::Format for VecView<T>
+where
+    T: defmt::Format,
+{
+    fn format(&self, fmt: Formatter<'_>) {
+        defmt::write!(fmt, "{=[?]}", self.as_slice())
+    }
+}
+
+impl<T, const N: usize> defmt::Format for crate::Deque<T, N>
+where
+    T: defmt::Format,
+{
+    fn format(&self, fmt: Formatter<'_>) {
+        self.as_view().format(fmt)
+    }
+}
+
+impl<T> defmt::Format for crate::DequeView<T>
+where
+    T: defmt::Format,
+{
+    fn format(&self, fmt: Formatter<'_>) {
+        // NOTE the contents may wrap around the end of the buffer so they can't be handed to
+        // `{=[?]}` as a single slice; emit the same encoding `write!` uses for one (see
+        // `InternalFormatter::fmt_slice`) from the iterator instead
+        let fmt = fmt.inner;
+        if fmt.needs_tag() {
+            fmt.istr(&defmt::intern!("{=[?]}"));
+        }
+        fmt.leb64(self.len());
+        for (i, item) in self.iter().enumerate() {
+            fmt.fmt(item, i != 0);
+        }
+    }
+}
+
+impl defmt::Format for crate::StringView {
+    fn format(&self, fmt: Formatter<'_>) {
+        defmt::write!(fmt, "{=str}", self.as_str());
+    }
+}
+
 impl<const N: usize> defmt::Format for crate::String<N>
 where
     u8: defmt::Format,
@@ -22,6 +65,12 @@ where
     }
 }
 
+impl<T> defmt::Format for CapacityError<T> {
+    fn format(&self, fmt: Formatter<'_>) {
+        defmt::write!(fmt, "insufficient capacity")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::Vec;
@@ -40,6 +89,34 @@ mod tests {
         f.finalize();
     }
 
+    #[test]
+    /// Tests that a Deque whose contents wrap around the end of its buffer is encoded as a single
+    /// list, like a Vec holding the same items
+    fn test_defmt_format_deque() {
+        let mut val: crate::Deque<u8, 4> = crate::Deque::new();
+        val.push_back(1).unwrap();
+        val.push_back(2).unwrap();
+        val.push_front(0).unwrap();
+        assert!(!val.as_slices().1.is_empty());
+
+        let index = defmt::export::fetch_string_index();
+        let mut f = defmt::InternalFormatter::new();
+        let g = defmt::Formatter { inner: &mut f };
+        val.format(g);
+        f.finalize();
+        assert_eq!(
+            f.bytes(),
+            &[
+                index,                        // "{=[?]}"
+                3,                            // length
+                index.wrapping_add(1) & 0x7f, // "{=u8}" / impl Format for u8
+                0,
+                1,
+                2,
+            ]
+        );
+    }
+
     /// Tests encoding String with defmt, asserting these types may be serialized
     /// Note: the exact wire format is NOT checked since its an unstable implementation detail of an external crate.
     /// based loosely on https://github.com/knurling-rs/defmt/blob/main/tests/encode.rs#L483
@@ -53,4 +130,14 @@ mod tests {
         val.format(g);
         f.finalize();
     }
+
+    #[test]
+    fn test_defmt_format_capacity_error() {
+        let val = crate::CapacityError::new(42);
+
+        let mut f = defmt::InternalFormatter::new();
+        let g = defmt::Formatter { inner: &mut f };
+        val.format(g);
+        f.finalize();
+    }
 }
@@ -0,0 +1,1417 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::{ops, ptr, slice};
+
+use crate::{CapacityError, TryFromIterator};
+
+/// A fixed capacity double-ended queue.
+///
+/// Unlike [`spsc::Queue`](crate::spsc::Queue) this is a plain, single-threaded ring buffer
+/// that can hold up to `N` elements (no slot is reserved to tell a full queue apart from an empty
+/// one).
+///
+/// # Examples
+///
+/// ```
+/// use heapless::Deque;
+///
+/// // A deque with a fixed capacity of 8 elements allocated on the stack
+/// let mut deque = Deque::<_, 8>::new();
+///
+/// // You can use it as a good old FIFO queue.
+/// deque.push_back(1).unwrap();
+/// deque.push_back(2).unwrap();
+/// assert_eq!(deque.len(), 2);
+///
+/// assert_eq!(deque.pop_front(), Some(1));
+/// assert_eq!(deque.pop_front(), Some(2));
+/// assert_eq!(deque.len(), 0);
+///
+/// // Deque is double-ended, you can push and pop from the front and back.
+/// deque.push_back(1).unwrap();
+/// deque.push_front(2).unwrap();
+/// deque.push_back(3).unwrap();
+/// deque.push_front(4).unwrap();
+/// assert_eq!(deque.pop_front(), Some(4));
+/// assert_eq!(deque.pop_front(), Some(2));
+/// assert_eq!(deque.pop_front(), Some(1));
+/// assert_eq!(deque.pop_front(), Some(3));
+///
+/// // You can iterate it, yielding all the elements front-to-back.
+/// for x in &deque {
+///     println!("{}", x);
+/// }
+/// ```
+#[repr(C)]
+pub struct Deque<T, const N: usize> {
+    // NOTE the layout must stay in sync with `DequeView`
+    /// Front index. Always 0..=(N-1)
+    front: usize,
+    /// Back index. Always 0..=(N-1).
+    back: usize,
+
+    /// Used to distinguish "empty" and "full" cases when `front == back`.
+    /// May only be `true` if `front == back`, always `false` otherwise.
+    full: bool,
+
+    buffer: [MaybeUninit<T>; N],
+}
+
+/// A [`Deque`] with a capacity that is not part of its type
+///
+/// `DequeView<T>` is to `Deque<T, N>` what `[T]` is to `[T; N]`: it's `!Sized` so it can only be
+/// used behind a reference, which is obtained with [`Deque::as_view`] or
+/// [`Deque::as_mut_view`].
+///
+/// # Examples
+///
+/// ```
+/// use heapless::{Deque, DequeView};
+///
+/// fn rotate(deque: &mut DequeView<u8>) {
+///     if let Some(x) = deque.pop_front() {
+///         deque.push_back(x).unwrap();
+///     }
+/// }
+///
+/// let mut deque: Deque<u8, 4> = Deque::new();
+/// deque.extend([1, 2, 3].iter());
+/// rotate(deque.as_mut_view());
+/// assert_eq!(deque.iter().copied().collect::<heapless::Vec<_, 4>>(), [2, 3, 1]);
+/// ```
+#[repr(C)]
+pub struct DequeView<T> {
+    front: usize,
+    back: usize,
+    full: bool,
+    buffer: [MaybeUninit<T>],
+}
+
+impl<T, const N: usize> Deque<T, N> {
+    const INIT: MaybeUninit<T> = MaybeUninit::uninit();
+
+    /// Constructs a new, empty deque with a fixed capacity of `N`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// // allocate the deque on the stack
+    /// let mut x: Deque<u8, 16> = Deque::new();
+    ///
+    /// // allocate the deque in a static variable
+    /// static mut X: Deque<u8, 16> = Deque::new();
+    /// ```
+    pub const fn new() -> Self {
+        // Const assert N > 0
+        crate::sealed::greater_than_0::<N>();
+
+        Self {
+            front: 0,
+            back: 0,
+            full: false,
+            buffer: [Self::INIT; N],
+        }
+    }
+
+    /// Returns a view of this deque that doesn't carry its capacity in its type
+    #[inline]
+    pub fn as_view(&self) -> &DequeView<T> {
+        // SAFETY: `Deque` and `DequeView` are `#[repr(C)]` with the same fields, in the same
+        // order, and the `N` elements of the buffer become the length of the trailing slice
+        unsafe {
+            &*(ptr::slice_from_raw_parts(self as *const Self as *const MaybeUninit<T>, N)
+                as *const DequeView<T>)
+        }
+    }
+
+    /// Returns a mutable view of this deque that doesn't carry its capacity in its type
+    #[inline]
+    pub fn as_mut_view(&mut self) -> &mut DequeView<T> {
+        // SAFETY: see `as_view`
+        unsafe {
+            &mut *(ptr::slice_from_raw_parts_mut(self as *mut Self as *mut MaybeUninit<T>, N)
+                as *mut DequeView<T>)
+        }
+    }
+
+    /// Returns the maximum number of elements the deque can hold.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of elements currently in the deque.
+    pub fn len(&self) -> usize {
+        self.as_view().len()
+    }
+
+    /// Clears the deque, removing all values.
+    pub fn clear(&mut self) {
+        self.as_mut_view().clear()
+    }
+
+    /// Returns whether the deque is empty.
+    pub fn is_empty(&self) -> bool {
+        self.as_view().is_empty()
+    }
+
+    /// Returns whether the deque is full (i.e. if `len() == capacity()`.
+    pub fn is_full(&self) -> bool {
+        self.as_view().is_full()
+    }
+
+    /// Returns a pair of slices which contain, in order, the contents of the `Deque`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let mut deque: Deque<_, 4> = Deque::new();
+    /// deque.push_back(1).unwrap();
+    /// deque.push_back(2).unwrap();
+    /// deque.push_front(0).unwrap();
+    ///
+    /// assert_eq!(deque.as_slices(), (&[0][..], &[1, 2][..]));
+    /// ```
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        self.as_view().as_slices()
+    }
+
+    /// Returns a pair of mutable slices which contain, in order, the contents of the `Deque`.
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        self.as_mut_view().as_mut_slices()
+    }
+
+    /// Rearranges the internal storage of the deque so that its contents are contiguous, and
+    /// returns them as a single mutable slice, in order.
+    ///
+    /// This does not change the order of the elements, only where they are stored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Deque;
+    ///
+    /// let mut deque: Deque<_, 4> = Deque::new();
+    /// deque.push_back(2).unwrap();
+    /// deque.push_back(3).unwrap();
+    /// deque.push_front(1).unwrap();
+    /// deque.push_front(0).unwrap();
+    ///
+    /// assert_eq!(deque.make_contiguous(), &[0, 1, 2, 3]);
+    /// assert_eq!(deque.as_slices(), (&[0, 1, 2, 3][..], &[][..]));
+    /// ```
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        self.as_mut_view().make_contiguous()
+    }
+
+    /// Provides a reference to the front element, or None if the `Deque` is empty.
+    pub fn front(&self) -> Option<&T> {
+        self.as_view().front()
+    }
+
+    /// Provides a mutable reference to the front element, or None if the `Deque` is empty.
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.as_mut_view().front_mut()
+    }
+
+    /// Provides a reference to the back element, or None if the `Deque` is empty.
+    pub fn back(&self) -> Option<&T> {
+        self.as_view().back()
+    }
+
+    /// Provides a mutable reference to the back element, or None if the `Deque` is empty.
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.as_mut_view().back_mut()
+    }
+
+    /// Returns a reference to the element at `index` counting from the front, or `None` if
+    /// `index` is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.as_view().get(index)
+    }
+
+    /// Returns a mutable reference to the element at `index` counting from the front, or `None`
+    /// if `index` is out of bounds.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.as_mut_view().get_mut(index)
+    }
+
+    /// Removes the item from the front of the deque and returns it, or `None` if it's empty
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.as_mut_view().pop_front()
+    }
+
+    /// Removes the item from the back of the deque and returns it, or `None` if it's empty
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.as_mut_view().pop_back()
+    }
+
+    /// Appends an `item` to the front of the deque
+    ///
+    /// Returns back the `item` if the deque is full
+    pub fn push_front(&mut self, item: T) -> Result<(), T> {
+        self.as_mut_view().push_front(item)
+    }
+
+    /// Appends an `item` to the back of the deque
+    ///
+    /// Returns back the `item` if the deque is full
+    pub fn push_back(&mut self, item: T) -> Result<(), T> {
+        self.as_mut_view().push_back(item)
+    }
+
+    /// Removes an item from the front of the deque and returns it, without checking that the deque
+    /// is not empty
+    ///
+    /// # Safety
+    ///
+    /// It's undefined behavior to call this on an empty deque
+    pub unsafe fn pop_front_unchecked(&mut self) -> T {
+        self.as_mut_view().pop_front_unchecked()
+    }
+
+    /// Removes an item from the back of the deque and returns it, without checking that the deque
+    /// is not empty
+    ///
+    /// # Safety
+    ///
+    /// It's undefined behavior to call this on an empty deque
+    pub unsafe fn pop_back_unchecked(&mut self) -> T {
+        self.as_mut_view().pop_back_unchecked()
+    }
+
+    /// Appends an `item` to the front of the deque
+    ///
+    /// # Safety
+    ///
+    /// This assumes the deque is not full.
+    pub unsafe fn push_front_unchecked(&mut self, item: T) {
+        self.as_mut_view().push_front_unchecked(item)
+    }
+
+    /// Appends an `item` to the back of the deque
+    ///
+    /// # Safety
+    ///
+    /// This assumes the deque is not full.
+    pub unsafe fn push_back_unchecked(&mut self, item: T) {
+        self.as_mut_view().push_back_unchecked(item)
+    }
+
+    /// Returns an iterator over the deque, front to back.
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.as_view().iter()
+    }
+
+    /// Returns an iterator that allows modifying each value, front to back.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.as_mut_view().iter_mut()
+    }
+}
+
+impl<T> DequeView<T> {
+    /// Returns the maximum number of elements the deque can hold.
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    fn increment(&self, i: usize) -> usize {
+        increment(i, self.capacity())
+    }
+
+    fn decrement(&self, i: usize) -> usize {
+        decrement(i, self.capacity())
+    }
+
+    /// Returns the number of elements currently in the deque.
+    pub fn len(&self) -> usize {
+        if self.full {
+            self.capacity()
+        } else if self.back < self.front {
+            self.back + self.capacity() - self.front
+        } else {
+            self.back - self.front
+        }
+    }
+
+    /// Clears the deque, removing all values.
+    pub fn clear(&mut self) {
+        // safety: we're immediately setting a consistent empty state.
+        unsafe { self.drop_contents() }
+        self.front = 0;
+        self.back = 0;
+        self.full = false;
+    }
+
+    /// Drop all items in the `Deque`, leaving the state `back/front/full` unmodified.
+    ///
+    /// safety: leaves the `Deque` in an inconsistent state, so can cause duplicate drops.
+    unsafe fn drop_contents(&mut self) {
+        // We drop each element used in the deque by turning into a &mut[T]
+        let (a, b) = self.as_mut_slices();
+        ptr::drop_in_place(a);
+        ptr::drop_in_place(b);
+    }
+
+    /// Returns whether the deque is empty.
+    pub fn is_empty(&self) -> bool {
+        self.front == self.back && !self.full
+    }
+
+    /// Returns whether the deque is full (i.e. if `len() == capacity()`.
+    pub fn is_full(&self) -> bool {
+        self.full
+    }
+
+    /// Returns a pair of slices which contain, in order, the contents of the `Deque`.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        // NOTE(unsafe) avoid bound checks in the slicing operation
+        unsafe {
+            if self.is_empty() {
+                (&[], &[])
+            } else if self.back <= self.front {
+                (
+                    slice::from_raw_parts(
+                        self.buffer.as_ptr().add(self.front) as *const T,
+                        self.capacity() - self.front,
+                    ),
+                    slice::from_raw_parts(self.buffer.as_ptr() as *const T, self.back),
+                )
+            } else {
+                (
+                    slice::from_raw_parts(
+                        self.buffer.as_ptr().add(self.front) as *const T,
+                        self.back - self.front,
+                    ),
+                    &[],
+                )
+            }
+        }
+    }
+
+    /// Returns a pair of mutable slices which contain, in order, the contents of the `Deque`.
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let cap = self.capacity();
+        let ptr = self.buffer.as_mut_ptr();
+
+        // NOTE(unsafe) avoid bound checks in the slicing operation
+        unsafe {
+            if self.is_empty() {
+                (&mut [], &mut [])
+            } else if self.back <= self.front {
+                (
+                    slice::from_raw_parts_mut(ptr.add(self.front) as *mut T, cap - self.front),
+                    slice::from_raw_parts_mut(ptr as *mut T, self.back),
+                )
+            } else {
+                (
+                    slice::from_raw_parts_mut(
+                        ptr.add(self.front) as *mut T,
+                        self.back - self.front,
+                    ),
+                    &mut [],
+                )
+            }
+        }
+    }
+
+    /// Rearranges the internal storage of the deque so that its contents are contiguous, and
+    /// returns them as a single mutable slice, in order.
+    ///
+    /// This does not change the order of the elements, only where they are stored.
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        let len = self.len();
+
+        if self.front + len > self.capacity() {
+            // the contents wrap around the end of the buffer; rotate the whole buffer left by
+            // `front` so that the front element ends up at index 0. The slots that are not in use
+            // are uninitialized but rotating `MaybeUninit`s only moves bytes around
+            self.buffer.rotate_left(self.front);
+            self.front = 0;
+            self.back = if self.full { 0 } else { len };
+        }
+
+        // NOTE(unsafe) the `len` elements starting at `front` are initialized and contiguous
+        unsafe {
+            slice::from_raw_parts_mut(self.buffer.as_mut_ptr().add(self.front) as *mut T, len)
+        }
+    }
+
+    /// Provides a reference to the front element, or None if the `Deque` is empty.
+    pub fn front(&self) -> Option<&T> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(unsafe { &*self.buffer.get_unchecked(self.front).as_ptr() })
+        }
+    }
+
+    /// Provides a mutable reference to the front element, or None if the `Deque` is empty.
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(unsafe { &mut *self.buffer.get_unchecked_mut(self.front).as_mut_ptr() })
+        }
+    }
+
+    /// Provides a reference to the back element, or None if the `Deque` is empty.
+    pub fn back(&self) -> Option<&T> {
+        if self.is_empty() {
+            None
+        } else {
+            let index = self.decrement(self.back);
+            Some(unsafe { &*self.buffer.get_unchecked(index).as_ptr() })
+        }
+    }
+
+    /// Provides a mutable reference to the back element, or None if the `Deque` is empty.
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        if self.is_empty() {
+            None
+        } else {
+            let index = self.decrement(self.back);
+            Some(unsafe { &mut *self.buffer.get_unchecked_mut(index).as_mut_ptr() })
+        }
+    }
+
+    /// Returns a reference to the element at `index` counting from the front, or `None` if
+    /// `index` is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len() {
+            let idx = self.to_physical_index(index);
+            Some(unsafe { &*self.buffer.get_unchecked(idx).as_ptr() })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the element at `index` counting from the front, or `None`
+    /// if `index` is out of bounds.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.len() {
+            let idx = self.to_physical_index(index);
+            Some(unsafe { &mut *self.buffer.get_unchecked_mut(idx).as_mut_ptr() })
+        } else {
+            None
+        }
+    }
+
+    fn to_physical_index(&self, index: usize) -> usize {
+        let idx = self.front + index;
+        if idx >= self.capacity() {
+            idx - self.capacity()
+        } else {
+            idx
+        }
+    }
+
+    /// Removes the item from the front of the deque and returns it, or `None` if it's empty
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(unsafe { self.pop_front_unchecked() })
+        }
+    }
+
+    /// Removes the item from the back of the deque and returns it, or `None` if it's empty
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(unsafe { self.pop_back_unchecked() })
+        }
+    }
+
+    /// Appends an `item` to the front of the deque
+    ///
+    /// Returns back the `item` if the deque is full
+    pub fn push_front(&mut self, item: T) -> Result<(), T> {
+        if self.is_full() {
+            Err(item)
+        } else {
+            unsafe { self.push_front_unchecked(item) }
+            Ok(())
+        }
+    }
+
+    /// Appends an `item` to the back of the deque
+    ///
+    /// Returns back the `item` if the deque is full
+    pub fn push_back(&mut self, item: T) -> Result<(), T> {
+        if self.is_full() {
+            Err(item)
+        } else {
+            unsafe { self.push_back_unchecked(item) }
+            Ok(())
+        }
+    }
+
+    /// Removes an item from the front of the deque and returns it, without checking that the deque
+    /// is not empty
+    ///
+    /// # Safety
+    ///
+    /// It's undefined behavior to call this on an empty deque
+    pub unsafe fn pop_front_unchecked(&mut self) -> T {
+        debug_assert!(!self.is_empty());
+
+        let index = self.front;
+        self.full = false;
+        self.front = self.increment(self.front);
+        self.buffer.get_unchecked_mut(index).as_ptr().read()
+    }
+
+    /// Removes an item from the back of the deque and returns it, without checking that the deque
+    /// is not empty
+    ///
+    /// # Safety
+    ///
+    /// It's undefined behavior to call this on an empty deque
+    pub unsafe fn pop_back_unchecked(&mut self) -> T {
+        debug_assert!(!self.is_empty());
+
+        self.full = false;
+        self.back = self.decrement(self.back);
+        self.buffer.get_unchecked_mut(self.back).as_ptr().read()
+    }
+
+    /// Appends an `item` to the front of the deque
+    ///
+    /// # Safety
+    ///
+    /// This assumes the deque is not full.
+    pub unsafe fn push_front_unchecked(&mut self, item: T) {
+        debug_assert!(!self.is_full());
+
+        let index = self.decrement(self.front);
+        // NOTE: the memory slot that we are about to write to is uninitialized. We assign
+        // a `MaybeUninit` to avoid running `T`'s destructor on the uninitialized memory
+        *self.buffer.get_unchecked_mut(index) = MaybeUninit::new(item);
+        self.front = index;
+        if self.front == self.back {
+            self.full = true;
+        }
+    }
+
+    /// Appends an `item` to the back of the deque
+    ///
+    /// # Safety
+    ///
+    /// This assumes the deque is not full.
+    pub unsafe fn push_back_unchecked(&mut self, item: T) {
+        debug_assert!(!self.is_full());
+
+        // NOTE: the memory slot that we are about to write to is uninitialized. We assign
+        // a `MaybeUninit` to avoid running `T`'s destructor on the uninitialized memory
+        *self.buffer.get_unchecked_mut(self.back) = MaybeUninit::new(item);
+        self.back = self.increment(self.back);
+        if self.front == self.back {
+            self.full = true;
+        }
+    }
+
+    /// Returns an iterator over the deque, front to back.
+    pub fn iter(&self) -> Iter<'_, T> {
+        let done = self.is_empty();
+        Iter {
+            _phantom: PhantomData,
+            buffer: self.buffer.as_ptr(),
+            capacity: self.capacity(),
+            front: self.front,
+            back: self.back,
+            done,
+        }
+    }
+
+    /// Returns an iterator that allows modifying each value, front to back.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let done = self.is_empty();
+        IterMut {
+            _phantom: PhantomData,
+            capacity: self.capacity(),
+            buffer: self.buffer.as_mut_ptr(),
+            front: self.front,
+            back: self.back,
+            done,
+        }
+    }
+}
+
+fn increment(i: usize, capacity: usize) -> usize {
+    if i + 1 == capacity {
+        0
+    } else {
+        i + 1
+    }
+}
+
+fn decrement(i: usize, capacity: usize) -> usize {
+    if i == 0 {
+        capacity - 1
+    } else {
+        i - 1
+    }
+}
+
+// Trait implementations
+
+impl<T, const N: usize> Default for Deque<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Drop for Deque<T, N> {
+    fn drop(&mut self) {
+        // safety: `self` is left in an inconsistent state but it doesn't matter since
+        // it's getting dropped. Nothing should be able to observe `self` after drop.
+        unsafe { self.as_mut_view().drop_contents() }
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for Deque<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T, const N: usize> Extend<T> for Deque<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push_back(item).ok().unwrap();
+        }
+    }
+}
+
+impl<'a, T: 'a + Copy, const N: usize> Extend<&'a T> for Deque<T, N> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+impl<T, const N: usize> TryFromIterator<T> for Deque<T, N> {
+    fn try_from_iter<I>(iter: I) -> Result<Self, CapacityError<T>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut deque = Deque::new();
+        for item in iter {
+            deque.push_back(item).map_err(CapacityError::new)?;
+        }
+        Ok(deque)
+    }
+}
+
+impl<T, const N: usize> ops::Index<usize> for Deque<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        self.get(index).expect("Out of bounds access")
+    }
+}
+
+impl<T, const N: usize> ops::IndexMut<usize> for Deque<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        self.get_mut(index).expect("Out of bounds access")
+    }
+}
+
+impl<T, const N: usize> Clone for Deque<T, N>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        let mut res = Deque::new();
+        for i in self {
+            // safety: the original and new deques have the same capacity, so it can
+            // not become full.
+            unsafe { res.push_back_unchecked(i.clone()) }
+        }
+        res
+    }
+}
+
+impl<A, B, const N1: usize, const N2: usize> PartialEq<Deque<B, N2>> for Deque<A, N1>
+where
+    A: PartialEq<B>,
+{
+    fn eq(&self, other: &Deque<B, N2>) -> bool {
+        self.as_view() == other.as_view()
+    }
+}
+
+impl<T, const N: usize> Eq for Deque<T, N> where T: Eq {}
+
+impl<T, const N: usize> core::hash::Hash for Deque<T, N>
+where
+    T: core::hash::Hash,
+{
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_view().hash(state)
+    }
+}
+
+impl<T, const N: usize> hash32::Hash for Deque<T, N>
+where
+    T: hash32::Hash,
+{
+    fn hash<H: hash32::Hasher>(&self, state: &mut H) {
+        hash32::Hash::hash(self.as_view(), state)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for DequeView<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T> Extend<T> for DequeView<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push_back(item).ok().unwrap();
+        }
+    }
+}
+
+impl<'a, T: 'a + Copy> Extend<&'a T> for DequeView<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+impl<T> ops::Index<usize> for DequeView<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        self.get(index).expect("Out of bounds access")
+    }
+}
+
+impl<T> ops::IndexMut<usize> for DequeView<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        self.get_mut(index).expect("Out of bounds access")
+    }
+}
+
+impl<A, B> PartialEq<DequeView<B>> for DequeView<A>
+where
+    A: PartialEq<B>,
+{
+    fn eq(&self, other: &DequeView<B>) -> bool {
+        self.len() == other.len() && self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
+
+impl<T> Eq for DequeView<T> where T: Eq {}
+
+impl<T> core::hash::Hash for DequeView<T>
+where
+    T: core::hash::Hash,
+{
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        // prefix-free, like `[T]`
+        self.len().hash(state);
+        for item in self {
+            item.hash(state);
+        }
+    }
+}
+
+impl<T> hash32::Hash for DequeView<T>
+where
+    T: hash32::Hash,
+{
+    fn hash<H: hash32::Hasher>(&self, state: &mut H) {
+        // prefix-free, like `[T]`
+        hash32::Hash::hash(&self.len(), state);
+        for item in self {
+            hash32::Hash::hash(item, state);
+        }
+    }
+}
+
+/// An iterator that moves out of a [`Deque`].
+///
+/// This struct is created by calling the `into_iter` method.
+#[derive(Clone)]
+pub struct IntoIter<T, const N: usize> {
+    deque: Deque<T, N>,
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.deque.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.deque.len();
+        (len, Some(len))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.deque.pop_back()
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
+
+impl<T, const N: usize> FusedIterator for IntoIter<T, N> {}
+
+impl<T, const N: usize> IntoIterator for Deque<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { deque: self }
+    }
+}
+
+/// An iterator over the elements of a [`Deque`].
+///
+/// This struct is created by calling the `iter` method.
+#[derive(Clone)]
+pub struct Iter<'a, T> {
+    buffer: *const MaybeUninit<T>,
+    capacity: usize,
+    _phantom: PhantomData<&'a T>,
+    front: usize,
+    back: usize,
+    done: bool,
+}
+
+// SAFETY: `Iter` only hands out `&T`s, like the `&[T]` it stands for
+unsafe impl<T: Sync> Send for Iter<'_, T> {}
+unsafe impl<T: Sync> Sync for Iter<'_, T> {}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            None
+        } else {
+            let index = self.front;
+            self.front = increment(self.front, self.capacity);
+            if self.front == self.back {
+                self.done = true;
+            }
+            Some(unsafe { &*(self.buffer.add(index) as *const T) })
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = if self.done {
+            0
+        } else if self.back <= self.front {
+            self.back + self.capacity - self.front
+        } else {
+            self.back - self.front
+        };
+
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.done {
+            None
+        } else {
+            self.back = decrement(self.back, self.capacity);
+            if self.front == self.back {
+                self.done = true;
+            }
+            Some(unsafe { &*(self.buffer.add(self.back) as *const T) })
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+impl<'a, T> FusedIterator for Iter<'a, T> {}
+
+/// An iterator over the elements of a [`Deque`].
+///
+/// This struct is created by calling the `iter_mut` method.
+pub struct IterMut<'a, T> {
+    buffer: *mut MaybeUninit<T>,
+    capacity: usize,
+    _phantom: PhantomData<&'a mut T>,
+    front: usize,
+    back: usize,
+    done: bool,
+}
+
+// SAFETY: `IterMut` only hands out `&mut T`s, like the `&mut [T]` it stands for
+unsafe impl<T: Send> Send for IterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for IterMut<'_, T> {}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            None
+        } else {
+            let index = self.front;
+            self.front = increment(self.front, self.capacity);
+            if self.front == self.back {
+                self.done = true;
+            }
+            Some(unsafe { &mut *(self.buffer.add(index) as *mut T) })
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = if self.done {
+            0
+        } else if self.back <= self.front {
+            self.back + self.capacity - self.front
+        } else {
+            self.back - self.front
+        };
+
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.done {
+            None
+        } else {
+            self.back = decrement(self.back, self.capacity);
+            if self.front == self.back {
+                self.done = true;
+            }
+            Some(unsafe { &mut *(self.buffer.add(self.back) as *mut T) })
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+impl<'a, T> FusedIterator for IterMut<'a, T> {}
+
+impl<'a, T, const N: usize> IntoIterator for &'a Deque<T, N> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut Deque<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a DequeView<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut DequeView<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Deque, DequeView};
+
+    #[test]
+    fn static_new() {
+        static mut _V: Deque<i32, 4> = Deque::new();
+    }
+
+    macro_rules! droppable {
+        () => {
+            struct Droppable;
+            impl Droppable {
+                fn new() -> Self {
+                    unsafe {
+                        COUNT += 1;
+                    }
+                    Droppable
+                }
+            }
+            impl Drop for Droppable {
+                fn drop(&mut self) {
+                    unsafe {
+                        COUNT -= 1;
+                    }
+                }
+            }
+
+            static mut COUNT: i32 = 0;
+        };
+    }
+
+    #[test]
+    fn drop() {
+        droppable!();
+
+        {
+            let mut v: Deque<Droppable, 2> = Deque::new();
+            v.push_back(Droppable::new()).ok().unwrap();
+            v.push_back(Droppable::new()).ok().unwrap();
+            v.pop_front().unwrap();
+        }
+
+        assert_eq!(unsafe { COUNT }, 0);
+
+        {
+            let mut v: Deque<Droppable, 2> = Deque::new();
+            v.push_back(Droppable::new()).ok().unwrap();
+            v.push_back(Droppable::new()).ok().unwrap();
+        }
+
+        assert_eq!(unsafe { COUNT }, 0);
+
+        {
+            let mut v: Deque<Droppable, 2> = Deque::new();
+            v.push_front(Droppable::new()).ok().unwrap();
+            v.push_front(Droppable::new()).ok().unwrap();
+        }
+
+        assert_eq!(unsafe { COUNT }, 0);
+
+        {
+            let mut v: Deque<Droppable, 3> = Deque::new();
+            v.push_back(Droppable::new()).ok().unwrap();
+            v.push_back(Droppable::new()).ok().unwrap();
+            v.push_front(Droppable::new()).ok().unwrap();
+            let mut it = v.into_iter();
+            it.next().unwrap();
+        }
+
+        assert_eq!(unsafe { COUNT }, 0);
+    }
+
+    #[test]
+    fn full() {
+        let mut v: Deque<i32, 4> = Deque::new();
+
+        v.push_back(0).unwrap();
+        v.push_front(1).unwrap();
+        v.push_back(2).unwrap();
+        v.push_back(3).unwrap();
+
+        assert!(v.push_front(4).is_err());
+        assert!(v.push_back(4).is_err());
+        assert!(v.is_full());
+        assert_eq!(v.len(), 4);
+    }
+
+    #[test]
+    fn empty() {
+        let mut v: Deque<i32, 4> = Deque::new();
+        assert!(v.is_empty());
+
+        v.push_back(0).unwrap();
+        assert!(!v.is_empty());
+
+        v.push_front(1).unwrap();
+        assert!(!v.is_empty());
+
+        v.pop_front().unwrap();
+        v.pop_front().unwrap();
+
+        assert!(v.pop_front().is_none());
+        assert!(v.pop_back().is_none());
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn front_back() {
+        let mut v: Deque<i32, 4> = Deque::new();
+        assert_eq!(v.front(), None);
+        assert_eq!(v.front_mut(), None);
+        assert_eq!(v.back(), None);
+        assert_eq!(v.back_mut(), None);
+
+        v.push_back(4).unwrap();
+        assert_eq!(v.front(), Some(&4));
+        assert_eq!(v.front_mut(), Some(&mut 4));
+        assert_eq!(v.back(), Some(&4));
+        assert_eq!(v.back_mut(), Some(&mut 4));
+
+        v.push_front(3).unwrap();
+        assert_eq!(v.front(), Some(&3));
+        assert_eq!(v.back(), Some(&4));
+
+        v.pop_back().unwrap();
+        assert_eq!(v.front(), Some(&3));
+        assert_eq!(v.back(), Some(&3));
+
+        v.pop_front().unwrap();
+        assert_eq!(v.front(), None);
+        assert_eq!(v.back(), None);
+    }
+
+    #[test]
+    fn iter() {
+        let mut v: Deque<i32, 4> = Deque::new();
+
+        v.push_back(0).unwrap();
+        v.push_back(1).unwrap();
+        v.push_front(2).unwrap();
+        v.push_front(3).unwrap();
+        v.pop_back().unwrap();
+        v.push_front(4).unwrap();
+
+        let mut items = v.iter();
+
+        assert_eq!(items.len(), 4);
+        assert_eq!(items.next(), Some(&4));
+        assert_eq!(items.next(), Some(&3));
+        assert_eq!(items.next(), Some(&2));
+        assert_eq!(items.next(), Some(&0));
+        assert_eq!(items.next(), None);
+        assert_eq!(items.len(), 0);
+    }
+
+    #[test]
+    fn iter_rev() {
+        let mut v: Deque<i32, 4> = Deque::new();
+
+        v.push_back(0).unwrap();
+        v.push_back(1).unwrap();
+        v.push_front(2).unwrap();
+        v.push_front(3).unwrap();
+        v.pop_back().unwrap();
+        v.push_front(4).unwrap();
+
+        let mut items = v.iter().rev();
+
+        assert_eq!(items.next(), Some(&0));
+        assert_eq!(items.next(), Some(&2));
+        assert_eq!(items.next(), Some(&3));
+        assert_eq!(items.next(), Some(&4));
+        assert_eq!(items.next(), None);
+
+        let mut items = v.iter();
+        assert_eq!(items.next(), Some(&4));
+        assert_eq!(items.next_back(), Some(&0));
+        assert_eq!(items.next(), Some(&3));
+        assert_eq!(items.next_back(), Some(&2));
+        assert_eq!(items.next(), None);
+        assert_eq!(items.next_back(), None);
+    }
+
+    #[test]
+    fn iter_mut() {
+        let mut v: Deque<i32, 4> = Deque::new();
+
+        v.push_back(0).unwrap();
+        v.push_back(1).unwrap();
+        v.push_front(2).unwrap();
+        v.push_front(3).unwrap();
+        v.pop_back().unwrap();
+        v.push_front(4).unwrap();
+
+        for x in v.iter_mut().rev() {
+            *x *= 10;
+        }
+
+        let mut items = v.iter_mut();
+
+        assert_eq!(items.next(), Some(&mut 40));
+        assert_eq!(items.next(), Some(&mut 30));
+        assert_eq!(items.next(), Some(&mut 20));
+        assert_eq!(items.next(), Some(&mut 0));
+        assert_eq!(items.next(), None);
+    }
+
+    #[test]
+    fn iter_move() {
+        let mut v: Deque<i32, 4> = Deque::new();
+        v.push_back(0).unwrap();
+        v.push_back(1).unwrap();
+        v.push_back(2).unwrap();
+        v.push_back(3).unwrap();
+
+        let mut items = v.into_iter();
+
+        assert_eq!(items.next(), Some(0));
+        assert_eq!(items.next_back(), Some(3));
+        assert_eq!(items.next(), Some(1));
+        assert_eq!(items.next(), Some(2));
+        assert_eq!(items.next(), None);
+    }
+
+    #[test]
+    fn as_slices_and_make_contiguous() {
+        let mut v: Deque<i32, 4> = Deque::new();
+        assert_eq!(v.as_slices(), (&[][..], &[][..]));
+        assert_eq!(v.make_contiguous(), &[]);
+
+        v.push_back(1).unwrap();
+        v.push_back(2).unwrap();
+        v.push_front(0).unwrap();
+        assert_eq!(v.as_slices(), (&[0][..], &[1, 2][..]));
+
+        v.push_front(-1).unwrap();
+        assert_eq!(v.as_slices(), (&[-1, 0][..], &[1, 2][..]));
+
+        assert_eq!(v.make_contiguous(), &[-1, 0, 1, 2]);
+        assert_eq!(v.as_slices(), (&[-1, 0, 1, 2][..], &[][..]));
+        assert!(v.is_full());
+
+        // still a working deque after moving things around
+        assert_eq!(v.pop_back(), Some(2));
+        v.push_front(-2).unwrap();
+        assert_eq!(v.make_contiguous(), &[-2, -1, 0, 1]);
+        assert_eq!(v.pop_front(), Some(-2));
+        assert_eq!(v.pop_back(), Some(1));
+        assert_eq!(v.len(), 2);
+    }
+
+    #[test]
+    fn index() {
+        let mut v: Deque<i32, 4> = Deque::new();
+        v.push_back(2).unwrap();
+        v.push_front(1).unwrap();
+        v.push_front(0).unwrap();
+
+        assert_eq!(v[0], 0);
+        assert_eq!(v[2], 2);
+        assert_eq!(v.get(3), None);
+
+        v[1] = 10;
+        assert_eq!(v.iter().copied().collect::<std::vec::Vec<_>>(), [0, 10, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds() {
+        let mut v: Deque<i32, 4> = Deque::new();
+        v.push_back(0).unwrap();
+        let _ = v[1];
+    }
+
+    #[test]
+    fn wrap_front_and_back() {
+        let mut v: Deque<i32, 3> = Deque::new();
+
+        for i in 0..10 {
+            v.push_back(i).unwrap();
+            assert_eq!(v.pop_front(), Some(i));
+
+            v.push_front(i).unwrap();
+            assert_eq!(v.pop_back(), Some(i));
+        }
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn eq_hash_clone() {
+        use core::hash::{Hash, Hasher};
+        use std::collections::hash_map::DefaultHasher;
+
+        let mut a: Deque<i32, 4> = Deque::new();
+        a.push_back(1).unwrap();
+        a.push_back(2).unwrap();
+
+        let mut b: Deque<i32, 8> = Deque::new();
+        b.push_front(2).unwrap();
+        b.push_front(1).unwrap();
+
+        assert_eq!(a, b);
+
+        let c = a.clone();
+        assert_eq!(a, c);
+
+        let hash = |d: &Deque<i32, 4>| {
+            let mut h = DefaultHasher::new();
+            d.hash(&mut h);
+            h.finish()
+        };
+        let mut d: Deque<i32, 4> = Deque::new();
+        d.push_front(2).unwrap();
+        d.push_front(1).unwrap();
+        assert_eq!(hash(&a), hash(&d));
+
+        b.push_back(3).unwrap();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn extend_and_debug() {
+        let mut v: Deque<i32, 4> = Deque::new();
+        v.extend([1, 2].iter());
+        v.push_front(0).unwrap();
+
+        assert_eq!(std::format!("{:?}", v), "[0, 1, 2]");
+    }
+
+    #[test]
+    fn view() {
+        fn rotate(d: &mut DequeView<i32>) {
+            let x = d.pop_front().unwrap();
+            d.push_back(x).unwrap();
+        }
+
+        let mut a: Deque<i32, 3> = Deque::new();
+        a.extend([1, 2, 3].iter());
+        let mut b: Deque<i32, 8> = Deque::new();
+        b.extend([1, 2, 3].iter());
+
+        rotate(a.as_mut_view());
+        rotate(b.as_mut_view());
+        assert_eq!(a, b);
+        assert_eq!(a.as_view(), b.as_view());
+        assert_eq!(
+            a.as_view()
+                .iter()
+                .rev()
+                .copied()
+                .collect::<std::vec::Vec<_>>(),
+            [1, 3, 2]
+        );
+
+        let view = a.as_mut_view();
+        assert_eq!(view.capacity(), 3);
+        assert!(view.is_full());
+        assert_eq!(view.make_contiguous(), &[2, 3, 1]);
+        view[0] = 20;
+        assert_eq!(view.front(), Some(&20));
+    }
+
+    #[test]
+    fn try_from_iter() {
+        use crate::{CapacityError, TryCollect, TryFromIterator};
+
+        let q = (0..4).try_collect::<Deque<_, 4>>().unwrap();
+        assert!(q.is_full());
+        assert!(q.iter().eq(&[0, 1, 2, 3]));
+
+        assert_eq!(
+            Deque::<_, 4>::try_from_iter(0..8).err(),
+            Some(CapacityError::new(4))
+        );
+    }
+}
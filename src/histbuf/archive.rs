@@ -0,0 +1,350 @@
+use super::{HistoryBuffer, OldestOrdered, Sample};
+use crate::sealed::histbuf::Cascade;
+
+/// How the samples of a tier are combined into one sample of the next, coarser, tier
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Consolidation {
+    /// The mean of the samples, rounded toward zero for integers
+    Average,
+    /// The smallest sample
+    Min,
+    /// The largest sample
+    Max,
+    /// The most recent sample
+    Last,
+}
+
+impl Consolidation {
+    // consolidates the `steps` most recent samples of `buffer`
+    fn apply<T, const N: usize>(self, buffer: &HistoryBuffer<T, N>, steps: usize) -> T
+    where
+        T: Sample,
+    {
+        let samples = (0..steps).map(|age| buffer[age]);
+        match self {
+            Consolidation::Average => T::mean(
+                samples.fold(T::Sum::default(), |sum, x| sum + x.to_sum()),
+                steps,
+            ),
+            Consolidation::Min => samples
+                .reduce(|min, x| if x < min { x } else { min })
+                .unwrap(),
+            Consolidation::Max => samples
+                .reduce(|max, x| if x > max { x } else { max })
+                .unwrap(),
+            Consolidation::Last => buffer[0],
+        }
+    }
+}
+
+/// A multi-resolution history, in the style of a round-robin database
+///
+/// An archive is a chain of [`HistoryBuffer`]s, or tiers, from the finest to the coarsest.
+/// Samples are written to the first tier; each time a tier has received `steps` new samples, they
+/// are consolidated into one sample of the next tier. The coarser tiers are `Archive`s
+/// themselves: `Archive<T, Next, N>` holds `N` samples and hands its consolidated samples to
+/// `Next`, which is `()` for the last tier.
+///
+/// With the `serde` feature an archive serializes to a struct holding the samples of its first
+/// tier, from the oldest to the most recent, and, unless it's the last tier, the consolidation
+/// function, `steps`, the number of samples written since the last consolidation and the next
+/// tier.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::histbuf::{Archive, Consolidation};
+///
+/// // 60 samples at 1 s, 60 at 1 min and 24 at 1 h
+/// let mut archive: Archive<u16, Archive<u16, Archive<u16, (), 24>, 60>, 60> = Archive::with_next(
+///     60,
+///     Consolidation::Average,
+///     Archive::with_next(60, Consolidation::Average, Archive::new()),
+/// );
+///
+/// // two minutes of samples
+/// for i in 0..120 {
+///     archive.write(if i < 60 { 10 } else { 20 });
+/// }
+///
+/// assert_eq!(archive.tier_count(), 3);
+/// assert_eq!(archive.tier(0).unwrap().len(), 60);
+/// assert!(archive.tier(1).unwrap().eq(&[10, 20]));
+/// assert_eq!(archive.tier(2).unwrap().len(), 0);
+/// ```
+pub struct Archive<T, Next, const N: usize> {
+    buffer: HistoryBuffer<T, N>,
+    // samples written since the last consolidation
+    pending: usize,
+    // number of samples consolidated into one sample of `next`
+    steps: usize,
+    consolidation: Consolidation,
+    next: Next,
+}
+
+impl<T, const N: usize> Archive<T, (), N> {
+    /// Creates an archive with a single tier of `N` samples
+    pub const fn new() -> Self {
+        Self {
+            buffer: HistoryBuffer::new(),
+            pending: 0,
+            steps: N,
+            consolidation: Consolidation::Last,
+            next: (),
+        }
+    }
+}
+
+impl<T, Next, const N: usize> Archive<T, Next, N>
+where
+    T: Sample,
+    Next: Cascade<T>,
+{
+    /// Creates an archive with a first tier of `N` samples, every `steps` of which are
+    /// consolidated into one sample of `next`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `steps` is 0 or greater than `N`.
+    pub fn with_next(steps: usize, consolidation: Consolidation, next: Next) -> Self {
+        assert!(steps > 0 && steps <= N, "steps out of range");
+
+        Self {
+            buffer: HistoryBuffer::new(),
+            pending: 0,
+            steps,
+            consolidation,
+            next,
+        }
+    }
+
+    /// Writes a sample to the first tier, cascading to the coarser tiers as needed
+    pub fn write(&mut self, value: T) {
+        self.buffer.write(value);
+
+        if Next::TIERS == 0 {
+            return;
+        }
+
+        self.pending += 1;
+        if self.pending == self.steps {
+            self.pending = 0;
+            let value = self.consolidation.apply(&self.buffer, self.steps);
+            self.next.write(value);
+        }
+    }
+
+    /// Returns the number of tiers
+    #[inline]
+    pub fn tier_count(&self) -> usize {
+        1 + Next::TIERS
+    }
+
+    /// Returns an iterator over the samples of the given tier, from the oldest to the most
+    /// recent, or `None` if there is no such tier
+    ///
+    /// Tier `0` is the finest one.
+    pub fn tier(&self, index: usize) -> Option<OldestOrdered<'_, T>> {
+        match index {
+            0 => Some(self.buffer.oldest_ordered()),
+            _ => self.next.tier(index - 1),
+        }
+    }
+
+    /// Returns an iterator over the tiers, from the finest to the coarsest
+    pub fn tiers(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = OldestOrdered<'_, T>> + ExactSizeIterator + '_ {
+        // NOTE(unwrap) every index below `tier_count` is a tier
+        (0..self.tier_count()).map(move |index| self.tier(index).unwrap())
+    }
+
+    /// Returns the first tier
+    #[inline]
+    pub fn history(&self) -> &HistoryBuffer<T, N> {
+        &self.buffer
+    }
+
+    /// Returns the coarser tiers
+    #[inline]
+    pub fn coarser(&self) -> &Next {
+        &self.next
+    }
+
+    /// Returns the number of samples in the first tier that will be consolidated into the next
+    /// one
+    #[inline]
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Returns the function that consolidates the samples of the first tier into the next one
+    #[inline]
+    pub fn consolidation(&self) -> Consolidation {
+        self.consolidation
+    }
+
+    /// Removes all the samples from all the tiers
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.pending = 0;
+        self.next.clear();
+    }
+
+    // checks the state restored by `Deserialize`
+    #[cfg(feature = "serde")]
+    pub(crate) fn from_parts(
+        buffer: HistoryBuffer<T, N>,
+        pending: usize,
+        steps: usize,
+        consolidation: Consolidation,
+        next: Next,
+    ) -> Result<Self, &'static str> {
+        if steps == 0 || steps > N {
+            return Err("steps out of range");
+        }
+        if pending >= steps {
+            return Err("more pending samples than steps");
+        }
+        // the samples to consolidate must all be in the buffer
+        if pending > buffer.len() {
+            return Err("more pending samples than samples");
+        }
+
+        Ok(Self {
+            buffer,
+            pending,
+            steps,
+            consolidation,
+            next,
+        })
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn pending(&self) -> usize {
+        self.pending
+    }
+}
+
+impl<T, Next, const N: usize> Cascade<T> for Archive<T, Next, N>
+where
+    T: Sample,
+    Next: Cascade<T>,
+{
+    const TIERS: usize = 1 + Next::TIERS;
+
+    fn write(&mut self, value: T) {
+        Archive::write(self, value)
+    }
+
+    fn tier(&self, index: usize) -> Option<OldestOrdered<'_, T>> {
+        Archive::tier(self, index)
+    }
+
+    fn clear(&mut self) {
+        Archive::clear(self)
+    }
+}
+
+impl<T, const N: usize> Default for Archive<T, (), N>
+where
+    T: Sample,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, Next, const N: usize> Extend<T> for Archive<T, Next, N>
+where
+    T: Sample,
+    Next: Cascade<T>,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for value in iter {
+            self.write(value);
+        }
+    }
+}
+
+impl<'a, T, Next, const N: usize> Extend<&'a T> for Archive<T, Next, N>
+where
+    T: 'a + Sample,
+    Next: Cascade<T>,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Archive, Consolidation};
+
+    #[test]
+    fn single_tier() {
+        let mut archive = Archive::<i32, (), 3>::new();
+        archive.extend(&[1, 2, 3, 4]);
+
+        assert_eq!(archive.tier_count(), 1);
+        assert!(archive.tier(0).unwrap().eq(&[2, 3, 4]));
+        assert!(archive.tier(1).is_none());
+    }
+
+    #[test]
+    fn consolidation() {
+        fn tier1(consolidation: Consolidation) -> std::vec::Vec<i32> {
+            let mut archive: Archive<i32, Archive<i32, (), 4>, 4> =
+                Archive::with_next(3, consolidation, Archive::new());
+            archive.extend(&[5, -1, 3, 2, 8, 4, 7]);
+            archive.tier(1).unwrap().copied().collect()
+        }
+
+        assert_eq!(tier1(Consolidation::Average), [2, 4]);
+        assert_eq!(tier1(Consolidation::Min), [-1, 2]);
+        assert_eq!(tier1(Consolidation::Max), [5, 8]);
+        assert_eq!(tier1(Consolidation::Last), [3, 4]);
+    }
+
+    #[test]
+    fn cascade() {
+        let mut archive: Archive<u8, Archive<u8, Archive<u8, (), 3>, 2>, 2> = Archive::with_next(
+            2,
+            Consolidation::Max,
+            Archive::with_next(2, Consolidation::Last, Archive::new()),
+        );
+        archive.extend(1..=12);
+
+        let tiers: std::vec::Vec<std::vec::Vec<u8>> = archive
+            .tiers()
+            .map(|tier| tier.copied().collect())
+            .collect();
+        assert_eq!(tiers, [&[11, 12][..], &[10, 12], &[4, 8, 12]]);
+        assert!(archive
+            .coarser()
+            .coarser()
+            .history()
+            .oldest_ordered()
+            .eq(&[4, 8, 12]));
+
+        archive.clear();
+        assert!(archive.tiers().all(|tier| tier.len() == 0));
+
+        // consolidation starts over after a clear
+        archive.extend(&[1, 2, 3, 4]);
+        assert!(archive.tier(2).unwrap().eq(&[4]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn steps_out_of_range() {
+        let _: Archive<u8, Archive<u8, (), 2>, 2> =
+            Archive::with_next(3, Consolidation::Last, Archive::new());
+    }
+}
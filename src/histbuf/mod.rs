@@ -0,0 +1,582 @@
+//! A "history buffer", a fixed length buffer that keeps the most recent values written to it,
+//! and the windowed statistics and multi-resolution archives built on top of it
+
+use core::fmt;
+use core::iter::FusedIterator;
+use core::mem::MaybeUninit;
+use core::ops::{Deref, Index};
+use core::ptr;
+use core::slice;
+
+pub use archive::{Archive, Consolidation};
+pub use order_stats::OrderStats;
+pub use rolling_stats::{RollingStats, Sample};
+
+mod archive;
+mod order_stats;
+mod rolling_stats;
+
+/// A "history buffer", similar to a write-only ring buffer of fixed length.
+///
+/// This buffer keeps a fixed number of elements.  On write, the oldest element
+/// is overwritten. Thus, the buffer is useful to keep a history of values with
+/// some desired depth, and for example calculate a rolling average.
+///
+/// # Examples
+/// ```
+/// use heapless::HistoryBuffer;
+///
+/// // Initialize a new buffer with 8 elements.
+/// let mut buf = HistoryBuffer::<_, 8>::new();
+///
+/// // Starts with no data
+/// assert_eq!(buf.recent(), None);
+///
+/// buf.write(3);
+/// buf.write(5);
+/// buf.extend(&[4, 4]);
+///
+/// // The most recent written element is a four.
+/// assert_eq!(buf.recent(), Some(&4));
+///
+/// // To access all elements in an unspecified order, use `as_slice()`.
+/// for el in buf.as_slice() { println!("{:?}", el); }
+///
+/// // To access them from the oldest to the most recent, use `oldest_ordered()`.
+/// assert!(buf.oldest_ordered().eq(&[3, 5, 4, 4]));
+///
+/// // Now we can prepare an average of all values, which comes out to 4.
+/// let avg = buf.as_slice().iter().sum::<usize>() / buf.len();
+/// assert_eq!(avg, 4);
+/// ```
+pub struct HistoryBuffer<T, const N: usize> {
+    data: [MaybeUninit<T>; N],
+    write_at: usize,
+    filled: bool,
+}
+
+impl<T, const N: usize> HistoryBuffer<T, N> {
+    const INIT: MaybeUninit<T> = MaybeUninit::uninit();
+
+    /// Constructs a new history buffer.
+    ///
+    /// The construction of a `HistoryBuffer` works in `const` contexts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// // Allocate a 16-element buffer on the stack
+    /// let x: HistoryBuffer<u8, 16> = HistoryBuffer::new();
+    /// assert_eq!(x.len(), 0);
+    /// ```
+    #[inline]
+    pub const fn new() -> Self {
+        // Const assert
+        crate::sealed::greater_than_0::<N>();
+
+        Self {
+            data: [Self::INIT; N],
+            write_at: 0,
+            filled: false,
+        }
+    }
+
+    /// Clears the buffer, replacing every element with the default value of
+    /// type `T`.
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl<T, const N: usize> HistoryBuffer<T, N>
+where
+    T: Copy + Clone,
+{
+    /// Constructs a new history buffer, where every element is the given value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// // Allocate a 16-element buffer on the stack
+    /// let mut x: HistoryBuffer<u8, 16> = HistoryBuffer::new_with(4);
+    /// // All elements are four
+    /// assert_eq!(x.as_slice(), [4; 16]);
+    /// ```
+    #[inline]
+    pub fn new_with(t: T) -> Self {
+        Self {
+            data: [MaybeUninit::new(t); N],
+            write_at: 0,
+            filled: true,
+        }
+    }
+
+    /// Clears the buffer, replacing every element with the given value.
+    pub fn clear_with(&mut self, t: T) {
+        *self = Self::new_with(t);
+    }
+}
+
+impl<T, const N: usize> HistoryBuffer<T, N> {
+    /// Returns the current fill level of the buffer.
+    #[inline]
+    pub fn len(&self) -> usize {
+        if self.filled {
+            N
+        } else {
+            self.write_at
+        }
+    }
+
+    /// Returns the capacity of the buffer, which is the length of the
+    /// underlying backing array.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Writes an element to the buffer, overwriting the oldest value.
+    ///
+    /// Returns the overwritten value, if the buffer was full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 2> = HistoryBuffer::new();
+    /// assert_eq!(x.write(1), None);
+    /// assert_eq!(x.write(2), None);
+    /// assert_eq!(x.write(3), Some(1));
+    /// ```
+    pub fn write(&mut self, t: T) -> Option<T> {
+        let evicted = if self.filled {
+            // Move the old value out before we overwrite it.
+            Some(unsafe { ptr::read(self.data[self.write_at].as_ptr()) })
+        } else {
+            None
+        };
+        self.data[self.write_at] = MaybeUninit::new(t);
+
+        self.write_at += 1;
+        if self.write_at == self.capacity() {
+            self.write_at = 0;
+            self.filled = true;
+        }
+
+        evicted
+    }
+
+    /// Clones and writes all elements in a slice to the buffer.
+    ///
+    /// If the slice is longer than the buffer, only the last `self.len()`
+    /// elements will actually be stored.
+    pub fn extend_from_slice(&mut self, other: &[T])
+    where
+        T: Clone,
+    {
+        for item in other {
+            self.write(item.clone());
+        }
+    }
+
+    /// Returns a reference to the most recently written value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 16> = HistoryBuffer::new();
+    /// x.write(4);
+    /// x.write(10);
+    /// assert_eq!(x.recent(), Some(&10));
+    /// ```
+    pub fn recent(&self) -> Option<&T> {
+        if self.write_at == 0 {
+            if self.filled {
+                Some(unsafe { &*self.data[self.capacity() - 1].as_ptr() })
+            } else {
+                None
+            }
+        } else {
+            Some(unsafe { &*self.data[self.write_at - 1].as_ptr() })
+        }
+    }
+
+    /// Returns the array slice backing the buffer, without keeping track
+    /// of the write position. Therefore, the element order is unspecified.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.data.as_ptr() as *const _, self.len()) }
+    }
+
+    /// Returns a pair of slices which contain, in order, the contents of the buffer, from the
+    /// oldest to the most recent value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut buffer: HistoryBuffer<u8, 6> = HistoryBuffer::new();
+    /// buffer.extend([0, 0, 0]);
+    /// buffer.extend([1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(buffer.as_slices(), (&[1, 2, 3][..], &[4, 5, 6][..]));
+    /// ```
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let buffer = self.as_slice();
+
+        if self.filled {
+            (&buffer[self.write_at..], &buffer[..self.write_at])
+        } else {
+            (buffer, &[])
+        }
+    }
+
+    /// Returns a reference to the oldest value in the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 3> = HistoryBuffer::new();
+    /// assert_eq!(x.oldest(), None);
+    ///
+    /// x.extend([1, 2, 3, 4]);
+    /// assert_eq!(x.oldest(), Some(&2));
+    /// ```
+    pub fn oldest(&self) -> Option<&T> {
+        let (front, back) = self.as_slices();
+        front.first().or_else(|| back.first())
+    }
+
+    /// Returns a reference to the value written `age` writes ago, where `get(0)` is the most
+    /// recent value, or `None` if the buffer doesn't hold that many values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 3> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3, 4]);
+    ///
+    /// assert_eq!(x.get(0), Some(&4));
+    /// assert_eq!(x.get(2), Some(&2));
+    /// assert_eq!(x.get(3), None);
+    /// ```
+    pub fn get(&self, age: usize) -> Option<&T> {
+        if age < self.len() {
+            let index = (self.write_at + N - 1 - age) % N;
+            Some(unsafe { &*self.data[index].as_ptr() })
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the values in the buffer, from the oldest to the most recent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut buffer: HistoryBuffer<u8, 6> = HistoryBuffer::new();
+    /// buffer.extend([0, 0, 0, 1, 2, 3, 4, 5, 6]);
+    /// let expected = [1, 2, 3, 4, 5, 6];
+    /// for (x, y) in buffer.oldest_ordered().zip(expected.iter()) {
+    ///     assert_eq!(x, y)
+    /// }
+    ///
+    /// // it can also be walked from the most recent value
+    /// assert_eq!(buffer.oldest_ordered().next_back(), Some(&6));
+    /// ```
+    pub fn oldest_ordered(&self) -> OldestOrdered<'_, T> {
+        let (front, back) = self.as_slices();
+        OldestOrdered {
+            front: front.iter(),
+            back: back.iter(),
+        }
+    }
+}
+
+impl<T, const N: usize> Extend<T> for HistoryBuffer<T, N> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for item in iter.into_iter() {
+            self.write(item);
+        }
+    }
+}
+
+impl<'a, T, const N: usize> Extend<&'a T> for HistoryBuffer<T, N>
+where
+    T: 'a + Clone,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend(iter.into_iter().cloned())
+    }
+}
+
+impl<T, const N: usize> Drop for HistoryBuffer<T, N> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                self.data.as_mut_ptr() as *mut T,
+                self.len(),
+            ))
+        }
+    }
+}
+
+impl<T, const N: usize> Index<usize> for HistoryBuffer<T, N> {
+    type Output = T;
+
+    /// Indexes the buffer by age, like [`get`](HistoryBuffer::get): `buffer[0]` is the most
+    /// recent value.
+    fn index(&self, age: usize) -> &T {
+        self.get(age).expect("index out of bounds")
+    }
+}
+
+impl<T, const N: usize> Deref for HistoryBuffer<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> AsRef<[T]> for HistoryBuffer<T, N> {
+    #[inline]
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for HistoryBuffer<T, N>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <[T] as fmt::Debug>::fmt(self, f)
+    }
+}
+
+/// An iterator over the values of a [`HistoryBuffer`], from the oldest to the most recent
+///
+/// This struct is created by [`HistoryBuffer::oldest_ordered`].
+pub struct OldestOrdered<'a, T> {
+    front: slice::Iter<'a, T>,
+    back: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Clone for OldestOrdered<'a, T> {
+    fn clone(&self) -> Self {
+        Self {
+            front: self.front.clone(),
+            back: self.back.clone(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for OldestOrdered<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.front.next().or_else(|| self.back.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for OldestOrdered<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.back.next_back().or_else(|| self.front.next_back())
+    }
+}
+
+impl<'a, T> ExactSizeIterator for OldestOrdered<'a, T> {
+    fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+}
+
+impl<'a, T> FusedIterator for OldestOrdered<'a, T> {}
+
+impl<'a, T> fmt::Debug for OldestOrdered<'a, T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::HistoryBuffer;
+
+    #[test]
+    fn new() {
+        let x: HistoryBuffer<u8, 4> = HistoryBuffer::new_with(1);
+        assert_eq!(x.len(), 4);
+        assert_eq!(x.as_slice(), [1; 4]);
+        assert_eq!(*x, [1; 4]);
+
+        let x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        assert_eq!(x.as_slice(), []);
+    }
+
+    #[test]
+    fn write() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        x.write(1);
+        x.write(4);
+        assert_eq!(x.as_slice(), [1, 4]);
+
+        x.write(5);
+        x.write(6);
+        x.write(10);
+        assert_eq!(x.as_slice(), [10, 4, 5, 6]);
+
+        x.extend([11, 12].iter());
+        assert_eq!(x.as_slice(), [10, 11, 12, 6]);
+    }
+
+    #[test]
+    fn clear() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new_with(1);
+        x.clear();
+        assert_eq!(x.as_slice(), []);
+
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        x.clear_with(1);
+        assert_eq!(x.as_slice(), [1; 4]);
+    }
+
+    #[test]
+    fn recent() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        assert_eq!(x.recent(), None);
+
+        x.write(1);
+        x.write(4);
+        assert_eq!(x.recent(), Some(&4));
+
+        x.write(5);
+        x.write(6);
+        x.write(10);
+        assert_eq!(x.recent(), Some(&10));
+    }
+
+    #[test]
+    fn as_slice() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+
+        assert_eq!(x.as_slice(), []);
+
+        x.extend([1, 2, 3, 4, 5].iter());
+
+        assert_eq!(x.as_slice(), [5, 2, 3, 4]);
+    }
+
+    #[test]
+    fn write_evicts() {
+        let mut x: HistoryBuffer<u8, 2> = HistoryBuffer::new();
+        assert_eq!(x.write(1), None);
+        assert_eq!(x.write(2), None);
+        assert_eq!(x.write(3), Some(1));
+        assert_eq!(x.write(4), Some(2));
+        assert_eq!(x.write(5), Some(3));
+    }
+
+    #[test]
+    fn write_drop() {
+        use core::cell::Cell;
+
+        struct Droppable<'a>(&'a Cell<usize>);
+        impl Drop for Droppable<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let dropped = Cell::new(0);
+        {
+            let mut x: HistoryBuffer<Droppable<'_>, 2> = HistoryBuffer::new();
+            x.write(Droppable(&dropped));
+            x.write(Droppable(&dropped));
+            let evicted = x.write(Droppable(&dropped));
+            assert_eq!(dropped.get(), 0);
+            drop(evicted);
+            assert_eq!(dropped.get(), 1);
+        }
+        assert_eq!(dropped.get(), 3);
+    }
+
+    #[test]
+    fn ordered() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        assert_eq!(x.as_slices(), (&[][..], &[][..]));
+        assert_eq!(x.oldest(), None);
+        assert_eq!(x.oldest_ordered().next(), None);
+
+        x.extend([1, 2, 3].iter());
+        assert_eq!(x.as_slices(), (&[1, 2, 3][..], &[][..]));
+        assert_eq!(x.oldest(), Some(&1));
+        assert!(x.oldest_ordered().eq(&[1, 2, 3]));
+
+        x.extend([4, 5, 6].iter());
+        assert_eq!(x.as_slices(), (&[3, 4][..], &[5, 6][..]));
+        assert_eq!(x.oldest(), Some(&3));
+        assert!(x.oldest_ordered().eq(&[3, 4, 5, 6]));
+        assert!(x.oldest_ordered().rev().eq(&[6, 5, 4, 3]));
+        assert_eq!(x.oldest_ordered().len(), 4);
+
+        // exactly full, write position wrapped to the start
+        x.extend([7, 8].iter());
+        assert_eq!(x.as_slices(), (&[5, 6, 7, 8][..], &[][..]));
+        assert!(x.oldest_ordered().eq(&[5, 6, 7, 8]));
+    }
+
+    #[test]
+    fn get() {
+        let mut x: HistoryBuffer<u8, 3> = HistoryBuffer::new();
+        assert_eq!(x.get(0), None);
+
+        x.write(1);
+        assert_eq!(x.get(0), Some(&1));
+        assert_eq!(x.get(1), None);
+
+        x.extend([2, 3, 4, 5].iter());
+        assert_eq!(x.get(0), Some(&5));
+        assert_eq!(x.get(1), Some(&4));
+        assert_eq!(x.get(2), Some(&3));
+        assert_eq!(x.get(3), None);
+
+        assert_eq!(x[0], 5);
+        assert_eq!(x[2], 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds() {
+        let mut x: HistoryBuffer<u8, 3> = HistoryBuffer::new();
+        x.write(1);
+        let _ = x[1];
+    }
+}
@@ -0,0 +1,262 @@
+use core::cmp::Ordering;
+
+use crate::{HistoryBuffer, Vec};
+
+/// A [`HistoryBuffer`] that keeps its window sorted, for median and percentile filters
+///
+/// Next to the buffer it keeps the positions of the samples sorted by value. Each write finds the
+/// place of the new sample with a binary search and shifts the positions after it, so it costs
+/// *O*(log N) comparisons plus an *O*(N) copy, and queries never have to sort the window.
+///
+/// The results are unspecified if the window contains values that can't be compared, like NaN.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::histbuf::OrderStats;
+///
+/// let mut window = OrderStats::<u16, 5>::new();
+///
+/// // a spike in the readings doesn't move the median
+/// window.extend([100, 101, 4095, 99, 100]);
+/// assert_eq!(window.median(), Some(&100));
+/// assert_eq!(window.percentile(100), Some(&4095));
+/// assert_eq!(window.rank(&101), 3);
+/// ```
+pub struct OrderStats<T, const N: usize> {
+    buffer: HistoryBuffer<T, N>,
+    // positions in `buffer.as_slice()`, sorted by the value they hold
+    sorted: Vec<usize, N>,
+}
+
+impl<T, const N: usize> OrderStats<T, N> {
+    /// Creates an empty window of `N` samples
+    pub const fn new() -> Self {
+        Self {
+            buffer: HistoryBuffer::new(),
+            sorted: Vec::new(),
+        }
+    }
+}
+
+impl<T, const N: usize> OrderStats<T, N>
+where
+    T: PartialOrd,
+{
+    /// Writes a sample, evicting the oldest one if the window is full
+    ///
+    /// Returns the evicted sample.
+    pub fn write(&mut self, value: T) -> Option<T> {
+        let slot = self.buffer.write_at;
+
+        if self.buffer.filled {
+            let values = self.buffer.as_slice();
+            let old = &values[slot];
+            // find the run of values equal to the one that is about to be evicted
+            let start = partition_point(&self.sorted, |&i| values[i] < *old);
+            let pos = match self.sorted[start..].iter().position(|&i| i == slot) {
+                Some(pos) => start + pos,
+                // only possible if the window holds incomparable values
+                None => self.sorted.iter().position(|&i| i == slot).unwrap(),
+            };
+            self.sorted.remove(pos);
+        }
+
+        let evicted = self.buffer.write(value);
+
+        let values = self.buffer.as_slice();
+        let new = &values[slot];
+        let pos = partition_point(&self.sorted, |&i| values[i] <= *new);
+        // NOTE(unwrap) `sorted` holds one position per sample and the one of the evicted sample,
+        // if any, has been removed
+        self.sorted.insert(pos, slot).unwrap();
+
+        evicted
+    }
+
+    /// Returns the number of samples in the window
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns `true` if no sample has been written yet
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.buffer.len() == 0
+    }
+
+    /// Returns the size of the window
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the sample with the given rank, where rank `0` is the smallest sample, or `None`
+    /// if `rank` is not smaller than the number of samples
+    pub fn nth_smallest(&self, rank: usize) -> Option<&T> {
+        let i = *self.sorted.get(rank)?;
+        Some(&self.buffer.as_slice()[i])
+    }
+
+    /// Returns the median of the window, or `None` if it's empty
+    ///
+    /// For an even number of samples this is the lower of the two middle samples.
+    pub fn median(&self) -> Option<&T> {
+        self.percentile(50)
+    }
+
+    /// Returns the `p`-th percentile of the window, using the nearest-rank method, or `None` if
+    /// it's empty
+    ///
+    /// That is the smallest sample that is greater than or equal to `p` percent of the samples:
+    /// `percentile(0)` is the minimum and `percentile(100)` the maximum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is greater than 100.
+    pub fn percentile(&self, p: u8) -> Option<&T> {
+        assert!(p <= 100, "percentile out of range");
+
+        // ceil(p / 100 * len) - 1, clamped to 0
+        let rank = (usize::from(p) * self.len()).saturating_sub(1) / 100;
+        self.nth_smallest(rank)
+    }
+
+    /// Returns the number of samples in the window that are smaller than `value`
+    pub fn rank(&self, value: &T) -> usize {
+        let values = self.buffer.as_slice();
+        partition_point(&self.sorted, |&i| values[i] < *value)
+    }
+
+    /// Returns an iterator over the samples in the window, from the smallest to the largest
+    pub fn iter_sorted(&self) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator + '_ {
+        let values = self.buffer.as_slice();
+        self.sorted.iter().map(move |&i| &values[i])
+    }
+
+    /// Returns the underlying buffer
+    #[inline]
+    pub fn history(&self) -> &HistoryBuffer<T, N> {
+        &self.buffer
+    }
+
+    /// Removes all the samples
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl<T, const N: usize> Default for OrderStats<T, N>
+where
+    T: PartialOrd,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Extend<T> for OrderStats<T, N>
+where
+    T: PartialOrd,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for value in iter {
+            self.write(value);
+        }
+    }
+}
+
+impl<'a, T, const N: usize> Extend<&'a T> for OrderStats<T, N>
+where
+    T: 'a + PartialOrd + Clone,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend(iter.into_iter().cloned())
+    }
+}
+
+// `<[T]>::partition_point`, which is only available since Rust 1.52
+fn partition_point<T>(slice: &[T], mut pred: impl FnMut(&T) -> bool) -> usize {
+    slice
+        .binary_search_by(|x| {
+            if pred(x) {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        })
+        .unwrap_or_else(|i| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OrderStats;
+
+    #[test]
+    fn empty() {
+        let window = OrderStats::<i32, 4>::new();
+        assert_eq!(window.median(), None);
+        assert_eq!(window.percentile(0), None);
+        assert_eq!(window.percentile(100), None);
+        assert_eq!(window.rank(&0), 0);
+        assert_eq!(window.iter_sorted().next(), None);
+    }
+
+    #[test]
+    fn sliding() {
+        let samples = [5, 3, 8, 1, 1, 9, 2, 7, 7, 4, 6, 0, 3, 3, 3, 10];
+        let mut window = OrderStats::<i32, 5>::new();
+
+        for (i, &x) in samples.iter().enumerate() {
+            window.write(x);
+
+            let mut sorted = std::vec::Vec::from(&samples[i.saturating_sub(4)..=i]);
+            sorted.sort_unstable();
+
+            assert!(window.iter_sorted().eq(sorted.iter()));
+            assert_eq!(window.median(), Some(&sorted[(sorted.len() - 1) / 2]));
+            assert_eq!(window.percentile(0), sorted.first());
+            assert_eq!(window.percentile(100), sorted.last());
+            assert_eq!(window.rank(&4), sorted.iter().filter(|&&v| v < 4).count());
+        }
+    }
+
+    #[test]
+    fn percentile() {
+        let mut window = OrderStats::<u8, 10>::new();
+        window.extend((1..=10).rev());
+
+        assert_eq!(window.percentile(0), Some(&1));
+        assert_eq!(window.percentile(10), Some(&1));
+        assert_eq!(window.percentile(11), Some(&2));
+        assert_eq!(window.percentile(50), Some(&5));
+        assert_eq!(window.percentile(90), Some(&9));
+        assert_eq!(window.percentile(91), Some(&10));
+        assert_eq!(window.percentile(100), Some(&10));
+    }
+
+    #[test]
+    #[should_panic]
+    fn percentile_out_of_range() {
+        let window = OrderStats::<u8, 10>::new();
+        window.percentile(101);
+    }
+
+    #[test]
+    fn floats() {
+        let mut window = OrderStats::<f32, 3>::new();
+        window.extend(&[0.5, -1.0, 2.5, 0.25]);
+
+        assert_eq!(window.median(), Some(&0.25));
+        assert_eq!(window.rank(&0.3), 2);
+        assert_eq!(window.nth_smallest(0), Some(&-1.0));
+        assert_eq!(window.nth_smallest(3), None);
+    }
+}
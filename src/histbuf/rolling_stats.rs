@@ -0,0 +1,402 @@
+use core::ops::{Add, Sub};
+
+use crate::{Deque, HistoryBuffer};
+
+/// A numeric type that [`RollingStats`] can keep statistics of
+///
+/// Implemented for all the primitive integer and floating point types.
+pub trait Sample: Copy + PartialOrd {
+    /// The type used to accumulate the running sum
+    ///
+    /// For integers this is a wider integer type, so the sum of a full window never overflows.
+    type Sum: Copy + Default + Add<Output = Self::Sum> + Sub<Output = Self::Sum>;
+
+    /// Converts the sample into the accumulator type
+    fn to_sum(self) -> Self::Sum;
+
+    /// Converts the sample to `f64`, possibly losing precision
+    fn to_f64(self) -> f64;
+
+    /// Converts an accumulated sum to `f64`, possibly losing precision
+    fn sum_to_f64(sum: Self::Sum) -> f64;
+
+    /// Divides the sum of `count` samples by `count`, rounding toward zero for integers
+    fn mean(sum: Self::Sum, count: usize) -> Self;
+}
+
+macro_rules! impl_sample {
+    ($($t:ty => $sum:ty,)*) => {
+        $(
+            impl Sample for $t {
+                type Sum = $sum;
+
+                #[inline]
+                fn to_sum(self) -> $sum {
+                    self as $sum
+                }
+
+                #[inline]
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                #[inline]
+                fn sum_to_f64(sum: $sum) -> f64 {
+                    sum as f64
+                }
+
+                #[inline]
+                fn mean(sum: $sum, count: usize) -> $t {
+                    (sum / count as $sum) as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_sample!(
+    i8 => i64,
+    i16 => i64,
+    i32 => i64,
+    i64 => i128,
+    isize => i128,
+    u8 => u64,
+    u16 => u64,
+    u32 => u64,
+    u64 => u128,
+    usize => u128,
+    f32 => f64,
+    f64 => f64,
+);
+
+/// A [`HistoryBuffer`] that keeps statistics of the values in its window up to date as they are
+/// written
+///
+/// Every operation is *O*(1) amortized: the sum, mean and variance are updated incrementally from
+/// the written and the evicted value, and the minimum and maximum are tracked with monotonic
+/// deques. The sum and variance are recomputed from scratch once every `N` writes so that
+/// floating point rounding errors don't build up.
+///
+/// The result of `min` and `max` is unspecified if the window contains NaN values.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::histbuf::RollingStats;
+///
+/// let mut stats = RollingStats::<i32, 4>::new();
+/// stats.extend([3, 1, 4, 1, 5]);
+///
+/// // the window holds the last 4 samples: 1, 4, 1, 5
+/// assert_eq!(stats.sum(), 11);
+/// assert_eq!(stats.mean(), Some(2.75));
+/// assert_eq!(stats.min(), Some(1));
+/// assert_eq!(stats.max(), Some(5));
+/// ```
+pub struct RollingStats<T, const N: usize>
+where
+    T: Sample,
+{
+    buffer: HistoryBuffer<T, N>,
+    sum: T::Sum,
+    // sum of the squared differences from the mean
+    m2: f64,
+    // sequence number of the next write; wraps around
+    seq: usize,
+    // (sequence number, value) pairs with increasing values
+    min: Deque<(usize, T), N>,
+    // (sequence number, value) pairs with decreasing values
+    max: Deque<(usize, T), N>,
+}
+
+impl<T, const N: usize> RollingStats<T, N>
+where
+    T: Sample,
+{
+    /// Creates an empty window of `N` samples
+    pub fn new() -> Self {
+        Self {
+            buffer: HistoryBuffer::new(),
+            sum: T::Sum::default(),
+            m2: 0.,
+            seq: 0,
+            min: Deque::new(),
+            max: Deque::new(),
+        }
+    }
+
+    /// Writes a sample, evicting the oldest one if the window is full
+    ///
+    /// Returns the evicted sample.
+    pub fn write(&mut self, value: T) -> Option<T> {
+        let old_len = self.buffer.len();
+        let old_mean = self.mean_f64();
+
+        let evicted = self.buffer.write(value);
+        self.sum = self.sum + value.to_sum();
+        if let Some(evicted) = evicted {
+            self.sum = self.sum - evicted.to_sum();
+        }
+        let new_mean = self.mean_f64();
+
+        let x = value.to_f64();
+        match evicted {
+            // the number of samples doesn't change
+            Some(evicted) => {
+                let y = evicted.to_f64();
+                self.m2 += (x - y) * (x - new_mean + y - old_mean);
+            }
+            None if old_len == 0 => self.m2 = 0.,
+            None => self.m2 += (x - old_mean) * (x - new_mean),
+        }
+
+        let seq = self.seq;
+        self.seq = self.seq.wrapping_add(1);
+
+        if evicted.is_some() {
+            let evicted_seq = seq.wrapping_sub(N);
+            if self.min.front().map(|&(s, _)| s) == Some(evicted_seq) {
+                self.min.pop_front();
+            }
+            if self.max.front().map(|&(s, _)| s) == Some(evicted_seq) {
+                self.max.pop_front();
+            }
+        }
+
+        while matches!(self.min.back(), Some(&(_, v)) if v >= value) {
+            self.min.pop_back();
+        }
+        while matches!(self.max.back(), Some(&(_, v)) if v <= value) {
+            self.max.pop_back();
+        }
+        // NOTE(unsafe) each deque holds at most one entry per sample in the window, and we just
+        // evicted one if the window was full
+        unsafe {
+            self.min.push_back_unchecked((seq, value));
+            self.max.push_back_unchecked((seq, value));
+        }
+
+        // once per pass over the buffer
+        if self.buffer.write_at == 0 {
+            self.resync();
+        }
+
+        evicted
+    }
+
+    /// Returns the number of samples in the window
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns `true` if no sample has been written yet
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.buffer.len() == 0
+    }
+
+    /// Returns the size of the window
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the sum of the samples in the window
+    #[inline]
+    pub fn sum(&self) -> T::Sum {
+        self.sum
+    }
+
+    /// Returns the mean of the samples in the window, or `None` if it's empty
+    pub fn mean(&self) -> Option<f64> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.mean_f64())
+        }
+    }
+
+    /// Returns the population variance of the samples in the window, or `None` if it's empty
+    pub fn variance(&self) -> Option<f64> {
+        if self.is_empty() {
+            None
+        } else {
+            // rounding errors can push a zero variance slightly below zero
+            Some(if self.m2 > 0. {
+                self.m2 / self.len() as f64
+            } else {
+                0.
+            })
+        }
+    }
+
+    /// Returns the smallest sample in the window, or `None` if it's empty
+    #[inline]
+    pub fn min(&self) -> Option<T> {
+        self.min.front().map(|&(_, v)| v)
+    }
+
+    /// Returns the largest sample in the window, or `None` if it's empty
+    #[inline]
+    pub fn max(&self) -> Option<T> {
+        self.max.front().map(|&(_, v)| v)
+    }
+
+    /// Returns the underlying buffer
+    #[inline]
+    pub fn history(&self) -> &HistoryBuffer<T, N> {
+        &self.buffer
+    }
+
+    /// Removes all the samples
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    fn mean_f64(&self) -> f64 {
+        match self.buffer.len() {
+            0 => 0.,
+            len => T::sum_to_f64(self.sum) / len as f64,
+        }
+    }
+
+    // recomputes the sum and the variance from the samples in the window
+    fn resync(&mut self) {
+        let mut sum = T::Sum::default();
+        for &v in self.buffer.as_slice() {
+            sum = sum + v.to_sum();
+        }
+        self.sum = sum;
+
+        let mean = self.mean_f64();
+        self.m2 = self
+            .buffer
+            .as_slice()
+            .iter()
+            .map(|v| {
+                let d = v.to_f64() - mean;
+                d * d
+            })
+            .sum();
+    }
+}
+
+impl<T, const N: usize> Default for RollingStats<T, N>
+where
+    T: Sample,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Extend<T> for RollingStats<T, N>
+where
+    T: Sample,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for value in iter {
+            self.write(value);
+        }
+    }
+}
+
+impl<'a, T, const N: usize> Extend<&'a T> for RollingStats<T, N>
+where
+    T: 'a + Sample,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RollingStats;
+
+    fn brute_force(samples: &[f64]) -> (f64, f64) {
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        let variance =
+            samples.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / samples.len() as f64;
+        (mean, variance)
+    }
+
+    #[test]
+    fn empty() {
+        let stats = RollingStats::<u8, 4>::new();
+        assert!(stats.is_empty());
+        assert_eq!(stats.sum(), 0);
+        assert_eq!(stats.mean(), None);
+        assert_eq!(stats.variance(), None);
+        assert_eq!(stats.min(), None);
+        assert_eq!(stats.max(), None);
+    }
+
+    #[test]
+    fn integers() {
+        let mut stats = RollingStats::<u8, 3>::new();
+        assert_eq!(stats.write(255), None);
+        assert_eq!(stats.write(255), None);
+        assert_eq!(stats.write(255), None);
+        // no overflow in the sum
+        assert_eq!(stats.sum(), 765);
+        assert_eq!(stats.variance(), Some(0.));
+
+        assert_eq!(stats.write(0), Some(255));
+        assert_eq!(stats.sum(), 510);
+        assert_eq!(stats.mean(), Some(170.));
+        assert_eq!(stats.min(), Some(0));
+        assert_eq!(stats.max(), Some(255));
+    }
+
+    #[test]
+    fn min_max() {
+        let samples = [5, 3, 8, 1, 1, 9, 2, 7, 7, 4, 6, 0, 3];
+        let mut stats = RollingStats::<i32, 4>::new();
+
+        for (i, &x) in samples.iter().enumerate() {
+            stats.write(x);
+
+            let window = &samples[i.saturating_sub(3)..=i];
+            assert_eq!(stats.min(), window.iter().copied().min());
+            assert_eq!(stats.max(), window.iter().copied().max());
+        }
+    }
+
+    #[test]
+    fn mean_variance() {
+        let samples = [
+            1.5f32, -2.25, 3.0, 100.0, 0.125, -7.5, 42.0, 3.0, 3.0, -1.0, 0.5, 12.0,
+        ];
+        let mut stats = RollingStats::<f32, 5>::new();
+
+        for (i, &x) in samples.iter().enumerate() {
+            stats.write(x);
+
+            let window: std::vec::Vec<f64> = samples[i.saturating_sub(4)..=i]
+                .iter()
+                .map(|&x| x as f64)
+                .collect();
+            let (mean, variance) = brute_force(&window);
+            assert!((stats.mean().unwrap() - mean).abs() < 1e-9);
+            assert!((stats.variance().unwrap() - variance).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn clear() {
+        let mut stats = RollingStats::<i16, 2>::new();
+        stats.extend(&[1, 2, 3]);
+        stats.clear();
+        assert!(stats.is_empty());
+        assert_eq!(stats.sum(), 0);
+        assert_eq!(stats.max(), None);
+    }
+}
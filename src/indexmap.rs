@@ -1,8 +1,10 @@
-use core::{borrow::Borrow, fmt, iter::FromIterator, mem, num::NonZeroU32, ops, slice};
+use core::{
+    borrow::Borrow, cmp::Ordering, fmt, iter::FromIterator, mem, num::NonZeroU32, ops, slice,
+};
 
-use hash32::{BuildHasher, BuildHasherDefault, FnvHasher, Hash, Hasher};
+use hash32::{BuildHasherDefault, FnvHasher};
 
-use crate::Vec;
+use crate::{sealed::indexmap::KeyHasher, vec, CapacityError, TryFromIterator, Vec};
 
 /// A [`heapless::IndexMap`](./struct.IndexMap.html) using the default FNV hasher
 ///
@@ -47,6 +49,49 @@ use crate::Vec;
 /// ```
 pub type FnvIndexMap<K, V, const N: usize> = IndexMap<K, V, BuildHasherDefault<FnvHasher>, N>;
 
+/// Hashes the keys of an [`IndexMap`](./struct.IndexMap.html) or
+/// [`IndexSet`](./struct.IndexSet.html) with the `core::hash::BuildHasher` `S`
+///
+/// By default the keys of an `IndexMap` implement `hash32::Hash` and its hasher is a
+/// `hash32::BuildHasher`, like the FNV hasher of [`FnvIndexMap`](./type.FnvIndexMap.html). With a
+/// `CoreBuildHasher` as its hasher the keys only need to implement `core::hash::Hash`, so
+/// `#[derive(Hash)]` types and the key types of other crates work as is. The 64-bit hashes of `S`
+/// are folded into the 16 bits that the map uses.
+///
+/// # Examples
+///
+/// ```
+/// use core::hash::BuildHasherDefault;
+/// use std::collections::hash_map::DefaultHasher;
+///
+/// use heapless::{CoreBuildHasher, IndexMap};
+///
+/// type Hasher = CoreBuildHasher<BuildHasherDefault<DefaultHasher>>;
+///
+/// #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+/// enum Sensor {
+///     Temperature(u8),
+///     Pressure,
+/// }
+///
+/// let mut readings = IndexMap::<Sensor, i32, Hasher, 8>::default();
+/// readings.insert(Sensor::Temperature(0), 21).unwrap();
+/// readings.insert(Sensor::Pressure, 1013).unwrap();
+/// assert_eq!(readings[&Sensor::Temperature(0)], 21);
+/// assert_eq!(readings.get(&Sensor::Temperature(1)), None);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CoreBuildHasher<S> {
+    pub(crate) build_hasher: S,
+}
+
+impl<S> CoreBuildHasher<S> {
+    /// Wraps the `core::hash::BuildHasher` `build_hasher`
+    pub const fn new(build_hasher: S) -> Self {
+        Self { build_hasher }
+    }
+}
+
 #[derive(Clone, Copy, Eq, PartialEq)]
 struct HashValue(u16);
 
@@ -135,7 +180,7 @@ impl<K, V, const N: usize> CoreMap<K, V, N> {
 
 impl<K, V, const N: usize> CoreMap<K, V, N>
 where
-    K: Eq + Hash,
+    K: Eq,
 {
     fn capacity() -> usize {
         N
@@ -298,11 +343,407 @@ where
             last_probe = probe;
         });
     }
+
+    // returns the probe (indices) that points to the entry at position `index`
+    fn probe_of(&self, index: usize) -> usize {
+        let mut probe = self.entries[index].hash.desired_pos(Self::mask());
+
+        probe_loop!(probe < self.indices.len(), {
+            if let Some(pos) = self.indices[probe] {
+                if pos.index() == index {
+                    return probe;
+                }
+            }
+        });
+    }
+
+    fn swap_remove_index(&mut self, index: usize) -> Option<(K, V)> {
+        if index < self.entries.len() {
+            let probe = self.probe_of(index);
+            Some(self.remove_found(probe, index))
+        } else {
+            None
+        }
+    }
+
+    fn swap_indices(&mut self, a: usize, b: usize) {
+        let probe_a = self.probe_of(a);
+        let probe_b = self.probe_of(b);
+
+        self.entries.swap(a, b);
+        self.indices[probe_a] = Some(Pos::new(b, self.entries[b].hash));
+        self.indices[probe_b] = Some(Pos::new(a, self.entries[a].hash));
+    }
+
+    fn shift_remove_found(&mut self, probe: usize, found: usize) -> (K, V) {
+        self.indices[probe] = None;
+        let entry = self.entries.remove(found);
+
+        // the entries after `found` moved one position down
+        for pos in self.indices.iter_mut().flatten() {
+            if pos.index() > found {
+                *pos = Pos::new(pos.index() - 1, pos.hash());
+            }
+        }
+
+        self.backward_shift_after_removal(probe);
+
+        (entry.key, entry.value)
+    }
+
+    // rebuilds `indices` from scratch for the first `len` entries
+    fn rebuild_indices(&mut self, len: usize) {
+        for pos in self.indices.iter_mut() {
+            *pos = None;
+        }
+
+        for index in 0..len {
+            let hash = self.entries[index].hash;
+            let mut probe = hash.desired_pos(Self::mask());
+            let mut dist = 0;
+
+            probe_loop!(probe < self.indices.len(), {
+                if let Some(pos) = self.indices[probe] {
+                    if pos.hash().probe_distance(Self::mask(), probe) < dist {
+                        // robin hood: steal the spot and shift the rest forward
+                        self.insert_phase_2(probe, Pos::new(index, hash));
+                        break;
+                    }
+                } else {
+                    self.indices[probe] = Some(Pos::new(index, hash));
+                    break;
+                }
+                dist += 1;
+            });
+        }
+    }
+
+    fn reverse(&mut self) {
+        self.entries.reverse();
+
+        let last = self.entries.len().wrapping_sub(1);
+        for pos in self.indices.iter_mut().flatten() {
+            *pos = Pos::new(last - pos.index(), pos.hash());
+        }
+    }
+
+    fn retain<F>(&mut self, keep: F)
+    where
+        F: FnMut(&mut Bucket<K, V>) -> bool,
+    {
+        let guard = RebuildOnDrop {
+            len: self.entries.len(),
+            core: self,
+        };
+        guard.core.entries.retain_mut(keep);
+    }
+
+    fn drain(&mut self, range: ops::Range<usize>) -> vec::Drain<'_, Bucket<K, V>> {
+        let len = self.entries.len();
+        let kept = len - range.len();
+
+        // move the drained entries to the end, so that `indices` never points to an entry past the
+        // end of `entries`, even if the `Drain` is leaked
+        self.entries[range.start..].rotate_left(range.len());
+        self.rebuild_indices(kept);
+        self.entries.drain(kept..)
+    }
+
+    fn move_index(&mut self, from: usize, to: usize) {
+        assert!(from < self.entries.len() && to < self.entries.len());
+
+        // shift the entries in between by one position, towards `from`
+        if from < to {
+            for i in from..to {
+                self.swap_indices(i, i + 1);
+            }
+        } else {
+            for i in (to..from).rev() {
+                self.swap_indices(i, i + 1);
+            }
+        }
+    }
+}
+
+// rebuilds `indices` on drop if entries were removed, so that they stay consistent with `entries`
+// even if a closure panics
+struct RebuildOnDrop<'a, K, V, const N: usize>
+where
+    K: Eq,
+{
+    core: &'a mut CoreMap<K, V, N>,
+    len: usize,
+}
+
+impl<K, V, const N: usize> Drop for RebuildOnDrop<'_, K, V, N>
+where
+    K: Eq,
+{
+    fn drop(&mut self) {
+        let len = self.core.entries.len();
+        if len != self.len {
+            self.core.rebuild_indices(len);
+        }
+    }
+}
+
+/// A view into an entry in the map
+pub enum Entry<'a, K, V, const N: usize> {
+    /// The entry corresponding to the key `K` exists in the map
+    Occupied(OccupiedEntry<'a, K, V, N>),
+    /// The entry corresponding to the key `K` does not exist in the map
+    Vacant(VacantEntry<'a, K, V, N>),
+}
+
+impl<'a, K, V, const N: usize> Entry<'a, K, V, N>
+where
+    K: Eq,
+{
+    /// Returns a reference to this entry's key
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map = FnvIndexMap::<&str, u32, 8>::new();
+    /// assert_eq!(map.entry("foobar").key(), &"foobar");
+    /// ```
+    pub fn key(&self) -> &K {
+        match *self {
+            Entry::Occupied(ref entry) => entry.key(),
+            Entry::Vacant(ref entry) => entry.key(),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the default if empty, and returns a mutable
+    /// reference to the value in the entry
+    ///
+    /// Returns back `default` if the entry is vacant and the map is full.
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map = FnvIndexMap::<&str, u32, 8>::new();
+    /// let result = map.entry("foo").or_insert(3);
+    /// assert_eq!(result, Ok(&mut 3));
+    /// *map.entry("foo").or_insert(5).unwrap() *= 2;
+    /// assert_eq!(map["foo"], 6);
+    /// ```
+    pub fn or_insert(self, default: V) -> Result<&'a mut V, V> {
+        match self {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default function if empty,
+    /// and returns a mutable reference to the value in the entry
+    ///
+    /// `default` is only called if the entry is vacant. Returns back the value it produced if the
+    /// map is full.
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map = FnvIndexMap::<&str, u32, 8>::new();
+    /// let value = 42;
+    /// assert_eq!(map.entry("foo").or_insert_with(|| value), Ok(&mut 42));
+    /// assert_eq!(map.entry("foo").or_insert_with(|| unreachable!()), Ok(&mut 42));
+    /// ```
+    pub fn or_insert_with<F>(self, default: F) -> Result<&'a mut V, V>
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting, if empty, the result of the default function
+    /// which is given a reference to the key, and returns a mutable reference to the value in the
+    /// entry
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map = FnvIndexMap::<&str, usize, 8>::new();
+    /// assert_eq!(map.entry("foobar").or_insert_with_key(|key| key.len()), Ok(&mut 6));
+    /// ```
+    pub fn or_insert_with_key<F>(self, default: F) -> Result<&'a mut V, V>
+    where
+        F: FnOnce(&K) -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => {
+                let value = default(entry.key());
+                entry.insert(value)
+            }
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting `V::default()` if empty, and returns a mutable
+    /// reference to the value in the entry
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map = FnvIndexMap::<&str, Option<u32>, 8>::new();
+    /// assert_eq!(map.entry("foo").or_default(), Ok(&mut None));
+    /// ```
+    pub fn or_default(self) -> Result<&'a mut V, V>
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any potential inserts into the
+    /// map
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map = FnvIndexMap::<&str, u32, 8>::new();
+    ///
+    /// map.entry("foo").and_modify(|e| *e += 1).or_insert(42).unwrap();
+    /// assert_eq!(map["foo"], 42);
+    ///
+    /// map.entry("foo").and_modify(|e| *e += 1).or_insert(42).unwrap();
+    /// assert_eq!(map["foo"], 43);
+    /// ```
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        match self {
+            Entry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                Entry::Occupied(entry)
+            }
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+}
+
+/// An occupied entry which can be manipulated
+pub struct OccupiedEntry<'a, K, V, const N: usize> {
+    key: K,
+    probe: usize,
+    pos: usize,
+    core: &'a mut CoreMap<K, V, N>,
+}
+
+impl<'a, K, V, const N: usize> OccupiedEntry<'a, K, V, N>
+where
+    K: Eq,
+{
+    /// Gets a reference to the key that this entity corresponds to
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Returns the index of the key-value pair in the map
+    pub fn index(&self) -> usize {
+        self.pos
+    }
+
+    /// Removes this entry from the map and yields its corresponding key and value
+    pub fn remove_entry(self) -> (K, V) {
+        self.core.remove_found(self.probe, self.pos)
+    }
+
+    /// Gets a reference to the value associated with this entry
+    pub fn get(&self) -> &V {
+        // SAFETY: Already checked existence at instantiation and the only mutable reference
+        // to the map is internally held.
+        unsafe { &self.core.entries.get_unchecked(self.pos).value }
+    }
+
+    /// Gets a mutable reference to the value associated with this entry
+    pub fn get_mut(&mut self) -> &mut V {
+        // SAFETY: Already checked existence at instantiation and the only mutable reference
+        // to the map is internally held.
+        unsafe { &mut self.core.entries.get_unchecked_mut(self.pos).value }
+    }
+
+    /// Consumes this entry and yields a reference to the underlying value
+    pub fn into_mut(self) -> &'a mut V {
+        // SAFETY: Already checked existence at instantiation and the only mutable reference
+        // to the map is internally held.
+        unsafe { &mut self.core.entries.get_unchecked_mut(self.pos).value }
+    }
+
+    /// Overwrites the underlying map's value with this entry's value, returning the old value
+    pub fn insert(self, value: V) -> V {
+        // SAFETY: Already checked existence at instantiation and the only mutable reference
+        // to the map is internally held.
+        unsafe {
+            mem::replace(
+                &mut self.core.entries.get_unchecked_mut(self.pos).value,
+                value,
+            )
+        }
+    }
+
+    /// Removes this entry from the map and yields its value
+    ///
+    /// Like [`IndexMap::swap_remove`](struct.IndexMap.html#method.swap_remove), this perturbs the
+    /// position of what used to be the last element of the map.
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+}
+
+/// A view into an empty slot in the underlying map
+pub struct VacantEntry<'a, K, V, const N: usize> {
+    key: K,
+    hash_val: HashValue,
+    core: &'a mut CoreMap<K, V, N>,
+}
+
+impl<'a, K, V, const N: usize> VacantEntry<'a, K, V, N>
+where
+    K: Eq,
+{
+    /// Get the key associated with this entry
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Consumes this entry to yield to key associated with it
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Inserts this entry into to underlying map, yields a mutable reference to the inserted value
+    ///
+    /// If the map is at capacity the value is returned instead.
+    pub fn insert(self, value: V) -> Result<&'a mut V, V> {
+        if self.core.entries.is_full() {
+            Err(value)
+        } else {
+            let index = self.core.entries.len();
+            match self.core.insert_phase_1(self.hash_val, self.key, value) {
+                Inserted::Swapped { .. } => {
+                    // NOTE the key was not found when this entry was created and the map is
+                    // mutably borrowed by this entry
+                    unreachable!()
+                }
+                Inserted::Done => {}
+                Inserted::RobinHood { probe, old_pos } => {
+                    self.core.insert_phase_2(probe, old_pos);
+                }
+            }
+            // SAFETY: the bucket was just pushed at position `index`
+            Ok(unsafe { &mut self.core.entries.get_unchecked_mut(index).value })
+        }
+    }
 }
 
 impl<K, V, const N: usize> Clone for CoreMap<K, V, N>
 where
-    K: Eq + Hash + Clone,
+    K: Eq + Clone,
     V: Clone,
 {
     fn clone(&self) -> Self {
@@ -317,7 +758,8 @@ where
 ///
 /// Note that you cannot use `IndexMap` directly, since it is generic around the hashing algorithm
 /// in use. Pick a concrete instantiation like [`FnvIndexMap`](./type.FnvIndexMap.html) instead
-/// or create your own.
+/// or create your own. Keys that implement `core::hash::Hash` rather than `hash32::Hash` need a
+/// [`CoreBuildHasher`](./struct.CoreBuildHasher.html).
 ///
 /// Note that the capacity of the `IndexMap` must be a power of 2.
 ///
@@ -375,10 +817,23 @@ impl<K, V, S, const N: usize> IndexMap<K, V, BuildHasherDefault<S>, N> {
     }
 }
 
+impl<K, V, S, const N: usize> IndexMap<K, V, S, N> {
+    /// Creates an empty `IndexMap` that hashes its keys with `build_hasher`.
+    pub const fn with_hasher(build_hasher: S) -> Self {
+        // Const assert N is a power of two
+        crate::sealed::power_of_two::<N>();
+
+        IndexMap {
+            build_hasher,
+            core: CoreMap::new(),
+        }
+    }
+}
+
 impl<K, V, S, const N: usize> IndexMap<K, V, S, N>
 where
-    K: Eq + Hash,
-    S: BuildHasher,
+    K: Eq,
+    S: KeyHasher<K>,
 {
     /* Public API */
     /// Returns the number of elements the map can hold
@@ -488,8 +943,43 @@ where
         }
     }
 
-    // TODO
-    // pub fn entry(&mut self, key: K) -> Entry<K, V> { .. }
+    /// Returns an entry for the corresponding key
+    ///
+    /// The key is hashed and looked up only once; the returned entry can then be used to read,
+    /// update, insert or remove the key-value pair without further lookups.
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    /// use heapless::Entry;
+    ///
+    /// let mut map = FnvIndexMap::<_, _, 16>::new();
+    /// if let Entry::Vacant(v) = map.entry("a") {
+    ///     v.insert(1).unwrap();
+    /// }
+    /// if let Entry::Occupied(mut o) = map.entry("a") {
+    ///     println!("found {}", *o.get()); // Prints 1
+    ///     o.insert(2);
+    /// }
+    /// // Prints 2
+    /// println!("val: {}", *map.get("a").unwrap());
+    /// ```
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, N> {
+        let hash_val = hash_with(&key, &self.build_hasher);
+        if let Some((probe, pos)) = self.core.find(hash_val, &key) {
+            Entry::Occupied(OccupiedEntry {
+                key,
+                probe,
+                pos,
+                core: &mut self.core,
+            })
+        } else {
+            Entry::Vacant(VacantEntry {
+                key,
+                hash_val,
+                core: &mut self.core,
+            })
+        }
+    }
 
     /// Return the number of key-value pairs in the map.
     ///
@@ -560,7 +1050,8 @@ where
     pub fn get<Q>(&self, key: &Q) -> Option<&V>
     where
         K: Borrow<Q>,
-        Q: ?Sized + Hash + Eq,
+        Q: ?Sized + Eq,
+        S: KeyHasher<Q>,
     {
         self.find(key)
             .map(|(_, found)| unsafe { &self.core.entries.get_unchecked(found).value })
@@ -586,7 +1077,8 @@ where
     pub fn contains_key<Q>(&self, key: &Q) -> bool
     where
         K: Borrow<Q>,
-        Q: ?Sized + Eq + Hash,
+        Q: ?Sized + Eq,
+        S: KeyHasher<Q>,
     {
         self.find(key).is_some()
     }
@@ -613,7 +1105,8 @@ where
     pub fn get_mut<'v, Q>(&'v mut self, key: &Q) -> Option<&'v mut V>
     where
         K: Borrow<Q>,
-        Q: ?Sized + Hash + Eq,
+        Q: ?Sized + Eq,
+        S: KeyHasher<Q>,
     {
         if let Some((_, found)) = self.find(key) {
             Some(unsafe { &mut self.core.entries.get_unchecked_mut(found).value })
@@ -681,7 +1174,8 @@ where
     pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
     where
         K: Borrow<Q>,
-        Q: ?Sized + Hash + Eq,
+        Q: ?Sized + Eq,
+        S: KeyHasher<Q>,
     {
         self.swap_remove(key)
     }
@@ -697,39 +1191,430 @@ where
     pub fn swap_remove<Q>(&mut self, key: &Q) -> Option<V>
     where
         K: Borrow<Q>,
-        Q: ?Sized + Hash + Eq,
+        Q: ?Sized + Eq,
+        S: KeyHasher<Q>,
     {
         self.find(key)
             .map(|(probe, found)| self.core.remove_found(probe, found).1)
     }
 
-    /* Private API */
-    /// Return probe (indices) and position (entries)
-    fn find<Q>(&self, key: &Q) -> Option<(usize, usize)>
-    where
-        K: Borrow<Q>,
-        Q: ?Sized + Hash + Eq,
-    {
-        if self.len() == 0 {
-            return None;
-        }
-        let h = hash_with(key, &self.build_hasher);
-        self.core.find(h, key)
-    }
-
-    fn insert_phase_1(&mut self, key: K, value: V) -> Inserted<V> {
-        let hash = hash_with(&key, &self.build_hasher);
-        self.core.insert_phase_1(hash, key, value)
+    /// Returns the key-value pair at position `index`, or `None` if `index` is out of bounds.
+    ///
+    /// Computes in **O(1)** time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map = FnvIndexMap::<_, _, 8>::new();
+    /// map.insert("a", 1).unwrap();
+    /// map.insert("b", 2).unwrap();
+    /// assert_eq!(map.get_index(1), Some((&"b", &2)));
+    /// assert_eq!(map.get_index(2), None);
+    /// ```
+    pub fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+        self.core
+            .entries
+            .get(index)
+            .map(|bucket| (&bucket.key, &bucket.value))
     }
-}
-
-impl<'a, K, Q, V, S, const N: usize> ops::Index<&'a Q> for IndexMap<K, V, S, N>
-where
-    K: Eq + Hash + Borrow<Q>,
-    Q: ?Sized + Eq + Hash,
-    S: BuildHasher,
-{
-    type Output = V;
+
+    /// Returns the key and a mutable reference to the value of the pair at position `index`, or
+    /// `None` if `index` is out of bounds.
+    ///
+    /// Computes in **O(1)** time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map = FnvIndexMap::<_, _, 8>::new();
+    /// map.insert("a", 1).unwrap();
+    /// if let Some((_, value)) = map.get_index_mut(0) {
+    ///     *value = 10;
+    /// }
+    /// assert_eq!(map["a"], 10);
+    /// ```
+    pub fn get_index_mut(&mut self, index: usize) -> Option<(&K, &mut V)> {
+        self.core
+            .entries
+            .get_mut(index)
+            .map(|bucket| (&bucket.key, &mut bucket.value))
+    }
+
+    /// Returns the position of the key-value pair equivalent to `key`, or `None` if `key` is not
+    /// in the map.
+    ///
+    /// Computes in **O(1)** time (average).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map = FnvIndexMap::<_, _, 8>::new();
+    /// map.insert("a", 1).unwrap();
+    /// map.insert("b", 2).unwrap();
+    /// assert_eq!(map.get_index_of("b"), Some(1));
+    /// assert_eq!(map.get_index_of("c"), None);
+    /// ```
+    pub fn get_index_of<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq,
+        S: KeyHasher<Q>,
+    {
+        self.find(key).map(|(_, found)| found)
+    }
+
+    /// Returns the first key-value pair of the map, or `None` if it is empty.
+    ///
+    /// Computes in **O(1)** time.
+    pub fn first(&self) -> Option<(&K, &V)> {
+        self.get_index(0)
+    }
+
+    /// Returns the last key-value pair of the map, or `None` if it is empty.
+    ///
+    /// Computes in **O(1)** time.
+    pub fn last(&self) -> Option<(&K, &V)> {
+        self.len()
+            .checked_sub(1)
+            .and_then(|last| self.get_index(last))
+    }
+
+    /// Removes the last key-value pair of the map and returns it, or `None` if it is empty.
+    ///
+    /// Computes in **O(1)** time (average).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map = FnvIndexMap::<_, _, 8>::new();
+    /// map.insert("a", 1).unwrap();
+    /// map.insert("b", 2).unwrap();
+    /// assert_eq!(map.pop(), Some(("b", 2)));
+    /// assert_eq!(map.last(), Some((&"a", &1)));
+    /// ```
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        let last = self.len().checked_sub(1)?;
+        self.core.swap_remove_index(last)
+    }
+
+    /// Removes the key-value pair at position `index` and returns it, or `None` if `index` is out
+    /// of bounds.
+    ///
+    /// Like `Vec::swap_remove`, the pair is removed by swapping it with the last element of the map
+    /// and popping it off. **This perturbs the postion of what used to be the last element!**
+    ///
+    /// Computes in **O(1)** time (average).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map = FnvIndexMap::<_, _, 8>::new();
+    /// map.insert("a", 1).unwrap();
+    /// map.insert("b", 2).unwrap();
+    /// map.insert("c", 3).unwrap();
+    /// assert_eq!(map.swap_remove_index(0), Some(("a", 1)));
+    /// assert!(map.keys().eq(&["c", "b"]));
+    /// ```
+    pub fn swap_remove_index(&mut self, index: usize) -> Option<(K, V)> {
+        self.core.swap_remove_index(index)
+    }
+
+    /// Swaps the positions of the key-value pairs at indices `a` and `b`.
+    ///
+    /// Computes in **O(1)** time (average).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` are out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map = FnvIndexMap::<_, _, 8>::new();
+    /// map.insert("a", 1).unwrap();
+    /// map.insert("b", 2).unwrap();
+    /// map.swap_indices(0, 1);
+    /// assert!(map.keys().eq(&["b", "a"]));
+    /// assert_eq!(map.get_index_of("a"), Some(1));
+    /// ```
+    pub fn swap_indices(&mut self, a: usize, b: usize) {
+        self.core.swap_indices(a, b)
+    }
+
+    /// Moves the key-value pair at position `from` to position `to`, shifting the pairs in
+    /// between by one position.
+    ///
+    /// Computes in **O(n)** time (average).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` or `to` are out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map = FnvIndexMap::<_, _, 8>::new();
+    /// for (i, key) in ["a", "b", "c", "d"].iter().enumerate() {
+    ///     map.insert(*key, i).unwrap();
+    /// }
+    ///
+    /// map.move_index(0, 2);
+    /// assert!(map.keys().eq(&["b", "c", "a", "d"]));
+    ///
+    /// map.move_index(3, 0);
+    /// assert!(map.keys().eq(&["d", "b", "c", "a"]));
+    /// ```
+    pub fn move_index(&mut self, from: usize, to: usize) {
+        self.core.move_index(from, to)
+    }
+
+    /// Removes the key-value pair equivalent to `key` and returns its value, or `None` if `key`
+    /// is not in the map.
+    ///
+    /// Like `Vec::remove`, the pair is removed by shifting all the pairs that follow it by one
+    /// position, which preserves the order of the map.
+    ///
+    /// Computes in **O(n)** time (average).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map = FnvIndexMap::<_, _, 8>::new();
+    /// map.insert("a", 1).unwrap();
+    /// map.insert("b", 2).unwrap();
+    /// map.insert("c", 3).unwrap();
+    /// assert_eq!(map.shift_remove("a"), Some(1));
+    /// assert!(map.keys().eq(&["b", "c"]));
+    /// ```
+    pub fn shift_remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq,
+        S: KeyHasher<Q>,
+    {
+        self.shift_remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Removes the key-value pair equivalent to `key` and returns it, or `None` if `key` is not in
+    /// the map.
+    ///
+    /// Like [`shift_remove`](struct.IndexMap.html#method.shift_remove), this preserves the order
+    /// of the map.
+    ///
+    /// Computes in **O(n)** time (average).
+    pub fn shift_remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq,
+        S: KeyHasher<Q>,
+    {
+        self.find(key)
+            .map(|(probe, found)| self.core.shift_remove_found(probe, found))
+    }
+
+    /// Retains only the key-value pairs specified by the predicate, in their order.
+    ///
+    /// In other words, removes all pairs `(k, v)` for which `f(&k, &mut v)` returns `false`.
+    ///
+    /// Computes in **O(n)** time (average).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map: FnvIndexMap<_, _, 8> = (0..8).map(|x| (x, x * 10)).collect();
+    /// map.retain(|&k, _| k % 3 != 0);
+    /// assert!(map.keys().eq(&[1, 2, 4, 5, 7]));
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        self.core.retain(|bucket| f(&bucket.key, &mut bucket.value))
+    }
+
+    /// Removes the key-value pairs in `range` and returns them as an iterator, in their order.
+    ///
+    /// The pairs after `range` are shifted down to fill the gap, preserving the order of the map.
+    /// The pairs that the iterator doesn't yield are dropped with it.
+    ///
+    /// Computes in **O(n)** time (average).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of `range` is greater than its end, or if its end is greater than the
+    /// length of the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map: FnvIndexMap<_, _, 8> = (0..5).map(|x| (x, x * 10)).collect();
+    /// assert!(map.drain(1..3).eq([(1, 10), (2, 20)]));
+    /// assert!(map.keys().eq(&[0, 3, 4]));
+    /// assert_eq!(map.get_index_of(&4), Some(2));
+    /// ```
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, K, V>
+    where
+        R: ops::RangeBounds<usize>,
+    {
+        let range = vec::range_of(range, self.len());
+        Drain {
+            iter: self.core.drain(range),
+        }
+    }
+
+    /// Sorts the map by key, in place.
+    ///
+    /// Computes in **O(n log n)** time (average).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map = FnvIndexMap::<_, _, 8>::new();
+    /// map.insert("b", 2).unwrap();
+    /// map.insert("c", 3).unwrap();
+    /// map.insert("a", 1).unwrap();
+    ///
+    /// map.sort_keys();
+    /// assert!(map.keys().eq(&["a", "b", "c"]));
+    /// assert_eq!(map["c"], 3);
+    /// ```
+    pub fn sort_keys(&mut self)
+    where
+        K: Ord,
+    {
+        // NOTE the keys are unique so an unstable sort gives the same order as a stable one
+        self.sort_unstable_by(|k1, _, k2, _| k1.cmp(k2))
+    }
+
+    /// Sorts the map in place with the comparison function `cmp`, which is given the key and the
+    /// value of two pairs.
+    ///
+    /// The sort is stable: pairs that compare equal keep their relative order.
+    ///
+    /// `core` has no stable sort, as the efficient ones need an allocation, so this is an insertion
+    /// sort: it does **O(n log n)** comparisons but moves **O(n²)** pairs in the worst case. Prefer
+    /// [`sort_unstable_by`](struct.IndexMap.html#method.sort_unstable_by) when stability is not
+    /// needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map = FnvIndexMap::<_, _, 8>::new();
+    /// map.insert("a", 2).unwrap();
+    /// map.insert("b", 1).unwrap();
+    /// map.insert("c", 2).unwrap();
+    ///
+    /// // by value, then in insertion order
+    /// map.sort_by(|_, v1, _, v2| v1.cmp(v2));
+    /// assert!(map.keys().eq(&["b", "a", "c"]));
+    /// ```
+    pub fn sort_by<F>(&mut self, mut cmp: F)
+    where
+        F: FnMut(&K, &V, &K, &V) -> Ordering,
+    {
+        let entries = &mut self.core.entries[..];
+        for i in 1..entries.len() {
+            let (sorted, rest) = entries.split_at(i);
+            let bucket = &rest[0];
+            // insert after the last pair that is not greater, to keep the sort stable
+            let j = sorted
+                .binary_search_by(|b| {
+                    cmp(&b.key, &b.value, &bucket.key, &bucket.value).then(Ordering::Less)
+                })
+                .unwrap_or_else(|i| i);
+            entries[j..=i].rotate_right(1);
+        }
+
+        self.core.rebuild_indices(self.len());
+    }
+
+    /// Sorts the map in place with the comparison function `cmp`, which is given the key and the
+    /// value of two pairs.
+    ///
+    /// The sort is unstable: pairs that compare equal may end up in any order.
+    ///
+    /// Computes in **O(n log n)** time (average).
+    pub fn sort_unstable_by<F>(&mut self, mut cmp: F)
+    where
+        F: FnMut(&K, &V, &K, &V) -> Ordering,
+    {
+        self.core
+            .entries
+            .sort_unstable_by(|a, b| cmp(&a.key, &a.value, &b.key, &b.value));
+
+        self.core.rebuild_indices(self.len());
+    }
+
+    /// Reverses the order of the key-value pairs of the map, in place.
+    ///
+    /// Computes in **O(n)** time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut map: FnvIndexMap<_, _, 8> = (0..3).map(|x| (x, x * 10)).collect();
+    /// map.reverse();
+    /// assert!(map.keys().eq(&[2, 1, 0]));
+    /// assert_eq!(map.get_index_of(&2), Some(0));
+    /// ```
+    pub fn reverse(&mut self) {
+        self.core.reverse()
+    }
+
+    /* Private API */
+    /// Return probe (indices) and position (entries)
+    fn find<Q>(&self, key: &Q) -> Option<(usize, usize)>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq,
+        S: KeyHasher<Q>,
+    {
+        if self.len() == 0 {
+            return None;
+        }
+        let h = hash_with(key, &self.build_hasher);
+        self.core.find(h, key)
+    }
+
+    fn insert_phase_1(&mut self, key: K, value: V) -> Inserted<V> {
+        let hash = hash_with(&key, &self.build_hasher);
+        self.core.insert_phase_1(hash, key, value)
+    }
+}
+
+impl<'a, K, Q, V, S, const N: usize> ops::Index<&'a Q> for IndexMap<K, V, S, N>
+where
+    K: Eq + Borrow<Q>,
+    Q: ?Sized + Eq,
+    S: KeyHasher<K> + KeyHasher<Q>,
+{
+    type Output = V;
 
     fn index(&self, key: &Q) -> &V {
         self.get(key).expect("key not found")
@@ -738,9 +1623,9 @@ where
 
 impl<'a, K, Q, V, S, const N: usize> ops::IndexMut<&'a Q> for IndexMap<K, V, S, N>
 where
-    K: Eq + Hash + Borrow<Q>,
-    Q: ?Sized + Eq + Hash,
-    S: BuildHasher,
+    K: Eq + Borrow<Q>,
+    Q: ?Sized + Eq,
+    S: KeyHasher<K> + KeyHasher<Q>,
 {
     fn index_mut(&mut self, key: &Q) -> &mut V {
         self.get_mut(key).expect("key not found")
@@ -749,7 +1634,7 @@ where
 
 impl<K, V, S, const N: usize> Clone for IndexMap<K, V, S, N>
 where
-    K: Eq + Hash + Clone,
+    K: Eq + Clone,
     V: Clone,
     S: Clone,
 {
@@ -763,9 +1648,9 @@ where
 
 impl<K, V, S, const N: usize> fmt::Debug for IndexMap<K, V, S, N>
 where
-    K: Eq + Hash + fmt::Debug,
+    K: Eq + fmt::Debug,
     V: fmt::Debug,
-    S: BuildHasher,
+    S: KeyHasher<K>,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_map().entries(self.iter()).finish()
@@ -774,8 +1659,8 @@ where
 
 impl<K, V, S, const N: usize> Default for IndexMap<K, V, S, N>
 where
-    K: Eq + Hash,
-    S: BuildHasher + Default,
+    K: Eq,
+    S: KeyHasher<K> + Default,
 {
     fn default() -> Self {
         IndexMap {
@@ -788,10 +1673,10 @@ where
 impl<K, V, S, S2, const N: usize, const N2: usize> PartialEq<IndexMap<K, V, S2, N2>>
     for IndexMap<K, V, S, N>
 where
-    K: Eq + Hash,
+    K: Eq,
     V: Eq,
-    S: BuildHasher,
-    S2: BuildHasher,
+    S: KeyHasher<K>,
+    S2: KeyHasher<K>,
 {
     fn eq(&self, other: &IndexMap<K, V, S2, N2>) -> bool {
         self.len() == other.len()
@@ -803,16 +1688,16 @@ where
 
 impl<K, V, S, const N: usize> Eq for IndexMap<K, V, S, N>
 where
-    K: Eq + Hash,
+    K: Eq,
     V: Eq,
-    S: BuildHasher,
+    S: KeyHasher<K>,
 {
 }
 
 impl<K, V, S, const N: usize> Extend<(K, V)> for IndexMap<K, V, S, N>
 where
-    K: Eq + Hash,
-    S: BuildHasher,
+    K: Eq,
+    S: KeyHasher<K>,
 {
     fn extend<I>(&mut self, iterable: I)
     where
@@ -826,9 +1711,9 @@ where
 
 impl<'a, K, V, S, const N: usize> Extend<(&'a K, &'a V)> for IndexMap<K, V, S, N>
 where
-    K: Eq + Hash + Copy,
+    K: Eq + Copy,
     V: Copy,
-    S: BuildHasher,
+    S: KeyHasher<K>,
 {
     fn extend<I>(&mut self, iterable: I)
     where
@@ -840,8 +1725,8 @@ where
 
 impl<K, V, S, const N: usize> FromIterator<(K, V)> for IndexMap<K, V, S, N>
 where
-    K: Eq + Hash,
-    S: BuildHasher + Default,
+    K: Eq,
+    S: KeyHasher<K> + Default,
 {
     fn from_iter<I>(iterable: I) -> Self
     where
@@ -853,10 +1738,42 @@ where
     }
 }
 
+impl<K, V, S, const N: usize> TryFromIterator<(K, V)> for IndexMap<K, V, S, N>
+where
+    K: Eq,
+    S: KeyHasher<K> + Default,
+{
+    fn try_from_iter<I>(iterable: I) -> Result<Self, CapacityError<(K, V)>>
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut map = IndexMap::default();
+        for (k, v) in iterable {
+            map.insert(k, v).map_err(CapacityError::new)?;
+        }
+        Ok(map)
+    }
+}
+
+impl<K, V, S, const N: usize> IntoIterator for IndexMap<K, V, S, N>
+where
+    K: Eq,
+    S: KeyHasher<K>,
+{
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            entries: self.core.entries.into_iter(),
+        }
+    }
+}
+
 impl<'a, K, V, S, const N: usize> IntoIterator for &'a IndexMap<K, V, S, N>
 where
-    K: Eq + Hash,
-    S: BuildHasher,
+    K: Eq,
+    S: KeyHasher<K>,
 {
     type Item = (&'a K, &'a V);
     type IntoIter = Iter<'a, K, V>;
@@ -868,8 +1785,8 @@ where
 
 impl<'a, K, V, S, const N: usize> IntoIterator for &'a mut IndexMap<K, V, S, N>
 where
-    K: Eq + Hash,
-    S: BuildHasher,
+    K: Eq,
+    S: KeyHasher<K>,
 {
     type Item = (&'a K, &'a mut V);
     type IntoIter = IterMut<'a, K, V>;
@@ -913,19 +1830,65 @@ impl<'a, K, V> Iterator for IterMut<'a, K, V> {
     }
 }
 
+/// An owning iterator over the key-value pairs of an [`IndexMap`], in their order
+pub struct IntoIter<K, V, const N: usize> {
+    entries: vec::IntoIter<Bucket<K, V>, N>,
+}
+
+impl<K, V, const N: usize> Iterator for IntoIter<K, V, N> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(|bucket| (bucket.key, bucket.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<K, V, const N: usize> ExactSizeIterator for IntoIter<K, V, N> {}
+
+/// A draining iterator over a range of key-value pairs of an [`IndexMap`], in their order
+///
+/// This `struct` is created by [`IndexMap::drain`].
+pub struct Drain<'a, K, V> {
+    iter: vec::Drain<'a, Bucket<K, V>>,
+}
+
+impl<K, V> Iterator for Drain<'_, K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|bucket| (bucket.key, bucket.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Drain<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next_back()
+            .map(|bucket| (bucket.key, bucket.value))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Drain<'_, K, V> {}
+
 fn hash_with<K, S>(key: &K, build_hasher: &S) -> HashValue
 where
-    K: ?Sized + Hash,
-    S: BuildHasher,
+    K: ?Sized,
+    S: KeyHasher<K>,
 {
-    let mut h = build_hasher.build_hasher();
-    key.hash(&mut h);
-    HashValue(h.finish() as u16)
+    HashValue(build_hasher.hash_key(key))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::FnvIndexMap;
+    use crate::{indexmap::Entry, FnvIndexMap};
     use core::mem;
 
     #[test]
@@ -970,4 +1933,348 @@ mod tests {
             assert!(a == b);
         }
     }
+
+    #[test]
+    fn entry_find() {
+        const MAP_SLOTS: usize = 4096;
+        let mut src: FnvIndexMap<usize, usize, MAP_SLOTS> = FnvIndexMap::new();
+        for x in 0..MAP_SLOTS {
+            src.insert(x, x).unwrap();
+        }
+
+        for x in 0..MAP_SLOTS {
+            match src.entry(x) {
+                Entry::Occupied(entry) => assert_eq!(*entry.get(), x),
+                Entry::Vacant(_) => panic!("Entry not found"),
+            }
+        }
+    }
+
+    #[test]
+    fn entry_vacant_insert() {
+        let mut map: FnvIndexMap<_, _, 4> = FnvIndexMap::new();
+        for i in 0..4 {
+            match map.entry(i) {
+                Entry::Occupied(_) => panic!("Found entry without inserting"),
+                Entry::Vacant(v) => {
+                    assert_eq!(i, *v.key());
+                    assert_eq!(v.insert(i * 10).unwrap(), &mut (i * 10));
+                }
+            }
+        }
+        for i in 0..4 {
+            assert_eq!(map[&i], i * 10);
+        }
+
+        // the map is full
+        match map.entry(4) {
+            Entry::Occupied(_) => panic!("Found entry without inserting"),
+            Entry::Vacant(v) => assert_eq!(v.insert(40), Err(40)),
+        }
+        assert_eq!(map.len(), 4);
+    }
+
+    #[test]
+    fn entry_occupied_remove() {
+        let mut map: FnvIndexMap<_, _, 8> = FnvIndexMap::new();
+        for i in 0..8 {
+            map.insert(i, i + 100).unwrap();
+        }
+
+        for i in 0..8 {
+            match map.entry(i) {
+                Entry::Occupied(o) => assert_eq!(o.remove_entry(), (i, i + 100)),
+                Entry::Vacant(_) => panic!("Value not found"),
+            }
+            assert!(!map.contains_key(&i));
+            for j in i + 1..8 {
+                assert_eq!(map[&j], j + 100);
+            }
+        }
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn entry_occupied_insert() {
+        let mut map: FnvIndexMap<_, _, 4> = FnvIndexMap::new();
+        map.insert("k", 1).unwrap();
+
+        match map.entry("k") {
+            Entry::Occupied(mut o) => {
+                *o.get_mut() += 1;
+                assert_eq!(o.insert(10), 2);
+            }
+            Entry::Vacant(_) => panic!("Value not found"),
+        }
+        assert_eq!(map["k"], 10);
+    }
+
+    #[test]
+    fn entry_or_insert() {
+        let mut map: FnvIndexMap<_, _, 2> = FnvIndexMap::new();
+        *map.entry("a").or_insert(1).unwrap() += 1;
+        *map.entry("a").or_insert(1).unwrap() += 1;
+        map.entry("b").and_modify(|v| *v += 1).or_default().unwrap();
+        map.entry("b").and_modify(|v| *v += 1).or_default().unwrap();
+        assert_eq!(map["a"], 3);
+        assert_eq!(map["b"], 1);
+
+        // full map: vacant entries hand the value back
+        assert_eq!(map.entry("c").or_insert_with(|| 42), Err(42));
+        assert_eq!(map.entry("a").or_insert_with(|| 42), Ok(&mut 3));
+    }
+
+    #[test]
+    fn index_access() {
+        let mut map: FnvIndexMap<_, _, 8> = FnvIndexMap::new();
+        assert_eq!(map.first(), None);
+        assert_eq!(map.pop(), None);
+        for i in 0..8 {
+            map.insert(i, i * 10).unwrap();
+        }
+
+        assert_eq!(map.first(), Some((&0, &0)));
+        assert_eq!(map.last(), Some((&7, &70)));
+        *map.get_index_mut(3).unwrap().1 += 1;
+        assert_eq!(map[&3], 31);
+
+        assert_eq!(map.swap_remove_index(8), None);
+        assert_eq!(map.swap_remove_index(2), Some((2, 20)));
+        assert_eq!(map.get_index_of(&7), Some(2));
+        assert_eq!(map.pop(), Some((6, 60)));
+        assert!(map.keys().eq(&[0, 1, 7, 3, 4, 5]));
+
+        map.swap_indices(0, 5);
+        map.move_index(1, 4);
+        map.move_index(5, 0);
+        assert!(map.keys().eq(&[0, 5, 7, 3, 4, 1]));
+
+        // the indices still find every key at its new position
+        for (i, (k, v)) in map.iter().enumerate() {
+            assert_eq!(map.get_index_of(k), Some(i));
+            assert_eq!(map.get(k), Some(v));
+        }
+        while let Some((k, _)) = map.pop() {
+            assert!(!map.contains_key(&k));
+            assert!(map.keys().all(|k| map.get_index_of(k).is_some()));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn swap_indices_out_of_bounds() {
+        let mut map: FnvIndexMap<_, _, 4> = FnvIndexMap::new();
+        map.insert(0, 0).unwrap();
+        map.swap_indices(0, 1);
+    }
+
+    // checks that `indices` finds every key at its position
+    fn assert_indices<const N: usize>(map: &FnvIndexMap<u16, u16, N>) {
+        for (i, (k, v)) in map.iter().enumerate() {
+            assert_eq!(map.get_index_of(k), Some(i));
+            assert_eq!(map.get(k), Some(v));
+        }
+    }
+
+    #[test]
+    fn shift_remove() {
+        let mut map: FnvIndexMap<u16, u16, 16> = (0..16).map(|x| (x * 7, x)).collect();
+
+        assert_eq!(map.shift_remove(&0), Some(0));
+        assert_eq!(map.shift_remove(&0), None);
+        assert_eq!(map.shift_remove_entry(&35), Some((35, 5)));
+        assert_eq!(map.shift_remove(&105), Some(15));
+        assert!(map.values().copied().eq((1..15).filter(|&x| x != 5)));
+        assert_indices(&map);
+
+        while let Some((&k, _)) = map.first() {
+            map.shift_remove(&k).unwrap();
+            assert_indices(&map);
+        }
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn sort() {
+        let mut map: FnvIndexMap<u16, u16, 16> = (0..16).map(|x| ((x * 7) % 16, x % 4)).collect();
+
+        map.sort_keys();
+        assert!(map.keys().copied().eq(0..16));
+        assert_indices(&map);
+
+        // stable: equal values stay sorted by key
+        map.sort_by(|_, v1, _, v2| v2.cmp(v1));
+        let keys = [3, 2, 1, 0]
+            .iter()
+            .flat_map(|&v| (0..16).filter(move |k| (k * 7) % 16 % 4 == v));
+        assert!(map.keys().copied().eq(keys));
+        assert_indices(&map);
+
+        map.sort_unstable_by(|k1, _, k2, _| k2.cmp(k1));
+        assert!(map.keys().copied().eq((0..16).rev()));
+        assert_indices(&map);
+
+        map.reverse();
+        assert!(map.keys().copied().eq(0..16));
+        assert_indices(&map);
+    }
+
+    #[test]
+    fn retain_drain() {
+        let mut map: FnvIndexMap<u16, u16, 16> = (0..16).map(|x| (x, x)).collect();
+
+        map.retain(|k, v| {
+            *v += 100;
+            k % 4 != 0
+        });
+        assert!(map.keys().copied().eq((0..16).filter(|k| k % 4 != 0)));
+        assert_eq!(map[&1], 101);
+        assert_indices(&map);
+
+        assert!(map.drain(2..5).map(|(k, _)| k).eq([3, 5, 6]));
+        assert!(map.keys().copied().eq([1, 2, 7, 9, 10, 11, 13, 14, 15]));
+        assert_indices(&map);
+
+        // the pairs that aren't yielded are dropped
+        let mut drain = map.drain(4..);
+        assert_eq!(drain.next_back(), Some((15, 115)));
+        drop(drain);
+        assert!(map.keys().copied().eq([1, 2, 7, 9]));
+        assert_indices(&map);
+
+        // a leaked drain leaves the map consistent
+        mem::forget(map.drain(1..2));
+        assert!(map.keys().copied().eq([1, 7, 9]));
+        assert_indices(&map);
+
+        map.drain(..);
+        assert!(map.is_empty());
+        assert_eq!(map.get(&1), None);
+    }
+
+    #[test]
+    fn retain_panic() {
+        let mut map: FnvIndexMap<u16, u16, 8> = (0..8).map(|x| (x, x)).collect();
+
+        let r = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            map.retain(|&k, _| {
+                if k == 6 {
+                    panic!();
+                }
+                k % 2 == 0
+            })
+        }));
+        assert!(r.is_err());
+
+        // the entries removed before the panic stay removed, and `indices` agrees with `entries`
+        assert!(map.keys().copied().eq([0, 2, 4, 6, 7].iter().copied()));
+        assert!(map.contains_key(&7));
+        assert!(!map.contains_key(&5));
+        assert_indices(&map);
+    }
+
+    #[test]
+    fn into_iter() {
+        let map: FnvIndexMap<u16, u16, 8> = (0..5).map(|x| (x, x * 10)).collect();
+        let mut iter = map.into_iter();
+        assert_eq!(iter.len(), 5);
+        assert_eq!(iter.next(), Some((0, 0)));
+        assert!(iter.eq([(1, 10), (2, 20), (3, 30), (4, 40)]));
+    }
+
+    // a `core::hash::Hasher` that only sets the high 16 bits of the hash
+    #[derive(Default)]
+    struct HighBits(u64);
+
+    impl core::hash::Hasher for HighBits {
+        fn finish(&self) -> u64 {
+            self.0 << 48
+        }
+
+        fn write(&mut self, bytes: &[u8]) {
+            for &byte in bytes {
+                self.0 = self.0.wrapping_mul(31).wrapping_add(u64::from(byte));
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct BuildHighBits;
+
+    impl core::hash::BuildHasher for BuildHighBits {
+        type Hasher = HighBits;
+
+        fn build_hasher(&self) -> HighBits {
+            HighBits::default()
+        }
+    }
+
+    #[test]
+    fn core_hash() {
+        use crate::{sealed::indexmap::KeyHasher, CoreBuildHasher, IndexMap, IndexSet};
+
+        // no `hash32::Hash` implementation
+        #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+        struct Key {
+            id: u16,
+            flag: bool,
+        }
+
+        static mut _MAP: IndexMap<Key, u8, CoreBuildHasher<BuildHighBits>, 8> =
+            IndexMap::with_hasher(CoreBuildHasher::new(BuildHighBits));
+        static mut _SET: IndexSet<Key, CoreBuildHasher<BuildHighBits>, 8> =
+            IndexSet::with_hasher(CoreBuildHasher::new(BuildHighBits));
+
+        // the high bits are folded into the hash value
+        let hasher = CoreBuildHasher::new(BuildHighBits);
+        assert_ne!(hasher.hash_key(&1u8), hasher.hash_key(&2u8));
+
+        let mut map = IndexMap::<Key, u16, _, 16>::with_hasher(hasher);
+        for id in 0..16 {
+            let key = Key {
+                id,
+                flag: id % 2 == 0,
+            };
+            assert_eq!(map.insert(key, id * 10), Ok(None));
+        }
+        for id in 0..16 {
+            let key = Key {
+                id,
+                flag: id % 2 == 0,
+            };
+            assert_eq!(map.get(&key), Some(&(id * 10)));
+            assert_eq!(map.get_index_of(&key), Some(usize::from(id)));
+            assert!(!map.contains_key(&Key {
+                id,
+                flag: id % 2 == 1
+            }));
+        }
+        assert_eq!(map.shift_remove(&Key { id: 0, flag: true }), Some(0));
+        assert_eq!(map.first(), Some((&Key { id: 1, flag: false }, &10)));
+
+        let mut set = IndexSet::<&str, CoreBuildHasher<BuildHighBits>, 4>::default();
+        assert_eq!(set.insert("a"), Ok(true));
+        assert_eq!(set.insert("a"), Ok(false));
+        assert!(set.contains("a"));
+        assert!(!set.contains("b"));
+    }
+
+    #[test]
+    fn try_from_iter() {
+        use crate::{CapacityError, TryCollect, TryFromIterator};
+
+        // duplicated keys don't use up capacity
+        let map = [("a", 1), ("a", 3), ("b", 2)]
+            .iter()
+            .cloned()
+            .try_collect::<FnvIndexMap<_, _, 2>>()
+            .unwrap();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["a"], 3);
+
+        assert_eq!(
+            FnvIndexMap::<_, _, 2>::try_from_iter((0..4).map(|i| (i, i))).err(),
+            Some(CapacityError::new((2, 2)))
+        );
+    }
 }
@@ -1,6 +1,7 @@
 use crate::indexmap::{self, IndexMap};
-use core::{borrow::Borrow, fmt, iter::FromIterator};
-use hash32::{BuildHasher, BuildHasherDefault, FnvHasher, Hash, Hasher};
+use crate::{sealed::indexmap::KeyHasher, CapacityError, TryFromIterator};
+use core::{borrow::Borrow, cmp::Ordering, fmt, iter::FromIterator, ops::RangeBounds};
+use hash32::{BuildHasherDefault, FnvHasher, Hasher};
 
 /// A [`heapless::IndexSet`](./struct.IndexSet.html) using the
 /// default FNV hasher.
@@ -74,16 +75,13 @@ pub type FnvIndexSet<T, const N: usize> = IndexSet<T, BuildHasherDefault<FnvHash
 ///     println!("{}", book);
 /// }
 /// ```
-pub struct IndexSet<T, S, const N: usize>
-where
-    T: Eq + Hash,
-{
+pub struct IndexSet<T, S, const N: usize> {
     map: IndexMap<T, (), S, N>,
 }
 
 impl<T, S, const N: usize> IndexSet<T, BuildHasherDefault<S>, N>
 where
-    T: Eq + Hash,
+    T: Eq,
     S: Default + Hasher,
 {
     /// Creates an empty `IndexSet`
@@ -96,10 +94,19 @@ where
     }
 }
 
+impl<T, S, const N: usize> IndexSet<T, S, N> {
+    /// Creates an empty `IndexSet` that hashes its values with `build_hasher`
+    pub const fn with_hasher(build_hasher: S) -> Self {
+        IndexSet {
+            map: IndexMap::with_hasher(build_hasher),
+        }
+    }
+}
+
 impl<T, S, const N: usize> IndexSet<T, S, N>
 where
-    T: Eq + Hash,
-    S: BuildHasher,
+    T: Eq,
+    S: KeyHasher<T>,
 {
     /// Returns the number of elements the set can hold
     ///
@@ -166,7 +173,7 @@ where
         other: &'a IndexSet<T, S2, N2>,
     ) -> Difference<'a, T, S2, N2>
     where
-        S2: BuildHasher,
+        S2: KeyHasher<T>,
     {
         Difference {
             iter: self.iter(),
@@ -201,7 +208,7 @@ where
         other: &'a IndexSet<T, S2, N2>,
     ) -> impl Iterator<Item = &'a T>
     where
-        S2: BuildHasher,
+        S2: KeyHasher<T>,
     {
         self.difference(other).chain(other.difference(self))
     }
@@ -230,7 +237,7 @@ where
         other: &'a IndexSet<T, S2, N2>,
     ) -> Intersection<'a, T, S2, N2>
     where
-        S2: BuildHasher,
+        S2: KeyHasher<T>,
     {
         Intersection {
             iter: self.iter(),
@@ -262,7 +269,7 @@ where
         other: &'a IndexSet<T, S2, N2>,
     ) -> impl Iterator<Item = &'a T>
     where
-        S2: BuildHasher,
+        S2: KeyHasher<T>,
     {
         self.iter().chain(other.difference(self))
     }
@@ -332,7 +339,8 @@ where
     pub fn contains<Q>(&self, value: &Q) -> bool
     where
         T: Borrow<Q>,
-        Q: ?Sized + Eq + Hash,
+        Q: ?Sized + Eq,
+        S: KeyHasher<Q>,
     {
         self.map.contains_key(value)
     }
@@ -356,7 +364,7 @@ where
     /// ```
     pub fn is_disjoint<S2, const N2: usize>(&self, other: &IndexSet<T, S2, N2>) -> bool
     where
-        S2: BuildHasher,
+        S2: KeyHasher<T>,
     {
         self.iter().all(|v| !other.contains(v))
     }
@@ -380,7 +388,7 @@ where
     /// ```
     pub fn is_subset<S2, const N2: usize>(&self, other: &IndexSet<T, S2, N2>) -> bool
     where
-        S2: BuildHasher,
+        S2: KeyHasher<T>,
     {
         self.iter().all(|v| other.contains(v))
     }
@@ -407,7 +415,7 @@ where
     /// ```
     pub fn is_superset<S2, const N2: usize>(&self, other: &IndexSet<T, S2, N2>) -> bool
     where
-        S2: BuildHasher,
+        S2: KeyHasher<T>,
     {
         other.is_subset(self)
     }
@@ -455,15 +463,253 @@ where
     pub fn remove<Q>(&mut self, value: &Q) -> bool
     where
         T: Borrow<Q>,
-        Q: ?Sized + Eq + Hash,
+        Q: ?Sized + Eq,
+        S: KeyHasher<Q>,
     {
         self.map.remove(value).is_some()
     }
+
+    /// Returns the value at position `index`, or `None` if `index` is out of bounds.
+    ///
+    /// Computes in **O(1)** time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let set: FnvIndexSet<_, 16> = [1, 2, 3].iter().cloned().collect();
+    /// assert_eq!(set.get_index(1), Some(&2));
+    /// assert_eq!(set.get_index(3), None);
+    /// ```
+    pub fn get_index(&self, index: usize) -> Option<&T> {
+        self.map.get_index(index).map(|(value, _)| value)
+    }
+
+    /// Returns the position of `value` in the set, or `None` if it is not in the set.
+    ///
+    /// The value may be any borrowed form of the set's value type, but `Hash` and `Eq` on the
+    /// borrowed form must match those for the value type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let set: FnvIndexSet<_, 16> = [1, 2, 3].iter().cloned().collect();
+    /// assert_eq!(set.get_index_of(&3), Some(2));
+    /// assert_eq!(set.get_index_of(&4), None);
+    /// ```
+    pub fn get_index_of<Q>(&self, value: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq,
+        S: KeyHasher<Q>,
+    {
+        self.map.get_index_of(value)
+    }
+
+    /// Returns the first value of the set, or `None` if it is empty.
+    pub fn first(&self) -> Option<&T> {
+        self.map.first().map(|(value, _)| value)
+    }
+
+    /// Returns the last value of the set, or `None` if it is empty.
+    pub fn last(&self) -> Option<&T> {
+        self.map.last().map(|(value, _)| value)
+    }
+
+    /// Removes the last value of the set and returns it, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let mut set: FnvIndexSet<_, 16> = [1, 2, 3].iter().cloned().collect();
+    /// assert_eq!(set.pop(), Some(3));
+    /// assert_eq!(set.last(), Some(&2));
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        self.map.pop().map(|(value, _)| value)
+    }
+
+    /// Removes the value at position `index` and returns it, or `None` if `index` is out of
+    /// bounds.
+    ///
+    /// Like `Vec::swap_remove`, the value is removed by swapping it with the last value of the set
+    /// and popping it off. **This perturbs the postion of what used to be the last value!**
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let mut set: FnvIndexSet<_, 16> = [1, 2, 3].iter().cloned().collect();
+    /// assert_eq!(set.swap_remove_index(0), Some(1));
+    /// assert!(set.iter().eq(&[3, 2]));
+    /// ```
+    pub fn swap_remove_index(&mut self, index: usize) -> Option<T> {
+        self.map.swap_remove_index(index).map(|(value, _)| value)
+    }
+
+    /// Swaps the positions of the values at indices `a` and `b`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` are out of bounds.
+    pub fn swap_indices(&mut self, a: usize, b: usize) {
+        self.map.swap_indices(a, b)
+    }
+
+    /// Moves the value at position `from` to position `to`, shifting the values in between by one
+    /// position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` or `to` are out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let mut set: FnvIndexSet<_, 16> = [1, 2, 3, 4].iter().cloned().collect();
+    /// set.move_index(3, 1);
+    /// assert!(set.iter().eq(&[1, 4, 2, 3]));
+    /// assert_eq!(set.get_index_of(&4), Some(1));
+    /// ```
+    pub fn move_index(&mut self, from: usize, to: usize) {
+        self.map.move_index(from, to)
+    }
+
+    /// Removes a value from the set, preserving the order of the values that follow it. Returns
+    /// `true` if the value was present in the set.
+    ///
+    /// Computes in **O(n)** time (average).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let mut set: FnvIndexSet<_, 16> = [1, 2, 3].iter().cloned().collect();
+    /// assert_eq!(set.shift_remove(&1), true);
+    /// assert!(set.iter().eq(&[2, 3]));
+    /// ```
+    pub fn shift_remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq,
+        S: KeyHasher<Q>,
+    {
+        self.map.shift_remove(value).is_some()
+    }
+
+    /// Removes and returns the value in the set equal to `value`, preserving the order of the
+    /// values that follow it, or `None` if there is no such value.
+    ///
+    /// Computes in **O(n)** time (average).
+    pub fn shift_take<Q>(&mut self, value: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq,
+        S: KeyHasher<Q>,
+    {
+        self.map.shift_remove_entry(value).map(|(value, _)| value)
+    }
+
+    /// Retains only the values specified by the predicate, in their order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let mut set: FnvIndexSet<_, 16> = (0..8).collect();
+    /// set.retain(|x| x % 3 != 0);
+    /// assert!(set.iter().eq(&[1, 2, 4, 5, 7]));
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.map.retain(|value, _| f(value))
+    }
+
+    /// Removes the values in `range` and returns them as an iterator, in their order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of `range` is greater than its end, or if its end is greater than the
+    /// length of the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let mut set: FnvIndexSet<_, 16> = (0..5).collect();
+    /// assert!(set.drain(..2).eq([0, 1]));
+    /// assert!(set.iter().eq(&[2, 3, 4]));
+    /// ```
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T>
+    where
+        R: RangeBounds<usize>,
+    {
+        Drain {
+            iter: self.map.drain(range),
+        }
+    }
+
+    /// Sorts the values of the set, in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let mut set: FnvIndexSet<_, 16> = [3, 1, 2].iter().cloned().collect();
+    /// set.sort();
+    /// assert!(set.iter().eq(&[1, 2, 3]));
+    /// ```
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.map.sort_keys()
+    }
+
+    /// Sorts the values of the set in place with the comparison function `cmp`.
+    ///
+    /// The sort is stable; see [`IndexMap::sort_by`](struct.IndexMap.html#method.sort_by) for its
+    /// cost.
+    pub fn sort_by<F>(&mut self, mut cmp: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.map.sort_by(|a, _, b, _| cmp(a, b))
+    }
+
+    /// Sorts the values of the set in place with the comparison function `cmp`.
+    ///
+    /// The sort is unstable: values that compare equal may end up in any order.
+    pub fn sort_unstable_by<F>(&mut self, mut cmp: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.map.sort_unstable_by(|a, _, b, _| cmp(a, b))
+    }
+
+    /// Reverses the order of the values of the set, in place.
+    pub fn reverse(&mut self) {
+        self.map.reverse()
+    }
 }
 
 impl<T, S, const N: usize> Clone for IndexSet<T, S, N>
 where
-    T: Eq + Hash + Clone,
+    T: Eq + Clone,
     S: Clone,
 {
     fn clone(&self) -> Self {
@@ -475,8 +721,8 @@ where
 
 impl<T, S, const N: usize> fmt::Debug for IndexSet<T, S, N>
 where
-    T: Eq + Hash + fmt::Debug,
-    S: BuildHasher,
+    T: Eq + fmt::Debug,
+    S: KeyHasher<T>,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_set().entries(self.iter()).finish()
@@ -485,8 +731,8 @@ where
 
 impl<T, S, const N: usize> Default for IndexSet<T, S, N>
 where
-    T: Eq + Hash,
-    S: BuildHasher + Default,
+    T: Eq,
+    S: KeyHasher<T> + Default,
 {
     fn default() -> Self {
         IndexSet {
@@ -498,9 +744,9 @@ where
 impl<T, S1, S2, const N1: usize, const N2: usize> PartialEq<IndexSet<T, S2, N2>>
     for IndexSet<T, S1, N1>
 where
-    T: Eq + Hash,
-    S1: BuildHasher,
-    S2: BuildHasher,
+    T: Eq,
+    S1: KeyHasher<T>,
+    S2: KeyHasher<T>,
 {
     fn eq(&self, other: &IndexSet<T, S2, N2>) -> bool {
         self.len() == other.len() && self.is_subset(other)
@@ -509,8 +755,8 @@ where
 
 impl<T, S, const N: usize> Extend<T> for IndexSet<T, S, N>
 where
-    T: Eq + Hash,
-    S: BuildHasher,
+    T: Eq,
+    S: KeyHasher<T>,
 {
     fn extend<I>(&mut self, iterable: I)
     where
@@ -522,8 +768,8 @@ where
 
 impl<'a, T, S, const N: usize> Extend<&'a T> for IndexSet<T, S, N>
 where
-    T: 'a + Eq + Hash + Copy,
-    S: BuildHasher,
+    T: 'a + Eq + Copy,
+    S: KeyHasher<T>,
 {
     fn extend<I>(&mut self, iterable: I)
     where
@@ -535,8 +781,8 @@ where
 
 impl<T, S, const N: usize> FromIterator<T> for IndexSet<T, S, N>
 where
-    T: Eq + Hash,
-    S: BuildHasher + Default,
+    T: Eq,
+    S: KeyHasher<T> + Default,
 {
     fn from_iter<I>(iter: I) -> Self
     where
@@ -548,10 +794,42 @@ where
     }
 }
 
+impl<T, S, const N: usize> TryFromIterator<T> for IndexSet<T, S, N>
+where
+    T: Eq,
+    S: KeyHasher<T> + Default,
+{
+    fn try_from_iter<I>(iter: I) -> Result<Self, CapacityError<T>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut set = IndexSet::default();
+        for value in iter {
+            set.insert(value).map_err(CapacityError::new)?;
+        }
+        Ok(set)
+    }
+}
+
+impl<T, S, const N: usize> IntoIterator for IndexSet<T, S, N>
+where
+    T: Eq,
+    S: KeyHasher<T>,
+{
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            iter: self.map.into_iter(),
+        }
+    }
+}
+
 impl<'a, T, S, const N: usize> IntoIterator for &'a IndexSet<T, S, N>
 where
-    T: Eq + Hash,
-    S: BuildHasher,
+    T: Eq,
+    S: KeyHasher<T>,
 {
     type Item = &'a T;
     type IntoIter = Iter<'a, T>;
@@ -581,10 +859,56 @@ impl<'a, T> Clone for Iter<'a, T> {
     }
 }
 
+/// An owning iterator over the values of an [`IndexSet`], in their order
+pub struct IntoIter<T, const N: usize> {
+    iter: indexmap::IntoIter<T, (), N>,
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(k, _)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
+
+/// A draining iterator over a range of values of an [`IndexSet`], in their order
+///
+/// This `struct` is created by [`IndexSet::drain`].
+pub struct Drain<'a, T> {
+    iter: indexmap::Drain<'a, T, ()>,
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(k, _)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Drain<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(k, _)| k)
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+
 pub struct Difference<'a, T, S, const N: usize>
 where
-    S: BuildHasher,
-    T: Eq + Hash,
+    S: KeyHasher<T>,
+    T: Eq,
 {
     iter: Iter<'a, T>,
     other: &'a IndexSet<T, S, N>,
@@ -592,8 +916,8 @@ where
 
 impl<'a, T, S, const N: usize> Iterator for Difference<'a, T, S, N>
 where
-    S: BuildHasher,
-    T: Eq + Hash,
+    S: KeyHasher<T>,
+    T: Eq,
 {
     type Item = &'a T;
 
@@ -609,8 +933,8 @@ where
 
 pub struct Intersection<'a, T, S, const N: usize>
 where
-    S: BuildHasher,
-    T: Eq + Hash,
+    S: KeyHasher<T>,
+    T: Eq,
 {
     iter: Iter<'a, T>,
     other: &'a IndexSet<T, S, N>,
@@ -618,8 +942,8 @@ where
 
 impl<'a, T, S, const N: usize> Iterator for Intersection<'a, T, S, N>
 where
-    S: BuildHasher,
-    T: Eq + Hash,
+    S: KeyHasher<T>,
+    T: Eq,
 {
     type Item = &'a T;
 
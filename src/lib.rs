@@ -44,20 +44,26 @@
 //! List of currently implemented data structures:
 //!
 //! - [`BinaryHeap`](binary_heap/struct.BinaryHeap.html) -- priority queue
+//! - [`CString`](struct.CString.html) -- nul terminated string for FFI (`cstring` feature)
+//! - [`Deque`](struct.Deque.html) -- double-ended circular buffer
 //! - [`IndexMap`](struct.IndexMap.html) -- hash table
 //! - [`IndexSet`](struct.IndexSet.html) -- hash set
 //! - [`LinearMap`](struct.LinearMap.html)
+//! - [`SortedLinearMap`](struct.SortedLinearMap.html) -- map sorted by key, with binary search
 //! - [`Pool`](pool/struct.Pool.html) -- lock-free memory pool
 //! - [`String`](struct.String.html)
 //! - [`Vec`](struct.Vec.html)
 //! - [`mpmc::Q*`](mpmc/index.html) -- multiple producer multiple consumer lock-free queue
 //! - [`spsc::Queue`](spsc/struct.Queue.html) -- single producer single consumer lock-free queue
+//! - [`timer::TimerQueue`](timer/struct.TimerQueue.html) -- timer queue with cancellable timers
 //!
 //! # Optional Features
 //!
 //! The `heapless` crate provides the following optional Cargo features:
 //!
 //! - `ufmt-impl`: Implement [`ufmt_write::uWrite`] for `String<N>` and `Vec<u8, N>`
+//! - `std`: Implement `std::error::Error` for [`CapacityError`]
+//! - `cstring`: Enable `CString`, which requires Rust 1.64
 //!
 //! [`ufmt_write::uWrite`]: https://docs.rs/ufmt-write/
 //!
@@ -66,7 +72,7 @@
 //! This crate is guaranteed to compile on stable Rust 1.51 and up with its default set of features.
 //! It *might* compile on older versions but that may change in any new patch release.
 
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 #![deny(missing_docs)]
 #![deny(rust_2018_compatibility)]
 #![deny(rust_2018_idioms)]
@@ -74,19 +80,28 @@
 #![deny(const_err)]
 
 pub use binary_heap::BinaryHeap;
-pub use histbuf::HistoryBuffer;
-pub use indexmap::{Bucket, FnvIndexMap, IndexMap, Pos};
+#[cfg(feature = "cstring")]
+pub use c_string::CString;
+pub use capacity_error::CapacityError;
+pub use deque::{Deque, DequeView};
+pub use histbuf::{HistoryBuffer, OldestOrdered};
+pub use indexmap::{
+    Bucket, CoreBuildHasher, Entry, FnvIndexMap, IndexMap, OccupiedEntry, Pos, VacantEntry,
+};
 pub use indexset::{FnvIndexSet, IndexSet};
 pub use linear_map::LinearMap;
-pub use string::String;
-pub use vec::Vec;
+pub use sorted_linear_map::SortedLinearMap;
+pub use string::{format, format_truncated, FromUtf8Error, String, StringView, TRUNCATION_MARKER};
+pub use try_from_iter::{TryCollect, TryFromIterator};
+pub use vec::{Vec, VecView};
 
+mod capacity_error;
 // NOTE this code was last ported from v0.4.1 of the indexmap crate
-mod histbuf;
+mod deque;
 mod indexmap;
 mod indexset;
-mod linear_map;
 mod string;
+mod try_from_iter;
 mod vec;
 
 #[cfg(feature = "serde")]
@@ -95,14 +110,20 @@ mod de;
 mod ser;
 
 pub mod binary_heap;
+#[cfg(feature = "cstring")]
+pub mod c_string;
 #[cfg(feature = "defmt-impl")]
 mod defmt;
+pub mod histbuf;
+pub mod linear_map;
 #[cfg(all(has_cas, feature = "cas"))]
 pub mod mpmc;
 #[cfg(all(has_cas, feature = "cas"))]
 pub mod pool;
+pub mod sorted_linear_map;
 #[cfg(has_atomics)]
 pub mod spsc;
+pub mod timer;
 
 #[cfg(feature = "ufmt-impl")]
 mod ufmt;
@@ -1,4 +1,8 @@
-use crate::Vec;
+//! A fixed capacity map that performs lookups via linear search
+//!
+//! See [`LinearMap`] for more details.
+
+use crate::{vec, CapacityError, TryFromIterator, Vec};
 use core::{borrow::Borrow, fmt, iter::FromIterator, mem, ops, slice};
 
 /// A fixed capacity map / dictionary that performs lookups via linear search
@@ -80,7 +84,11 @@ where
     /// assert_eq!(map.contains_key(&1), true);
     /// assert_eq!(map.contains_key(&2), false);
     /// ```
-    pub fn contains_key(&self, key: &K) -> bool {
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
         self.get(key).is_some()
     }
 
@@ -103,9 +111,29 @@ where
         K: Borrow<Q>,
         Q: Eq + ?Sized,
     {
-        self.iter()
-            .find(|&(k, _)| k.borrow() == key)
-            .map(|(_, v)| v)
+        self.get_key_value(key).map(|(_, v)| v)
+    }
+
+    /// Returns the key-value pair corresponding to the key
+    ///
+    /// Computes in **O(N)** time
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::LinearMap;
+    ///
+    /// let mut map: LinearMap<_, _, 8> = LinearMap::new();
+    /// map.insert(1, "a").unwrap();
+    /// assert_eq!(map.get_key_value(&1), Some((&1, &"a")));
+    /// assert_eq!(map.get_key_value(&2), None);
+    /// ```
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.iter().find(|&(k, _)| k.borrow() == key)
     }
 
     /// Returns a mutable reference to the value corresponding to the key
@@ -291,13 +319,112 @@ where
         K: Borrow<Q>,
         Q: Eq + ?Sized,
     {
-        let idx = self
-            .keys()
-            .enumerate()
-            .find(|&(_, k)| k.borrow() == key)
-            .map(|(idx, _)| idx);
+        self.remove_entry(key).map(|(_, v)| v)
+    }
+
+    /// Removes a key from the map, returning the stored key and value if the key was previously in
+    /// the map
+    ///
+    /// Computes in **O(N)** time
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::LinearMap;
+    ///
+    /// let mut map: LinearMap<_, _, 8> = LinearMap::new();
+    /// map.insert(1, "a").unwrap();
+    /// assert_eq!(map.remove_entry(&1), Some((1, "a")));
+    /// assert_eq!(map.remove_entry(&1), None);
+    /// ```
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let idx = self.position(key)?;
+        Some(self.buffer.swap_remove(idx))
+    }
+
+    fn position<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.keys().position(|k| k.borrow() == key)
+    }
+
+    /// Returns an entry for the corresponding key
+    ///
+    /// The key is looked up only once; the returned entry can then be used to read, update,
+    /// insert or remove the key-value pair without further lookups.
+    ///
+    /// Computes in **O(N)** time
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::linear_map::{Entry, LinearMap};
+    ///
+    /// let mut counts: LinearMap<_, u32, 8> = LinearMap::new();
+    /// for word in ["a", "b", "a"] {
+    ///     *counts.entry(word).or_insert(0).unwrap() += 1;
+    /// }
+    /// assert_eq!(counts["a"], 2);
+    ///
+    /// if let Entry::Occupied(entry) = counts.entry("b") {
+    ///     assert_eq!(entry.remove(), 1);
+    /// }
+    /// assert!(!counts.contains_key("b"));
+    /// ```
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, N> {
+        match self.position(&key) {
+            Some(index) => Entry::Occupied(OccupiedEntry { index, map: self }),
+            None => Entry::Vacant(VacantEntry { key, map: self }),
+        }
+    }
+
+    /// Retains only the elements specified by the predicate
+    ///
+    /// In other words, removes all pairs `(k, v)` for which `f(&k, &mut v)` returns `false`. The
+    /// order of the remaining elements is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::LinearMap;
+    ///
+    /// let mut map: LinearMap<_, _, 8> = (0..8).map(|x| (x, x * 10)).collect();
+    /// map.retain(|&k, _| k % 2 == 0);
+    /// assert!(map.keys().eq(&[0, 2, 4, 6]));
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        self.buffer.retain_mut(|(k, v)| f(k, v))
+    }
 
-        idx.map(|idx| self.buffer.swap_remove(idx).1)
+    /// Clears the map, returning all key-value pairs as an iterator
+    ///
+    /// The pairs that the iterator doesn't yield are dropped with it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::LinearMap;
+    ///
+    /// let mut map: LinearMap<_, _, 8> = LinearMap::new();
+    /// map.insert(1, "a").unwrap();
+    /// map.insert(2, "b").unwrap();
+    ///
+    /// assert!(map.drain().eq([(1, "a"), (2, "b")]));
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn drain(&mut self) -> Drain<'_, K, V> {
+        Drain {
+            inner: self.buffer.drain(..),
+        }
     }
 
     /// An iterator visiting all values in arbitrary order
@@ -367,6 +494,176 @@ where
     }
 }
 
+/// A view into a single entry in a [`LinearMap`], which may either be vacant or occupied
+///
+/// This `enum` is created by [`LinearMap::entry`].
+pub enum Entry<'a, K, V, const N: usize> {
+    /// The entry corresponding to the key `K` exists in the map
+    Occupied(OccupiedEntry<'a, K, V, N>),
+    /// The entry corresponding to the key `K` does not exist in the map
+    Vacant(VacantEntry<'a, K, V, N>),
+}
+
+impl<'a, K, V, const N: usize> Entry<'a, K, V, N>
+where
+    K: Eq,
+{
+    /// Returns a reference to this entry's key
+    pub fn key(&self) -> &K {
+        match *self {
+            Entry::Occupied(ref entry) => entry.key(),
+            Entry::Vacant(ref entry) => entry.key(),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the default if empty, and returns a mutable
+    /// reference to the value in the entry
+    ///
+    /// Returns back `default` if the entry is vacant and the map is full.
+    pub fn or_insert(self, default: V) -> Result<&'a mut V, V> {
+        match self {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default function if empty,
+    /// and returns a mutable reference to the value in the entry
+    ///
+    /// `default` is only called if the entry is vacant. Returns back the value it produced if the
+    /// map is full.
+    pub fn or_insert_with<F>(self, default: F) -> Result<&'a mut V, V>
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting, if empty, the result of the default function
+    /// which is given a reference to the key, and returns a mutable reference to the value in the
+    /// entry
+    pub fn or_insert_with_key<F>(self, default: F) -> Result<&'a mut V, V>
+    where
+        F: FnOnce(&K) -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => {
+                let value = default(entry.key());
+                entry.insert(value)
+            }
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting `V::default()` if empty, and returns a mutable
+    /// reference to the value in the entry
+    pub fn or_default(self) -> Result<&'a mut V, V>
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any potential inserts into the
+    /// map
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        match self {
+            Entry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                Entry::Occupied(entry)
+            }
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+}
+
+/// An occupied entry which can be manipulated
+pub struct OccupiedEntry<'a, K, V, const N: usize> {
+    index: usize,
+    map: &'a mut LinearMap<K, V, N>,
+}
+
+impl<'a, K, V, const N: usize> OccupiedEntry<'a, K, V, N>
+where
+    K: Eq,
+{
+    /// Gets a reference to the key in the map
+    pub fn key(&self) -> &K {
+        &self.map.buffer[self.index].0
+    }
+
+    /// Removes this entry from the map and yields its corresponding key and value
+    ///
+    /// Like [`LinearMap::remove`], this moves the last element of the map in its place.
+    pub fn remove_entry(self) -> (K, V) {
+        self.map.buffer.swap_remove(self.index)
+    }
+
+    /// Gets a reference to the value associated with this entry
+    pub fn get(&self) -> &V {
+        &self.map.buffer[self.index].1
+    }
+
+    /// Gets a mutable reference to the value associated with this entry
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.map.buffer[self.index].1
+    }
+
+    /// Consumes this entry and yields a reference to the underlying value
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.map.buffer[self.index].1
+    }
+
+    /// Overwrites the underlying map's value with this entry's value, returning the old value
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    /// Removes this entry from the map and yields its value
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+}
+
+/// A view into an empty slot in the underlying map
+pub struct VacantEntry<'a, K, V, const N: usize> {
+    key: K,
+    map: &'a mut LinearMap<K, V, N>,
+}
+
+impl<'a, K, V, const N: usize> VacantEntry<'a, K, V, N>
+where
+    K: Eq,
+{
+    /// Get the key associated with this entry
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Consumes this entry to yield to key associated with it
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Inserts this entry into to underlying map, yields a mutable reference to the inserted value
+    ///
+    /// If the map is at capacity the value is returned instead.
+    pub fn insert(self, value: V) -> Result<&'a mut V, V> {
+        self.map
+            .buffer
+            .push((self.key, value))
+            .map_err(|(_, value)| value)?;
+        // NOTE(unwrap) the entry has just been pushed
+        Ok(&mut self.map.buffer.last_mut().unwrap().1)
+    }
+}
+
 impl<K, V, const N: usize> Default for LinearMap<K, V, N>
 where
     K: Eq,
@@ -412,6 +709,23 @@ where
     }
 }
 
+impl<K, V, const N: usize> TryFromIterator<(K, V)> for LinearMap<K, V, N>
+where
+    K: Eq,
+{
+    fn try_from_iter<I>(iter: I) -> Result<Self, CapacityError<(K, V)>>
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut out = Self::new();
+        for (k, v) in iter {
+            out.insert(k, v).map_err(CapacityError::new)?;
+        }
+        Ok(out)
+    }
+}
+
+/// An owning iterator over the entries of a [`LinearMap`], in arbitrary order
 pub struct IntoIter<K, V, const N: usize>
 where
     K: Eq,
@@ -427,6 +741,26 @@ where
     fn next(&mut self) -> Option<Self::Item> {
         self.inner.next()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V, const N: usize> ExactSizeIterator for IntoIter<K, V, N> where K: Eq {}
+
+impl<K, V, const N: usize> IntoIterator for LinearMap<K, V, N>
+where
+    K: Eq,
+{
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            inner: self.buffer.into_iter(),
+        }
+    }
 }
 
 impl<'a, K, V, const N: usize> IntoIterator for &'a LinearMap<K, V, N>
@@ -441,6 +775,7 @@ where
     }
 }
 
+/// An iterator over the entries of a [`LinearMap`], in arbitrary order
 pub struct Iter<'a, K, V> {
     iter: slice::Iter<'a, (K, V)>,
 }
@@ -461,15 +796,7 @@ impl<'a, K, V> Clone for Iter<'a, K, V> {
     }
 }
 
-impl<K, V, const N: usize> Drop for LinearMap<K, V, N> {
-    fn drop(&mut self) {
-        // heapless::Vec implements drop right?
-        drop(&self.buffer);
-        // original code below
-        // unsafe { ptr::drop_in_place(self.buffer.as_mut_slice()) }
-    }
-}
-
+/// A mutable iterator over the entries of a [`LinearMap`], in arbitrary order
 pub struct IterMut<'a, K, V> {
     iter: slice::IterMut<'a, (K, V)>,
 }
@@ -482,6 +809,45 @@ impl<'a, K, V> Iterator for IterMut<'a, K, V> {
     }
 }
 
+impl<'a, K, V, const N: usize> IntoIterator for &'a mut LinearMap<K, V, N>
+where
+    K: Eq,
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// A draining iterator over the entries of a [`LinearMap`], in arbitrary order
+///
+/// This `struct` is created by [`LinearMap::drain`].
+pub struct Drain<'a, K, V> {
+    inner: vec::Drain<'a, (K, V)>,
+}
+
+impl<K, V> Iterator for Drain<'_, K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Drain<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Drain<'_, K, V> {}
+
 impl<K, V, const N: usize, const N2: usize> PartialEq<LinearMap<K, V, N2>> for LinearMap<K, V, N>
 where
     K: Eq,
@@ -504,6 +870,9 @@ where
 
 #[cfg(test)]
 mod test {
+    use std::string::String;
+
+    use super::Entry;
     use crate::LinearMap;
 
     #[test]
@@ -540,5 +909,131 @@ mod test {
         }
     }
 
-    // TODO: drop test
+    #[test]
+    fn entry() {
+        let mut map = LinearMap::<_, _, 2>::new();
+        assert_eq!(map.entry("a").or_insert(1), Ok(&mut 1));
+        *map.entry("a").or_insert(10).unwrap() += 1;
+        assert_eq!(map["a"], 2);
+
+        map.entry("a")
+            .and_modify(|v| *v *= 10)
+            .or_default()
+            .unwrap();
+        assert_eq!(map["a"], 20);
+        assert_eq!(map.entry("b").or_insert_with_key(|k| k.len()), Ok(&mut 1));
+        // full
+        assert_eq!(map.entry("c").or_insert(3), Err(3));
+
+        match map.entry("b") {
+            Entry::Occupied(mut entry) => {
+                assert_eq!(entry.insert(5), 1);
+                assert_eq!(entry.remove_entry(), ("b", 5));
+            }
+            Entry::Vacant(_) => panic!(),
+        }
+        match map.entry("b") {
+            Entry::Occupied(_) => panic!(),
+            Entry::Vacant(entry) => assert_eq!(entry.into_key(), "b"),
+        }
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn borrow() {
+        let mut map = LinearMap::<String, u8, 4>::new();
+        map.insert(String::from("ab"), 1).unwrap();
+
+        assert!(map.contains_key("ab"));
+        assert!(!map.contains_key("b"));
+        assert_eq!(
+            map.get_key_value("ab").map(|(k, v)| (k.as_str(), *v)),
+            Some(("ab", 1))
+        );
+        assert_eq!(
+            map.remove_entry("ab").map(|(k, v)| (k.len(), v)),
+            Some((2, 1))
+        );
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn retain_drain() {
+        let mut map: LinearMap<_, _, 8> = (0..8).map(|i| (i, i)).collect();
+        map.retain(|k, v| {
+            *v *= 2;
+            k % 3 != 0
+        });
+        assert!(map
+            .iter()
+            .eq([(&1, &2), (&2, &4), (&4, &8), (&5, &10), (&7, &14)]));
+
+        let mut drain = map.drain();
+        assert_eq!(drain.len(), 5);
+        assert_eq!(drain.next(), Some((1, 2)));
+        assert_eq!(drain.next_back(), Some((7, 14)));
+        core::mem::drop(drain);
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn into_iter() {
+        let mut map = LinearMap::<_, _, 4>::new();
+        map.insert(1, 'a').unwrap();
+        map.insert(2, 'b').unwrap();
+
+        for (_, v) in &mut map {
+            *v = v.to_ascii_uppercase();
+        }
+
+        let mut iter = map.into_iter();
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next(), Some((1, 'A')));
+        assert_eq!(iter.next(), Some((2, 'B')));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn drop() {
+        static mut COUNT: i32 = 0;
+
+        struct Droppable;
+        impl Droppable {
+            fn new() -> Self {
+                unsafe {
+                    COUNT += 1;
+                }
+                Droppable
+            }
+        }
+        impl Drop for Droppable {
+            fn drop(&mut self) {
+                unsafe {
+                    COUNT -= 1;
+                }
+            }
+        }
+
+        {
+            let mut map = LinearMap::<u8, Droppable, 4>::new();
+            map.insert(0, Droppable::new()).ok().unwrap();
+            map.insert(1, Droppable::new()).ok().unwrap();
+            map.insert(2, Droppable::new()).ok().unwrap();
+
+            let mut iter = map.into_iter();
+            iter.next().unwrap();
+            assert_eq!(unsafe { COUNT }, 2);
+        }
+        assert_eq!(unsafe { COUNT }, 0);
+
+        {
+            let mut map = LinearMap::<u8, Droppable, 4>::new();
+            map.insert(0, Droppable::new()).ok().unwrap();
+            map.insert(1, Droppable::new()).ok().unwrap();
+            map.retain(|&k, _| k == 1);
+            assert_eq!(unsafe { COUNT }, 1);
+            map.drain().next_back();
+            assert_eq!(unsafe { COUNT }, 0);
+        }
+    }
 }
@@ -1,23 +1,93 @@
 /// Sealed traits and implementations for `binary_heap`
 pub mod binary_heap {
+    use core::marker::PhantomData;
+
     use crate::binary_heap::{Max, Min};
-    use core::cmp::Ordering;
 
-    /// The binary heap kind: min-heap or max-heap
-    pub unsafe trait Kind {
+    /// The binary heap kind `K`: min-heap or max-heap
+    ///
+    /// NOTE implemented by `PhantomData<K>` rather than by `K` because Rust 1.51 rejects bounds
+    /// like `K: Kind` on `const fn`s but accepts `PhantomData<K>: Kind<K>`
+    pub trait Kind<K> {
+        #[doc(hidden)]
+        const INSTANCE: K;
+    }
+
+    impl Kind<Min> for PhantomData<Min> {
+        const INSTANCE: Min = Min;
+    }
+
+    impl Kind<Max> for PhantomData<Max> {
+        const INSTANCE: Max = Max;
+    }
+}
+
+/// Sealed traits and implementations for `histbuf`
+pub mod histbuf {
+    use crate::histbuf::OldestOrdered;
+
+    /// The coarser tiers of an `Archive`: another `Archive`, or `()` after the last tier
+    pub trait Cascade<T> {
+        #[doc(hidden)]
+        const TIERS: usize;
+
+        #[doc(hidden)]
+        fn write(&mut self, value: T);
+
+        #[doc(hidden)]
+        fn tier(&self, index: usize) -> Option<OldestOrdered<'_, T>>;
+
+        #[doc(hidden)]
+        fn clear(&mut self);
+    }
+
+    impl<T> Cascade<T> for () {
+        const TIERS: usize = 0;
+
+        fn write(&mut self, _: T) {}
+
+        fn tier(&self, _: usize) -> Option<OldestOrdered<'_, T>> {
+            None
+        }
+
+        fn clear(&mut self) {}
+    }
+}
+
+/// Sealed traits and implementations for `IndexMap` and `IndexSet`
+pub mod indexmap {
+    use crate::indexmap::CoreBuildHasher;
+
+    /// How an `IndexMap` hashes its keys: with a `hash32::BuildHasher` for `hash32::Hash` keys, or
+    /// with a `CoreBuildHasher` for `core::hash::Hash` keys
+    pub trait KeyHasher<K: ?Sized> {
         #[doc(hidden)]
-        fn ordering() -> Ordering;
+        fn hash_key(&self, key: &K) -> u16;
     }
 
-    unsafe impl Kind for Min {
-        fn ordering() -> Ordering {
-            Ordering::Less
+    impl<K, S> KeyHasher<K> for S
+    where
+        K: ?Sized + hash32::Hash,
+        S: hash32::BuildHasher,
+    {
+        fn hash_key(&self, key: &K) -> u16 {
+            let mut hasher = self.build_hasher();
+            key.hash(&mut hasher);
+            hash32::Hasher::finish(&hasher) as u16
         }
     }
 
-    unsafe impl Kind for Max {
-        fn ordering() -> Ordering {
-            Ordering::Greater
+    impl<K, S> KeyHasher<K> for CoreBuildHasher<S>
+    where
+        K: ?Sized + core::hash::Hash,
+        S: core::hash::BuildHasher,
+    {
+        fn hash_key(&self, key: &K) -> u16 {
+            let mut hasher = self.build_hasher.build_hasher();
+            key.hash(&mut hasher);
+            let hash = core::hash::Hasher::finish(&hasher);
+            // fold all the bits of the hash into the 16 bits that the map uses
+            (hash ^ hash >> 16 ^ hash >> 32 ^ hash >> 48) as u16
         }
     }
 }
@@ -34,6 +104,13 @@ pub(crate) const fn greater_than_1<const N: usize>() {
     Assert::<N, 1>::GREATER;
 }
 
+#[allow(dead_code)]
+#[allow(path_statements)]
+pub(crate) const fn power_of_two<const N: usize>() {
+    Assert::<N, 0>::GREATER;
+    Assert::<N, 0>::POWER_OF_TWO;
+}
+
 #[allow(dead_code)]
 /// Const assert hack
 pub struct Assert<const L: usize, const R: usize>;
@@ -57,4 +134,7 @@ impl<const L: usize, const R: usize> Assert<L, R> {
 
     /// Const assert hack
     pub const LESS: usize = R - L - 1;
+
+    /// Const assert hack
+    pub const POWER_OF_TWO: usize = 0 - (L & (L - 1));
 }
@@ -1,16 +1,18 @@
 use crate::{
-    sealed::binary_heap::Kind as BinaryHeapKind, BinaryHeap, IndexMap, IndexSet, LinearMap, String,
-    Vec,
+    binary_heap::Compare,
+    histbuf::{Archive, Consolidation, Sample},
+    sealed::{histbuf::Cascade, indexmap::KeyHasher},
+    BinaryHeap, Deque, DequeView, HistoryBuffer, IndexMap, IndexSet, LinearMap, SortedLinearMap,
+    String, StringView, Vec, VecView,
 };
-use hash32::{BuildHasher, Hash};
-use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, SerializeStruct, Serializer};
 
 // Sequential containers
 
 impl<T, KIND, const N: usize> Serialize for BinaryHeap<T, KIND, N>
 where
-    T: Ord + Serialize,
-    KIND: BinaryHeapKind,
+    T: Serialize,
+    KIND: Compare<T>,
 {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -26,8 +28,8 @@ where
 
 impl<T, S, const N: usize> Serialize for IndexSet<T, S, N>
 where
-    T: Eq + Hash + Serialize,
-    S: BuildHasher,
+    T: Eq + Serialize,
+    S: KeyHasher<T>,
 {
     fn serialize<SER>(&self, serializer: SER) -> Result<SER::Ok, SER::Error>
     where
@@ -57,12 +59,119 @@ where
     }
 }
 
+impl<T, const N: usize> Serialize for Deque<T, N>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for element in self {
+            seq.serialize_element(element)?;
+        }
+        seq.end()
+    }
+}
+
+impl<T> Serialize for VecView<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for element in self {
+            seq.serialize_element(element)?;
+        }
+        seq.end()
+    }
+}
+
+impl<T> Serialize for DequeView<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for element in self {
+            seq.serialize_element(element)?;
+        }
+        seq.end()
+    }
+}
+
+impl<T, const N: usize> Serialize for HistoryBuffer<T, N>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for element in self.oldest_ordered() {
+            seq.serialize_element(element)?;
+        }
+        seq.end()
+    }
+}
+
+// Archives
+
+impl Serialize for Consolidation {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let (index, variant) = match self {
+            Consolidation::Average => (0, "Average"),
+            Consolidation::Min => (1, "Min"),
+            Consolidation::Max => (2, "Max"),
+            Consolidation::Last => (3, "Last"),
+        };
+        serializer.serialize_unit_variant("Consolidation", index, variant)
+    }
+}
+
+impl<T, Next, const N: usize> Serialize for Archive<T, Next, N>
+where
+    T: Sample + Serialize,
+    Next: Cascade<T> + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // the last tier doesn't consolidate into anything
+        if Next::TIERS == 0 {
+            let mut archive = serializer.serialize_struct("Archive", 1)?;
+            archive.serialize_field("samples", self.history())?;
+            return archive.end();
+        }
+
+        let mut archive = serializer.serialize_struct("Archive", 5)?;
+        archive.serialize_field("samples", self.history())?;
+        archive.serialize_field("consolidation", &self.consolidation())?;
+        archive.serialize_field("steps", &self.steps())?;
+        archive.serialize_field("pending", &self.pending())?;
+        archive.serialize_field("next", self.coarser())?;
+        archive.end()
+    }
+}
+
 // Dictionaries
 
 impl<K, V, S, const N: usize> Serialize for IndexMap<K, V, S, N>
 where
-    K: Eq + Hash + Serialize,
-    S: BuildHasher,
+    K: Eq + Serialize,
+    S: KeyHasher<K>,
     V: Serialize,
 {
     fn serialize<SER>(&self, serializer: SER) -> Result<SER::Ok, SER::Error>
@@ -94,6 +203,23 @@ where
     }
 }
 
+impl<K, V, const N: usize> Serialize for SortedLinearMap<K, V, N>
+where
+    K: Serialize,
+    V: Serialize,
+{
+    fn serialize<SER>(&self, serializer: SER) -> Result<SER::Ok, SER::Error>
+    where
+        SER: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (k, v) in self {
+            map.serialize_entry(k, v)?;
+        }
+        map.end()
+    }
+}
+
 // String containers
 
 impl<const N: usize> Serialize for String<N> {
@@ -104,3 +230,12 @@ impl<const N: usize> Serialize for String<N> {
         serializer.serialize_str(&*self)
     }
 }
+
+impl Serialize for StringView {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
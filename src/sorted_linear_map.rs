@@ -0,0 +1,853 @@
+//! A fixed capacity map that keeps its entries sorted by key
+//!
+//! See [`SortedLinearMap`] for more details.
+
+use crate::{vec, CapacityError, TryFromIterator, Vec};
+use core::{
+    borrow::Borrow,
+    cmp::Ordering,
+    fmt,
+    iter::{FromIterator, FusedIterator},
+    mem,
+    ops::{self, Bound, RangeBounds},
+    slice,
+};
+
+/// A fixed capacity map / dictionary that keeps its entries sorted by key and performs lookups
+/// via binary search
+///
+/// The entries are stored in a `Vec<(K, V), N>`, so lookups are **O(log N)** but insertions and
+/// removals shift the entries after them, which is **O(N)**. This suits tables that are mostly
+/// read, like configuration tables of a few hundred entries.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::SortedLinearMap;
+///
+/// // baud rate -> clock divider
+/// let mut dividers: SortedLinearMap<u32, u16, 8> = SortedLinearMap::new();
+/// dividers.insert(115_200, 8).unwrap();
+/// dividers.insert(9_600, 104).unwrap();
+/// dividers.insert(57_600, 17).unwrap();
+///
+/// assert_eq!(dividers.get(&9_600), Some(&104));
+/// assert!(dividers.keys().eq(&[9_600, 57_600, 115_200]));
+/// assert_eq!(dividers.first_key_value(), Some((&9_600, &104)));
+///
+/// // the closest rate that is not faster than 100_000
+/// assert_eq!(dividers.upper_bound(&100_000).peek_prev(), Some((&57_600, &17)));
+/// ```
+pub struct SortedLinearMap<K, V, const N: usize> {
+    pub(crate) buffer: Vec<(K, V), N>,
+}
+
+impl<K, V, const N: usize> SortedLinearMap<K, V, N> {
+    /// Creates an empty `SortedLinearMap`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::SortedLinearMap;
+    ///
+    /// // allocate the map on the stack
+    /// let mut map: SortedLinearMap<&str, isize, 8> = SortedLinearMap::new();
+    ///
+    /// // allocate the map in a static variable
+    /// static mut MAP: SortedLinearMap<&str, isize, 8> = SortedLinearMap::new();
+    /// ```
+    pub const fn new() -> Self {
+        Self { buffer: Vec::new() }
+    }
+
+    /// Returns the number of elements that the map can hold
+    ///
+    /// Computes in **O(1)** time
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Clears the map, removing all key-value pairs
+    pub fn clear(&mut self) {
+        self.buffer.clear()
+    }
+
+    /// Returns the number of elements in this map
+    ///
+    /// Computes in **O(1)** time
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns true if the map contains no elements
+    ///
+    /// Computes in **O(1)** time
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// An iterator visiting all key-value pairs in ascending key order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::SortedLinearMap;
+    ///
+    /// let mut map: SortedLinearMap<_, _, 8> = SortedLinearMap::new();
+    /// map.insert("b", 2).unwrap();
+    /// map.insert("a", 1).unwrap();
+    /// map.insert("c", 3).unwrap();
+    ///
+    /// assert!(map.iter().eq([(&"a", &1), (&"b", &2), (&"c", &3)]));
+    /// ```
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            iter: self.buffer.as_slice().iter(),
+        }
+    }
+
+    /// An iterator visiting all key-value pairs in ascending key order, with mutable references to
+    /// the values
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            iter: self.buffer.as_mut_slice().iter_mut(),
+        }
+    }
+
+    /// An iterator visiting all keys in ascending order
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> + ExactSizeIterator {
+        self.iter().map(|(k, _)| k)
+    }
+
+    /// An iterator visiting all values in ascending key order
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> + ExactSizeIterator {
+        self.iter().map(|(_, v)| v)
+    }
+
+    /// An iterator visiting all values mutably in ascending key order
+    pub fn values_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut V> + ExactSizeIterator {
+        self.iter_mut().map(|(_, v)| v)
+    }
+
+    /// Returns the entry with the smallest key, or `None` if the map is empty
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.buffer.first().map(|(k, v)| (k, v))
+    }
+
+    /// Returns the entry with the largest key, or `None` if the map is empty
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.buffer.last().map(|(k, v)| (k, v))
+    }
+
+    /// Removes the entry with the smallest key and returns it, or `None` if the map is empty
+    ///
+    /// Computes in **O(N)** time
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.buffer.remove(0))
+        }
+    }
+
+    /// Removes the entry with the largest key and returns it, or `None` if the map is empty
+    ///
+    /// Computes in **O(1)** time
+    pub fn pop_last(&mut self) -> Option<(K, V)> {
+        self.buffer.pop()
+    }
+}
+
+impl<K, V, const N: usize> SortedLinearMap<K, V, N>
+where
+    K: Ord,
+{
+    // position of `key`, or where it would be inserted
+    fn search<Q>(&self, key: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.buffer.binary_search_by(|(k, _)| k.borrow().cmp(key))
+    }
+
+    // number of entries whose key is before `bound`
+    fn partition<Q>(&self, bound: Bound<&Q>, start: bool) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        // never returns `Equal`, so that the binary search ends where the bound splits the keys
+        let cmp = |k: &K, key: &Q, included: bool| match k.borrow().cmp(key) {
+            Ordering::Equal if included != start => Ordering::Less,
+            Ordering::Equal => Ordering::Greater,
+            ordering => ordering,
+        };
+        let search = |key: &Q, included: bool| {
+            self.buffer
+                .binary_search_by(|(k, _)| cmp(k, key, included))
+                .unwrap_or_else(|i| i)
+        };
+
+        match bound {
+            Bound::Included(key) => search(key, true),
+            Bound::Excluded(key) => search(key, false),
+            Bound::Unbounded if start => 0,
+            Bound::Unbounded => self.len(),
+        }
+    }
+
+    /// Returns true if the map contains a value for the specified key.
+    ///
+    /// Computes in **O(log N)** time
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::SortedLinearMap;
+    ///
+    /// let mut map: SortedLinearMap<&str, _, 8> = SortedLinearMap::new();
+    /// map.insert("a", 1).unwrap();
+    /// assert!(map.contains_key("a"));
+    /// assert!(!map.contains_key("b"));
+    /// ```
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.search(key).is_ok()
+    }
+
+    /// Returns a reference to the value corresponding to the key
+    ///
+    /// Computes in **O(log N)** time
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::SortedLinearMap;
+    ///
+    /// let mut map: SortedLinearMap<_, _, 8> = SortedLinearMap::new();
+    /// map.insert(1, "a").unwrap();
+    /// assert_eq!(map.get(&1), Some(&"a"));
+    /// assert_eq!(map.get(&2), None);
+    /// ```
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get_key_value(key).map(|(_, v)| v)
+    }
+
+    /// Returns the key-value pair corresponding to the key
+    ///
+    /// Computes in **O(log N)** time
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (k, v) = &self.buffer[self.search(key).ok()?];
+        Some((k, v))
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key
+    ///
+    /// Computes in **O(log N)** time
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let i = self.search(key).ok()?;
+        Some(&mut self.buffer[i].1)
+    }
+
+    /// Inserts a key-value pair into the map, keeping the entries sorted.
+    ///
+    /// If the map did not have this key present, `None` is returned.
+    ///
+    /// If the map did have this key present, the value is updated, and the old value is returned.
+    ///
+    /// Returns back the key-value pair if the map is full and doesn't have this key.
+    ///
+    /// Computes in **O(N)** time
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::SortedLinearMap;
+    ///
+    /// let mut map: SortedLinearMap<_, _, 2> = SortedLinearMap::new();
+    /// assert_eq!(map.insert(37, "a"), Ok(None));
+    /// assert_eq!(map.insert(37, "b"), Ok(Some("a")));
+    /// assert_eq!(map.insert(1, "c"), Ok(None));
+    /// assert_eq!(map.insert(2, "d"), Err((2, "d")));
+    /// ```
+    pub fn insert(&mut self, key: K, mut value: V) -> Result<Option<V>, (K, V)> {
+        match self.search(&key) {
+            Ok(i) => {
+                mem::swap(&mut self.buffer[i].1, &mut value);
+                Ok(Some(value))
+            }
+            Err(i) => {
+                self.buffer.insert(i, (key, value))?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously in the
+    /// map
+    ///
+    /// Computes in **O(N)** time
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::SortedLinearMap;
+    ///
+    /// let mut map: SortedLinearMap<_, _, 8> = SortedLinearMap::new();
+    /// map.insert(1, "a").unwrap();
+    /// assert_eq!(map.remove(&1), Some("a"));
+    /// assert_eq!(map.remove(&1), None);
+    /// ```
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, v)| v)
+    }
+
+    /// Removes a key from the map, returning the stored key and value if the key was previously in
+    /// the map
+    ///
+    /// Computes in **O(N)** time
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let i = self.search(key).ok()?;
+        Some(self.buffer.remove(i))
+    }
+
+    /// An iterator over the entries whose keys are within `range`, in ascending key order
+    ///
+    /// The iterator is empty if the start of the range is after its end.
+    ///
+    /// Computes in **O(log N)** time
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::SortedLinearMap;
+    ///
+    /// let map: SortedLinearMap<_, _, 8> = [(1, 'a'), (3, 'b'), (5, 'c'), (7, 'd')]
+    ///     .iter()
+    ///     .copied()
+    ///     .collect();
+    ///
+    /// assert!(map.range(2..7).eq([(&3, &'b'), (&5, &'c')]));
+    /// assert!(map.range(5..).map(|(k, _)| *k).eq([5, 7]));
+    /// ```
+    pub fn range<Q, R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let (start, end) = self.range_bounds(range);
+        Iter {
+            iter: self.buffer[start..end].iter(),
+        }
+    }
+
+    /// An iterator over the entries whose keys are within `range`, in ascending key order, with
+    /// mutable references to the values
+    pub fn range_mut<Q, R>(&mut self, range: R) -> IterMut<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let (start, end) = self.range_bounds(range);
+        IterMut {
+            iter: self.buffer[start..end].iter_mut(),
+        }
+    }
+
+    fn range_bounds<Q, R>(&self, range: R) -> (usize, usize)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let start = self.partition(range.start_bound(), true);
+        let end = self.partition(range.end_bound(), false);
+        (start, end.max(start))
+    }
+
+    /// Returns a cursor placed before the first entry whose key is not less than `key`
+    ///
+    /// [`Cursor::peek_next`] then returns that entry, and [`Cursor::peek_prev`] the last entry
+    /// whose key is less than `key`.
+    ///
+    /// Computes in **O(log N)** time
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::SortedLinearMap;
+    ///
+    /// let map: SortedLinearMap<_, _, 8> = [(10, 'a'), (20, 'b'), (30, 'c')].iter().copied().collect();
+    ///
+    /// let mut cursor = map.lower_bound(&20);
+    /// assert_eq!(cursor.peek_prev(), Some((&10, &'a')));
+    /// assert_eq!(cursor.peek_next(), Some((&20, &'b')));
+    ///
+    /// assert_eq!(cursor.next(), Some((&20, &'b')));
+    /// assert_eq!(cursor.next(), Some((&30, &'c')));
+    /// assert_eq!(cursor.next(), None);
+    /// assert_eq!(cursor.prev(), Some((&30, &'c')));
+    /// ```
+    pub fn lower_bound<Q>(&self, key: &Q) -> Cursor<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        Cursor {
+            entries: self.buffer.as_slice(),
+            index: self.partition(Bound::Included(key), true),
+        }
+    }
+
+    /// Returns a cursor placed before the first entry whose key is greater than `key`
+    ///
+    /// [`Cursor::peek_next`] then returns that entry, and [`Cursor::peek_prev`] the last entry
+    /// whose key is not greater than `key`.
+    ///
+    /// Computes in **O(log N)** time
+    pub fn upper_bound<Q>(&self, key: &Q) -> Cursor<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        Cursor {
+            entries: self.buffer.as_slice(),
+            index: self.partition(Bound::Excluded(key), true),
+        }
+    }
+}
+
+/// A cursor over the entries of a [`SortedLinearMap`]
+///
+/// A cursor sits between two entries, or before the first one, or after the last one. It is an
+/// iterator that moves forward from its position, and it can move backward as well with
+/// [`prev`](Self::prev).
+///
+/// This `struct` is created by [`SortedLinearMap::lower_bound`] and
+/// [`SortedLinearMap::upper_bound`]. See their documentation for more.
+pub struct Cursor<'a, K, V> {
+    entries: &'a [(K, V)],
+    // index of the entry after the cursor
+    index: usize,
+}
+
+impl<'a, K, V> Cursor<'a, K, V> {
+    /// Returns the number of entries before the cursor
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the entry after the cursor, or `None` if the cursor is after the last entry
+    pub fn peek_next(&self) -> Option<(&'a K, &'a V)> {
+        self.entries.get(self.index).map(|(k, v)| (k, v))
+    }
+
+    /// Returns the entry before the cursor, or `None` if the cursor is before the first entry
+    pub fn peek_prev(&self) -> Option<(&'a K, &'a V)> {
+        let (k, v) = self.entries.get(self.index.checked_sub(1)?)?;
+        Some((k, v))
+    }
+
+    /// Moves the cursor back over the entry before it and returns that entry, or `None` if the
+    /// cursor is before the first entry
+    pub fn prev(&mut self) -> Option<(&'a K, &'a V)> {
+        let entry = self.peek_prev()?;
+        self.index -= 1;
+        Some(entry)
+    }
+}
+
+impl<'a, K, V> Iterator for Cursor<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.peek_next()?;
+        self.index += 1;
+        Some(entry)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.entries.len() - self.index;
+        (len, Some(len))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Cursor<'_, K, V> {}
+
+impl<K, V> FusedIterator for Cursor<'_, K, V> {}
+
+impl<K, V> Clone for Cursor<'_, K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            entries: self.entries,
+            index: self.index,
+        }
+    }
+}
+
+impl<K, V, Q, const N: usize> ops::Index<&Q> for SortedLinearMap<K, V, N>
+where
+    K: Borrow<Q> + Ord,
+    Q: Ord + ?Sized,
+{
+    type Output = V;
+
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+impl<K, V, Q, const N: usize> ops::IndexMut<&Q> for SortedLinearMap<K, V, N>
+where
+    K: Borrow<Q> + Ord,
+    Q: Ord + ?Sized,
+{
+    fn index_mut(&mut self, key: &Q) -> &mut V {
+        self.get_mut(key).expect("no entry found for key")
+    }
+}
+
+impl<K, V, const N: usize> Default for SortedLinearMap<K, V, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, const N: usize> Clone for SortedLinearMap<K, V, N>
+where
+    K: Clone,
+    V: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            buffer: self.buffer.clone(),
+        }
+    }
+}
+
+impl<K, V, const N: usize> fmt::Debug for SortedLinearMap<K, V, N>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V, const N: usize> FromIterator<(K, V)> for SortedLinearMap<K, V, N>
+where
+    K: Ord,
+{
+    /// Collects the entries into a map, keeping the last value of duplicate keys
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than `N` distinct keys.
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        match Self::try_from_iter(iter) {
+            Ok(map) => map,
+            Err(_) => panic!("SortedLinearMap::from_iter overflowed capacity"),
+        }
+    }
+}
+
+impl<K, V, const N: usize> TryFromIterator<(K, V)> for SortedLinearMap<K, V, N>
+where
+    K: Ord,
+{
+    fn try_from_iter<I>(iter: I) -> Result<Self, CapacityError<(K, V)>>
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut out = Self::new();
+        for (k, v) in iter {
+            out.insert(k, v).map_err(CapacityError::new)?;
+        }
+        Ok(out)
+    }
+}
+
+impl<K, V, const N: usize> IntoIterator for SortedLinearMap<K, V, N> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            inner: self.buffer.into_iter(),
+        }
+    }
+}
+
+impl<'a, K, V, const N: usize> IntoIterator for &'a SortedLinearMap<K, V, N> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, const N: usize> IntoIterator for &'a mut SortedLinearMap<K, V, N> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K, V, const N: usize, const N2: usize> PartialEq<SortedLinearMap<K, V, N2>>
+    for SortedLinearMap<K, V, N>
+where
+    K: PartialEq,
+    V: PartialEq,
+{
+    fn eq(&self, other: &SortedLinearMap<K, V, N2>) -> bool {
+        // both maps are sorted
+        self.buffer.as_slice() == other.buffer.as_slice()
+    }
+}
+
+impl<K, V, const N: usize> Eq for SortedLinearMap<K, V, N>
+where
+    K: Eq,
+    V: Eq,
+{
+}
+
+/// An owning iterator over the entries of a [`SortedLinearMap`], in ascending key order
+pub struct IntoIter<K, V, const N: usize> {
+    inner: vec::IntoIter<(K, V), N>,
+}
+
+impl<K, V, const N: usize> Iterator for IntoIter<K, V, N> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V, const N: usize> ExactSizeIterator for IntoIter<K, V, N> {}
+
+/// An iterator over the entries of a [`SortedLinearMap`], in ascending key order
+pub struct Iter<'a, K, V> {
+    iter: slice::Iter<'a, (K, V)>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(k, v)| (k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Iter<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(k, v)| (k, v))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+impl<K, V> Clone for Iter<'_, K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+/// A mutable iterator over the entries of a [`SortedLinearMap`], in ascending key order
+pub struct IterMut<'a, K, V> {
+    iter: slice::IterMut<'a, (K, V)>,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(k, v)| (&*k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for IterMut<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(k, v)| (&*k, v))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IterMut<'_, K, V> {}
+
+impl<K, V> FusedIterator for IterMut<'_, K, V> {}
+
+#[cfg(test)]
+mod tests {
+    use core::ops::Bound;
+    use std::vec::Vec;
+
+    use super::SortedLinearMap;
+    use crate::TryFromIterator;
+
+    #[test]
+    fn static_new() {
+        static mut _L: SortedLinearMap<i32, i32, 8> = SortedLinearMap::new();
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut map = SortedLinearMap::<u8, u8, 16>::new();
+        for i in 0..16 {
+            assert_eq!(map.insert((i * 7) % 16, i), Ok(None));
+        }
+        assert_eq!(map.insert(16, 0), Err((16, 0)));
+        assert_eq!(map.insert(7, 100), Ok(Some(1)));
+
+        assert!(map.keys().copied().eq(0..16));
+        assert!(map.keys().rev().copied().eq((0..16).rev()));
+
+        for key in (0..16).step_by(3) {
+            assert!(map.remove(&key).is_some());
+            assert!(!map.contains_key(&key));
+        }
+        assert!(map.keys().all(|key| key % 3 != 0));
+        assert_eq!(map.len(), 10);
+
+        assert_eq!(map.pop_first(), Some((1, 7)));
+        assert_eq!(map.pop_last(), Some((14, 2)));
+        assert_eq!(map.first_key_value(), Some((&2, &14)));
+        assert_eq!(map.last_key_value(), Some((&13, &11)));
+    }
+
+    #[test]
+    fn range() {
+        let map: SortedLinearMap<_, _, 8> = (0..8).map(|i| (i * 10, i)).collect();
+        let keys = |range: (Bound<&i32>, Bound<&i32>)| -> Vec<i32> {
+            map.range::<i32, _>(range).map(|(k, _)| *k).collect()
+        };
+
+        assert_eq!(
+            keys((Bound::Included(&20), Bound::Excluded(&50))),
+            [20, 30, 40]
+        );
+        assert_eq!(
+            keys((Bound::Excluded(&20), Bound::Included(&50))),
+            [30, 40, 50]
+        );
+        assert_eq!(keys((Bound::Included(&15), Bound::Included(&25))), [20]);
+        assert_eq!(keys((Bound::Unbounded, Bound::Excluded(&10))), [0]);
+        assert_eq!(keys((Bound::Excluded(&60), Bound::Unbounded)), [70]);
+        assert!(keys((Bound::Included(&50), Bound::Excluded(&20))).is_empty());
+        assert!(keys((Bound::Excluded(&30), Bound::Excluded(&30))).is_empty());
+        assert_eq!(map.range(..).len(), 8);
+    }
+
+    #[test]
+    fn range_mut() {
+        let mut map: SortedLinearMap<_, _, 8> = (0..8).map(|i| (i, i)).collect();
+        for (_, v) in map.range_mut(2..=4) {
+            *v *= 10;
+        }
+        assert!(map.values().copied().eq([0, 1, 20, 30, 40, 5, 6, 7]));
+    }
+
+    #[test]
+    fn bounds() {
+        let map: SortedLinearMap<_, _, 8> = [(10, 'a'), (20, 'b'), (20, 'c'), (30, 'd')]
+            .iter()
+            .copied()
+            .collect();
+        assert_eq!(map.get(&20), Some(&'c'));
+
+        let cursor = map.lower_bound(&20);
+        assert_eq!(cursor.index(), 1);
+        assert_eq!(cursor.peek_next(), Some((&20, &'c')));
+
+        let mut cursor = map.upper_bound(&20);
+        assert_eq!(cursor.index(), 2);
+        assert_eq!(cursor.peek_prev(), Some((&20, &'c')));
+        assert_eq!(cursor.len(), 1);
+        assert_eq!(cursor.prev(), Some((&20, &'c')));
+        assert_eq!(cursor.prev(), Some((&10, &'a')));
+        assert_eq!(cursor.prev(), None);
+        assert_eq!(cursor.index(), 0);
+
+        assert_eq!(map.lower_bound(&0).peek_prev(), None);
+        assert_eq!(map.upper_bound(&30).peek_next(), None);
+        assert_eq!(map.lower_bound(&31).index(), 3);
+    }
+
+    #[test]
+    fn borrow() {
+        let mut map = SortedLinearMap::<std::string::String, u8, 4>::new();
+        map.insert("b".into(), 2).unwrap();
+        map.insert("a".into(), 1).unwrap();
+
+        assert!(map.contains_key("a"));
+        assert_eq!(map["b"], 2);
+        map["b"] += 1;
+        assert_eq!(map.remove("b"), Some(3));
+        let range = (Bound::Included("a"), Bound::Excluded("b"));
+        assert_eq!(map.range::<str, _>(range).count(), 1);
+    }
+
+    #[test]
+    fn try_from_iter() {
+        let err =
+            SortedLinearMap::<_, _, 2>::try_from_iter([(3, 'c'), (1, 'a'), (2, 'b')]).unwrap_err();
+        assert_eq!(err.into_inner(), (2, 'b'));
+    }
+
+    #[test]
+    fn partial_eq() {
+        let a: SortedLinearMap<_, _, 4> = [(1, 'a'), (2, 'b')].iter().copied().collect();
+        let b: SortedLinearMap<_, _, 8> = [(2, 'b'), (1, 'a')].iter().copied().collect();
+        assert!(a == b);
+        assert_eq!(std::format!("{:?}", a), "{1: 'a', 2: 'b'}");
+    }
+}
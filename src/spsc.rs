@@ -92,11 +92,16 @@ use core::{
     sync::atomic::{AtomicUsize, Ordering},
 };
 
+use crate::{CapacityError, TryFromIterator};
+
 /// A statically allocated single producer single consumer queue with a capacity of `N - 1` elements
 ///
 /// *IMPORTANT*: To get better performance use a capacity that is a power of 2 (e.g. `16`, `32`,
 /// etc.).
+#[repr(C)]
 pub struct Queue<T, const N: usize> {
+    // NOTE the layout must stay in sync with `QueueView`
+
     // this is from where we dequeue items
     pub(crate) head: AtomicUsize,
 
@@ -106,14 +111,40 @@ pub struct Queue<T, const N: usize> {
     pub(crate) buffer: [UnsafeCell<MaybeUninit<T>>; N],
 }
 
+/// A [`Queue`] with a capacity that is not part of its type
+///
+/// `QueueView<T>` is to `Queue<T, N>` what `[T]` is to `[T; N]`: it's `!Sized` so it can only be
+/// used behind a reference, which is obtained with [`Queue::as_view`] or [`Queue::as_mut_view`].
+///
+/// # Examples
+///
+/// ```
+/// use heapless::spsc::{Queue, QueueView};
+///
+/// fn drain_all(queue: &mut QueueView<u8>) -> usize {
+///     let mut n = 0;
+///     while queue.dequeue().is_some() {
+///         n += 1;
+///     }
+///     n
+/// }
+///
+/// let mut queue: Queue<u8, 4> = Queue::new();
+/// queue.enqueue(1).unwrap();
+/// queue.enqueue(2).unwrap();
+/// assert_eq!(drain_all(queue.as_mut_view()), 2);
+/// assert!(queue.is_empty());
+/// ```
+#[repr(C)]
+pub struct QueueView<T> {
+    head: AtomicUsize,
+    tail: AtomicUsize,
+    buffer: [UnsafeCell<MaybeUninit<T>>],
+}
+
 impl<T, const N: usize> Queue<T, N> {
     const INIT: UnsafeCell<MaybeUninit<T>> = UnsafeCell::new(MaybeUninit::uninit());
 
-    #[inline]
-    fn increment(val: usize) -> usize {
-        (val + 1) % N
-    }
-
     /// Creates an empty queue with a fixed capacity of `N - 1`
     pub const fn new() -> Self {
         // Const assert N > 1
@@ -132,25 +163,47 @@ impl<T, const N: usize> Queue<T, N> {
         N - 1
     }
 
+    /// Returns a view of this queue that doesn't carry its capacity in its type
+    #[inline]
+    pub fn as_view(&self) -> &QueueView<T> {
+        // SAFETY: `Queue` and `QueueView` are `#[repr(C)]` with the same fields, in the same
+        // order, and the `N` slots of the buffer become the length of the trailing slice
+        unsafe {
+            &*(ptr::slice_from_raw_parts(
+                self as *const Self as *const UnsafeCell<MaybeUninit<T>>,
+                N,
+            ) as *const QueueView<T>)
+        }
+    }
+
+    /// Returns a mutable view of this queue that doesn't carry its capacity in its type
+    #[inline]
+    pub fn as_mut_view(&mut self) -> &mut QueueView<T> {
+        // SAFETY: see `as_view`
+        unsafe {
+            &mut *(ptr::slice_from_raw_parts_mut(
+                self as *mut Self as *mut UnsafeCell<MaybeUninit<T>>,
+                N,
+            ) as *mut QueueView<T>)
+        }
+    }
+
     /// Returns the number of elements in the queue
     #[inline]
     pub fn len(&self) -> usize {
-        let current_head = self.head.load(Ordering::Relaxed);
-        let current_tail = self.tail.load(Ordering::Relaxed);
-
-        current_tail.wrapping_sub(current_head).wrapping_add(N) % N
+        self.as_view().inner_len(Const::<N>)
     }
 
     /// Returns `true` if the queue is empty
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.head.load(Ordering::Relaxed) == self.tail.load(Ordering::Relaxed)
+        self.as_view().is_empty()
     }
 
     /// Returns `true` if the queue is full
     #[inline]
     pub fn is_full(&self) -> bool {
-        Self::increment(self.tail.load(Ordering::Relaxed)) == self.head.load(Ordering::Relaxed)
+        self.as_view().inner_is_full(Const::<N>)
     }
 
     /// Iterates from the front of the queue to the back
@@ -177,13 +230,13 @@ impl<T, const N: usize> Queue<T, N> {
     /// Returns back the `item` if the queue is full
     #[inline]
     pub fn enqueue(&mut self, val: T) -> Result<(), T> {
-        unsafe { self.inner_enqueue(val) }
+        unsafe { self.as_view().inner_enqueue(val, Const::<N>) }
     }
 
     /// Returns the item in the front of the queue, or `None` if the queue is empty
     #[inline]
     pub fn dequeue(&mut self) -> Option<T> {
-        unsafe { self.inner_dequeue() }
+        unsafe { self.as_view().inner_dequeue(Const::<N>) }
     }
 
     /// Returns a reference to the item in the front of the queue without dequeuing, or
@@ -201,6 +254,124 @@ impl<T, const N: usize> Queue<T, N> {
     /// assert_eq!(Some(1), consumer.dequeue());
     /// assert_eq!(None, consumer.peek());
     /// ```
+    pub fn peek(&self) -> Option<&T> {
+        self.as_view().peek()
+    }
+
+    /// Adds an `item` to the end of the queue, without checking if it's full
+    ///
+    /// # Safety
+    ///
+    /// If the queue is full this operation will leak a value (T's destructor won't run on
+    /// the value that got overwritten by `item`), *and* will allow the `dequeue` operation
+    /// to create a copy of `item`, which could result in `T`'s destructor running on `item`
+    /// twice.
+    pub unsafe fn enqueue_unchecked(&mut self, val: T) {
+        self.as_view().inner_enqueue_unchecked(val, Const::<N>)
+    }
+
+    /// Returns the item in the front of the queue, without checking if there is something in the
+    /// queue
+    ///
+    /// # Safety
+    ///
+    /// If the queue is empty this operation will return uninitialized memory.
+    pub unsafe fn dequeue_unchecked(&mut self) -> T {
+        self.as_view().inner_dequeue_unchecked(Const::<N>)
+    }
+
+    /// Splits a queue into producer and consumer endpoints
+    pub fn split(&mut self) -> (Producer<'_, T, N>, Consumer<'_, T, N>) {
+        (Producer { rb: self }, Consumer { rb: self })
+    }
+}
+
+// The number of slots in the buffer of a queue. `Queue`, `Producer` and `Consumer` know it at
+// compile time, as `Const<N>`, so that their index arithmetic is a mask (for power of 2 capacities)
+// rather than a division; only the `QueueView` entry points read it from the buffer at runtime.
+trait Slots: Copy {
+    fn get(self) -> usize;
+}
+
+#[derive(Clone, Copy)]
+struct Const<const N: usize>;
+
+impl<const N: usize> Slots for Const<N> {
+    #[inline(always)]
+    fn get(self) -> usize {
+        N
+    }
+}
+
+impl Slots for usize {
+    #[inline(always)]
+    fn get(self) -> usize {
+        self
+    }
+}
+
+#[inline(always)]
+fn increment<S: Slots>(val: usize, slots: S) -> usize {
+    (val + 1) % slots.get()
+}
+
+impl<T> QueueView<T> {
+    /// Returns the maximum number of elements the queue can hold
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buffer.len() - 1
+    }
+
+    /// Returns the number of elements in the queue
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner_len(self.buffer.len())
+    }
+
+    #[inline]
+    fn inner_len<S: Slots>(&self, slots: S) -> usize {
+        let current_head = self.head.load(Ordering::Relaxed);
+        let current_tail = self.tail.load(Ordering::Relaxed);
+
+        current_tail
+            .wrapping_sub(current_head)
+            .wrapping_add(slots.get())
+            % slots.get()
+    }
+
+    /// Returns `true` if the queue is empty
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.head.load(Ordering::Relaxed) == self.tail.load(Ordering::Relaxed)
+    }
+
+    /// Returns `true` if the queue is full
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.inner_is_full(self.buffer.len())
+    }
+
+    #[inline]
+    fn inner_is_full<S: Slots>(&self, slots: S) -> bool {
+        increment(self.tail.load(Ordering::Relaxed), slots) == self.head.load(Ordering::Relaxed)
+    }
+
+    /// Adds an `item` to the end of the queue
+    ///
+    /// Returns back the `item` if the queue is full
+    #[inline]
+    pub fn enqueue(&mut self, val: T) -> Result<(), T> {
+        unsafe { self.inner_enqueue(val, self.buffer.len()) }
+    }
+
+    /// Returns the item in the front of the queue, or `None` if the queue is empty
+    #[inline]
+    pub fn dequeue(&mut self) -> Option<T> {
+        unsafe { self.inner_dequeue(self.buffer.len()) }
+    }
+
+    /// Returns a reference to the item in the front of the queue without dequeuing, or
+    /// `None` if the queue is empty.
     pub fn peek(&self) -> Option<&T> {
         if !self.is_empty() {
             let head = self.head.load(Ordering::Relaxed);
@@ -213,9 +384,10 @@ impl<T, const N: usize> Queue<T, N> {
     // The memory for enqueueing is "owned" by the tail pointer.
     // NOTE: This internal function uses internal mutability to allow the [`Producer`] to enqueue
     // items without doing pointer arithmetic and accessing internal fields of this type.
-    unsafe fn inner_enqueue(&self, val: T) -> Result<(), T> {
+    #[inline]
+    unsafe fn inner_enqueue<S: Slots>(&self, val: T, slots: S) -> Result<(), T> {
         let current_tail = self.tail.load(Ordering::Relaxed);
-        let next_tail = Self::increment(current_tail);
+        let next_tail = increment(current_tail, slots);
 
         if next_tail != self.head.load(Ordering::Acquire) {
             (self.buffer.get_unchecked(current_tail).get()).write(MaybeUninit::new(val));
@@ -230,12 +402,13 @@ impl<T, const N: usize> Queue<T, N> {
     // The memory for enqueueing is "owned" by the tail pointer.
     // NOTE: This internal function uses internal mutability to allow the [`Producer`] to enqueue
     // items without doing pointer arithmetic and accessing internal fields of this type.
-    unsafe fn inner_enqueue_unchecked(&self, val: T) {
+    #[inline]
+    unsafe fn inner_enqueue_unchecked<S: Slots>(&self, val: T, slots: S) {
         let current_tail = self.tail.load(Ordering::Relaxed);
 
         (self.buffer.get_unchecked(current_tail).get()).write(MaybeUninit::new(val));
         self.tail
-            .store(Self::increment(current_tail), Ordering::Release);
+            .store(increment(current_tail, slots), Ordering::Release);
     }
 
     /// Adds an `item` to the end of the queue, without checking if it's full
@@ -247,13 +420,14 @@ impl<T, const N: usize> Queue<T, N> {
     /// to create a copy of `item`, which could result in `T`'s destructor running on `item`
     /// twice.
     pub unsafe fn enqueue_unchecked(&mut self, val: T) {
-        self.inner_enqueue_unchecked(val)
+        self.inner_enqueue_unchecked(val, self.buffer.len())
     }
 
     // The memory for dequeuing is "owned" by the head pointer,.
     // NOTE: This internal function uses internal mutability to allow the [`Consumer`] to dequeue
     // items without doing pointer arithmetic and accessing internal fields of this type.
-    unsafe fn inner_dequeue(&self) -> Option<T> {
+    #[inline]
+    unsafe fn inner_dequeue<S: Slots>(&self, slots: S) -> Option<T> {
         let current_head = self.head.load(Ordering::Relaxed);
 
         if current_head == self.tail.load(Ordering::Acquire) {
@@ -262,7 +436,7 @@ impl<T, const N: usize> Queue<T, N> {
             let v = (self.buffer.get_unchecked(current_head).get() as *const T).read();
 
             self.head
-                .store(Self::increment(current_head), Ordering::Release);
+                .store(increment(current_head, slots), Ordering::Release);
 
             Some(v)
         }
@@ -271,12 +445,13 @@ impl<T, const N: usize> Queue<T, N> {
     // The memory for dequeuing is "owned" by the head pointer,.
     // NOTE: This internal function uses internal mutability to allow the [`Consumer`] to dequeue
     // items without doing pointer arithmetic and accessing internal fields of this type.
-    unsafe fn inner_dequeue_unchecked(&self) -> T {
+    #[inline]
+    unsafe fn inner_dequeue_unchecked<S: Slots>(&self, slots: S) -> T {
         let current_head = self.head.load(Ordering::Relaxed);
         let v = (self.buffer.get_unchecked(current_head).get() as *const T).read();
 
         self.head
-            .store(Self::increment(current_head), Ordering::Release);
+            .store(increment(current_head, slots), Ordering::Release);
 
         v
     }
@@ -288,12 +463,7 @@ impl<T, const N: usize> Queue<T, N> {
     ///
     /// If the queue is empty this operation will return uninitialized memory.
     pub unsafe fn dequeue_unchecked(&mut self) -> T {
-        self.inner_dequeue_unchecked()
-    }
-
-    /// Splits a queue into producer and consumer endpoints
-    pub fn split(&mut self) -> (Producer<'_, T, N>, Consumer<'_, T, N>) {
-        (Producer { rb: self }, Consumer { rb: self })
+        self.inner_dequeue_unchecked(self.buffer.len())
     }
 }
 
@@ -316,6 +486,19 @@ where
     }
 }
 
+impl<T, const N: usize> TryFromIterator<T> for Queue<T, N> {
+    fn try_from_iter<I>(iter: I) -> Result<Self, CapacityError<T>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut queue = Queue::new();
+        for item in iter {
+            queue.enqueue(item).map_err(CapacityError::new)?;
+        }
+        Ok(queue)
+    }
+}
+
 impl<T, const N: usize, const N2: usize> PartialEq<Queue<T, N2>> for Queue<T, N>
 where
     T: PartialEq,
@@ -496,7 +679,7 @@ impl<'a, T, const N: usize> Consumer<'a, T, N> {
     /// Returns the item in the front of the queue, or `None` if the queue is empty
     #[inline]
     pub fn dequeue(&mut self) -> Option<T> {
-        unsafe { self.rb.inner_dequeue() }
+        unsafe { self.rb.as_view().inner_dequeue(Const::<N>) }
     }
 
     /// Returns the item in the front of the queue, without checking if there are elements in the
@@ -505,7 +688,7 @@ impl<'a, T, const N: usize> Consumer<'a, T, N> {
     /// See [`Queue::dequeue_unchecked`] for safety
     #[inline]
     pub unsafe fn dequeue_unchecked(&mut self) -> T {
-        self.rb.inner_dequeue_unchecked()
+        self.rb.as_view().inner_dequeue_unchecked(Const::<N>)
     }
 
     /// Returns if there are any items to dequeue. When this returns `true`, at least the
@@ -552,7 +735,7 @@ impl<'a, T, const N: usize> Producer<'a, T, N> {
     /// Adds an `item` to the end of the queue, returns back the `item` if the queue is full
     #[inline]
     pub fn enqueue(&mut self, val: T) -> Result<(), T> {
-        unsafe { self.rb.inner_enqueue(val) }
+        unsafe { self.rb.as_view().inner_enqueue(val, Const::<N>) }
     }
 
     /// Adds an `item` to the end of the queue, without checking if the queue is full
@@ -560,7 +743,7 @@ impl<'a, T, const N: usize> Producer<'a, T, N> {
     /// See [`Queue::enqueue_unchecked`] for safety
     #[inline]
     pub unsafe fn enqueue_unchecked(&mut self, val: T) {
-        self.rb.inner_enqueue_unchecked(val)
+        self.rb.as_view().inner_enqueue_unchecked(val, Const::<N>)
     }
 
     /// Returns if there is any space to enqueue a new item. When this returns true, at
@@ -585,7 +768,7 @@ impl<'a, T, const N: usize> Producer<'a, T, N> {
 
 #[cfg(test)]
 mod tests {
-    use crate::spsc::Queue;
+    use crate::spsc::{Queue, QueueView};
     use hash32::Hasher;
 
     #[test]
@@ -897,4 +1080,34 @@ mod tests {
         };
         assert_eq!(hash1, hash2);
     }
+
+    #[test]
+    fn view() {
+        fn refill(q: &mut QueueView<u8>) -> usize {
+            let mut n = 0;
+            while q.enqueue(0).is_ok() {
+                n += 1;
+            }
+            n
+        }
+
+        let mut a: Queue<u8, 4> = Queue::new();
+        let mut b: Queue<u8, 8> = Queue::new();
+        a.enqueue(1).unwrap();
+        assert_eq!(refill(a.as_mut_view()), 2);
+        assert_eq!(refill(b.as_mut_view()), 7);
+        assert!(a.is_full());
+        assert!(b.is_full());
+
+        let view = a.as_mut_view();
+        assert_eq!(view.capacity(), 3);
+        assert_eq!(view.len(), 3);
+        assert_eq!(view.peek(), Some(&1));
+        assert_eq!(view.dequeue(), Some(1));
+        assert!(!view.is_full());
+
+        let (mut p, mut c) = a.split();
+        p.enqueue(2).unwrap();
+        assert_eq!(c.dequeue(), Some(0));
+    }
 }
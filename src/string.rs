@@ -1,14 +1,50 @@
-use core::{fmt, fmt::Write, hash, ops, str};
+use core::{
+    fmt,
+    fmt::Write,
+    hash,
+    iter::FusedIterator,
+    ops::{self, RangeBounds},
+    str,
+};
 
 use hash32;
 
-use crate::Vec;
+use crate::{CapacityError, TryFromIterator, Vec, VecView};
 
 /// A fixed capacity [`String`](https://doc.rust-lang.org/std/string/struct.String.html)
 pub struct String<const N: usize> {
     vec: Vec<u8, N>,
 }
 
+/// A [`String`] with a capacity that is not part of its type
+///
+/// `StringView` is to `String<N>` what [`VecView<u8>`](crate::VecView) is to `Vec<u8, N>`: it's
+/// `!Sized` so it can only be used behind a reference, which is obtained with
+/// [`String::as_view`] or [`String::as_mut_view`]. Functions that take a `&mut StringView` work
+/// with strings of any capacity without being generic over `N`.
+///
+/// # Examples
+///
+/// ```
+/// use core::fmt::Write;
+/// use heapless::{String, StringView};
+///
+/// fn greet(out: &mut StringView, name: &str) -> core::fmt::Result {
+///     write!(out, "Hello, {}!", name)
+/// }
+///
+/// let mut s: String<16> = String::new();
+/// greet(s.as_mut_view(), "world").unwrap();
+/// assert_eq!(s, "Hello, world!");
+///
+/// let mut s: String<8> = String::new();
+/// assert!(greet(s.as_mut_view(), "world").is_err());
+/// ```
+#[repr(transparent)]
+pub struct StringView {
+    vec: VecView<u8>,
+}
+
 impl<const N: usize> String<N> {
     /// Constructs a new, empty `String` with a fixed capacity of `N`
     ///
@@ -30,6 +66,130 @@ impl<const N: usize> String<N> {
         Self { vec: Vec::new() }
     }
 
+    /// Returns a view of this string that doesn't carry its capacity in its type
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{String, StringView};
+    ///
+    /// let s: String<8> = String::from("foo");
+    /// let view: &StringView = s.as_view();
+    /// assert_eq!(view, "foo");
+    /// assert_eq!(view.capacity(), 8);
+    /// ```
+    #[inline]
+    pub fn as_view(&self) -> &StringView {
+        // SAFETY: `StringView` is a `#[repr(transparent)]` wrapper around `VecView<u8>`
+        unsafe { &*(self.vec.as_view() as *const VecView<u8> as *const StringView) }
+    }
+
+    /// Returns a mutable view of this string that doesn't carry its capacity in its type
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{String, StringView};
+    ///
+    /// let mut s: String<8> = String::from("foo");
+    /// let view: &mut StringView = s.as_mut_view();
+    /// view.push_str("bar").unwrap();
+    /// assert_eq!(s, "foobar");
+    /// ```
+    #[inline]
+    pub fn as_mut_view(&mut self) -> &mut StringView {
+        // SAFETY: `StringView` is a `#[repr(transparent)]` wrapper around `VecView<u8>`
+        unsafe { &mut *(self.vec.as_mut_view() as *mut VecView<u8> as *mut StringView) }
+    }
+
+    /// Converts a vector of bytes into a `String`.
+    ///
+    /// Returns an error if the bytes are not valid UTF-8. The vector can be recovered from the
+    /// error with [`FromUtf8Error::into_bytes`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{String, Vec};
+    ///
+    /// let bytes: Vec<u8, 8> = Vec::from_slice(&[240, 159, 146, 150]).unwrap();
+    /// let sparkle_heart = String::from_utf8(bytes).unwrap();
+    /// assert_eq!(sparkle_heart, "\u{1f496}");
+    ///
+    /// let bytes: Vec<u8, 8> = Vec::from_slice(&[0, 159, 146, 150]).unwrap();
+    /// let err = String::from_utf8(bytes).unwrap_err();
+    /// assert_eq!(err.utf8_error().valid_up_to(), 1);
+    /// assert_eq!(err.into_bytes(), [0, 159, 146, 150]);
+    /// ```
+    pub fn from_utf8(vec: Vec<u8, N>) -> Result<Self, FromUtf8Error<N>> {
+        match str::from_utf8(&vec) {
+            Ok(_) => Ok(Self { vec }),
+            Err(error) => Err(FromUtf8Error { bytes: vec, error }),
+        }
+    }
+
+    /// Converts a vector of bytes into a `String` without checking that it contains valid UTF-8.
+    ///
+    /// # Safety
+    ///
+    /// The bytes passed in must be valid UTF-8; see [`String::from_utf8`] for the checked
+    /// version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{String, Vec};
+    ///
+    /// let bytes: Vec<u8, 8> = Vec::from_slice(b"abc").unwrap();
+    /// let s = unsafe { String::from_utf8_unchecked(bytes) };
+    /// assert_eq!(s, "abc");
+    /// ```
+    #[inline]
+    pub unsafe fn from_utf8_unchecked(vec: Vec<u8, N>) -> Self {
+        Self { vec }
+    }
+
+    /// Converts a slice of bytes to a `String`, replacing invalid UTF-8 sequences with
+    /// `U+FFFD REPLACEMENT CHARACTER` (\u{fffd}).
+    ///
+    /// Each replacement character takes 3 bytes, so the result can be longer than the input.
+    /// Returns an error if it doesn't fit in `N` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let s: String<16> = String::from_utf8_lossy(b"Hello \xF0\x90\x80World").unwrap();
+    /// assert_eq!(s, "Hello \u{fffd}World");
+    ///
+    /// assert!(String::<4>::from_utf8_lossy(b"\xF0\x90\x80ab").is_err());
+    /// ```
+    pub fn from_utf8_lossy(v: &[u8]) -> Result<Self, CapacityError> {
+        let mut s = Self::new();
+        let mut input = v;
+        loop {
+            match str::from_utf8(input) {
+                Ok(valid) => {
+                    s.push_str(valid)?;
+                    return Ok(s);
+                }
+                Err(error) => {
+                    let (valid, after) = input.split_at(error.valid_up_to());
+                    // SAFETY: `valid_up_to` is the length of the valid UTF-8 prefix
+                    s.push_str(unsafe { str::from_utf8_unchecked(valid) })?;
+                    s.push('\u{fffd}').map_err(CapacityError::simplify)?;
+
+                    match error.error_len() {
+                        Some(len) => input = &after[len..],
+                        // the input ends in the middle of a sequence
+                        None => return Ok(s),
+                    }
+                }
+            }
+        }
+    }
+
     /// Converts a `String` into a byte vector.
     ///
     /// This consumes the `String`, so we do not need to copy its contents.
@@ -69,7 +229,7 @@ impl<const N: usize> String<N> {
     /// ```
     #[inline]
     pub fn as_str(&self) -> &str {
-        unsafe { str::from_utf8_unchecked(self.vec.as_slice()) }
+        self.as_view().as_str()
     }
 
     /// Converts a `String` into a mutable string slice.
@@ -87,7 +247,7 @@ impl<const N: usize> String<N> {
     /// ```
     #[inline]
     pub fn as_mut_str(&mut self) -> &mut str {
-        unsafe { str::from_utf8_unchecked_mut(self.vec.as_mut_slice()) }
+        self.as_mut_view().as_mut_str()
     }
 
     /// Returns a mutable reference to the contents of this `String`.
@@ -136,8 +296,8 @@ impl<const N: usize> String<N> {
     /// assert!(s.push_str("tender").is_err());
     /// ```
     #[inline]
-    pub fn push_str(&mut self, string: &str) -> Result<(), ()> {
-        self.vec.extend_from_slice(string.as_bytes())
+    pub fn push_str(&mut self, string: &str) -> Result<(), CapacityError> {
+        self.as_mut_view().push_str(string)
     }
 
     /// Returns the maximum number of elements the String can hold
@@ -179,13 +339,8 @@ impl<const N: usize> String<N> {
     /// assert_eq!("abc123", s);
     /// ```
     #[inline]
-    pub fn push(&mut self, c: char) -> Result<(), ()> {
-        match c.len_utf8() {
-            1 => self.vec.push(c as u8).map_err(|_| {}),
-            _ => self
-                .vec
-                .extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
-        }
+    pub fn push(&mut self, c: char) -> Result<(), CapacityError<char>> {
+        self.as_mut_view().push(c)
     }
 
     /// Shortens this `String` to the specified length.
@@ -217,10 +372,7 @@ impl<const N: usize> String<N> {
     /// ```
     #[inline]
     pub fn truncate(&mut self, new_len: usize) {
-        if new_len <= self.len() {
-            assert!(self.is_char_boundary(new_len));
-            self.vec.truncate(new_len)
-        }
+        self.as_mut_view().truncate(new_len)
     }
 
     /// Removes the last character from the string buffer and returns it.
@@ -245,16 +397,225 @@ impl<const N: usize> String<N> {
     /// assert_eq!(s.pop(), None);
     /// ```
     pub fn pop(&mut self) -> Option<char> {
-        let ch = self.chars().rev().next()?;
+        self.as_mut_view().pop()
+    }
 
-        // pop bytes that correspond to `ch`
-        for _ in 0..ch.len_utf8() {
-            unsafe {
-                self.vec.pop_unchecked();
-            }
-        }
+    /// Inserts a character into this `String` at a byte position.
+    ///
+    /// This is an *O*(*n*) operation as it requires copying every element in the buffer.
+    ///
+    /// Returns back the character, and leaves the string untouched, if it doesn't fit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is larger than the `String`'s length, or if it does not lie on a [`char`]
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<4> = String::from("ac");
+    /// s.insert(1, 'b').unwrap();
+    /// s.insert(0, '\u{2192}').unwrap_err();
+    ///
+    /// assert_eq!(s, "abc");
+    /// ```
+    pub fn insert(&mut self, idx: usize, ch: char) -> Result<(), CapacityError<char>> {
+        self.as_mut_view().insert(idx, ch)
+    }
 
-        Some(ch)
+    /// Inserts a string slice into this `String` at a byte position.
+    ///
+    /// This is an *O*(*n*) operation as it requires copying every element in the buffer.
+    ///
+    /// Returns an error, and leaves the string untouched, if `string` doesn't fit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is larger than the `String`'s length, or if it does not lie on a [`char`]
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<8> = String::from("bar");
+    /// s.insert_str(0, "foo").unwrap();
+    /// assert_eq!(s, "foobar");
+    ///
+    /// assert!(s.insert_str(3, "baz").is_err());
+    /// assert_eq!(s, "foobar");
+    /// ```
+    pub fn insert_str(&mut self, idx: usize, string: &str) -> Result<(), CapacityError> {
+        self.as_mut_view().insert_str(idx, string)
+    }
+
+    /// Removes a [`char`] from this `String` at a byte position and returns it.
+    ///
+    /// This is an *O*(*n*) operation, as it requires copying every element in the buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is larger than or equal to the `String`'s length, or if it does not lie on
+    /// a [`char`] boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<8> = String::from("foo");
+    ///
+    /// assert_eq!(s.remove(0), 'f');
+    /// assert_eq!(s.remove(1), 'o');
+    /// assert_eq!(s.remove(0), 'o');
+    /// ```
+    pub fn remove(&mut self, idx: usize) -> char {
+        self.as_mut_view().remove(idx)
+    }
+
+    /// Retains only the characters specified by the predicate.
+    ///
+    /// In other words, removes all characters `c` such that `f(c)` returns `false`. This method
+    /// operates in place, visiting each character exactly once in the original order, and
+    /// preserves the order of the retained characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<16> = String::from("f_o_ob_ar");
+    ///
+    /// s.retain(|c| c != '_');
+    ///
+    /// assert_eq!(s, "foobar");
+    /// ```
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(char) -> bool,
+    {
+        self.as_mut_view().retain(f)
+    }
+
+    /// Removes the specified range from the string in bulk, returning all removed characters as
+    /// an iterator.
+    ///
+    /// The range is removed even if the iterator is not consumed until the end.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point or end point do not lie on a [`char`] boundary, or if they're
+    /// out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{String, TryCollect};
+    ///
+    /// let mut s: String<32> = String::from("\u{3b1} is alpha, \u{3b2} is beta");
+    /// let beta_offset = s.find('\u{3b2}').unwrap_or(s.len());
+    ///
+    /// // Remove the range up until the \u{3b2} from the string
+    /// let t: String<32> = s.drain(..beta_offset).try_collect().unwrap();
+    /// assert_eq!(t, "\u{3b1} is alpha, ");
+    /// assert_eq!(s, "\u{3b2} is beta");
+    ///
+    /// // A full range clears the string
+    /// s.drain(..);
+    /// assert_eq!(s, "");
+    /// ```
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.as_mut_view().drain(range)
+    }
+
+    /// Replaces the specified range in the string with the given string slice.
+    ///
+    /// The length of the range can differ from the length of `replace_with`. Returns an error,
+    /// and leaves the string untouched, if the result doesn't fit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point or end point do not lie on a [`char`] boundary, or if they're
+    /// out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<16> = String::from("\u{3b1} is alpha");
+    /// s.replace_range(..2, "A").unwrap();
+    /// assert_eq!(s, "A is alpha");
+    ///
+    /// assert!(s.replace_range(5.., "a very long name").is_err());
+    /// assert_eq!(s, "A is alpha");
+    /// ```
+    pub fn replace_range<R>(&mut self, range: R, replace_with: &str) -> Result<(), CapacityError>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.as_mut_view().replace_range(range, replace_with)
+    }
+
+    /// Splits the string into two at the given byte index.
+    ///
+    /// Returns a new string containing the bytes `[at, len)`. After the call the original string
+    /// is left containing the bytes `[0, at)`.
+    ///
+    /// Returns an error, and leaves `self` untouched, if the split off part doesn't fit in a
+    /// `String` of capacity `M`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is not on a [`char`] boundary, or if it is beyond the end of the string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut hello: String<16> = String::from("Hello, World!");
+    /// let world: String<8> = hello.split_off(7).unwrap();
+    /// assert_eq!(hello, "Hello, ");
+    /// assert_eq!(world, "World!");
+    ///
+    /// assert!(hello.split_off::<1>(0).is_err());
+    /// ```
+    pub fn split_off<const M: usize>(&mut self, at: usize) -> Result<String<M>, CapacityError> {
+        self.as_mut_view().split_off(at)
+    }
+
+    /// Replaces all matches of the string slice `from` with the string slice `to`, returning the
+    /// result as a new `String` of capacity `M`.
+    ///
+    /// Returns an error if the result doesn't fit in `M` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let s: String<16> = String::from("this is old");
+    ///
+    /// let new: String<16> = s.replace("old", "new").unwrap();
+    /// assert_eq!(new, "this is new");
+    ///
+    /// assert!(s.replace::<16>("is", "was").is_ok());
+    /// assert!(s.replace::<16>(" ", "    ").is_err());
+    /// ```
+    pub fn replace<const M: usize>(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<String<M>, CapacityError> {
+        self.as_view().replace(from, to)
     }
 
     /// Truncates this `String`, removing all contents.
@@ -283,6 +644,306 @@ impl<const N: usize> String<N> {
     }
 }
 
+impl StringView {
+    /// Extracts a string slice containing the entire string.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        unsafe { str::from_utf8_unchecked(self.vec.as_slice()) }
+    }
+
+    /// Converts a `StringView` into a mutable string slice.
+    #[inline]
+    pub fn as_mut_str(&mut self) -> &mut str {
+        unsafe { str::from_utf8_unchecked_mut(self.vec.as_mut_slice()) }
+    }
+
+    /// Returns a mutable reference to the contents of this `StringView`.
+    ///
+    /// # Safety
+    ///
+    /// This function is unsafe because it does not check that the bytes passed
+    /// to it are valid UTF-8. If this constraint is violated, it may cause
+    /// memory unsafety issues with future users of the `StringView`, as the rest of
+    /// the library assumes that `StringView`s are valid UTF-8.
+    pub unsafe fn as_mut_vec(&mut self) -> &mut VecView<u8> {
+        &mut self.vec
+    }
+
+    /// Appends a given string slice onto the end of this `StringView`.
+    #[inline]
+    pub fn push_str(&mut self, string: &str) -> Result<(), CapacityError> {
+        self.vec.extend_from_slice(string.as_bytes())
+    }
+
+    /// Returns the maximum number of elements the string can hold
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.vec.capacity()
+    }
+
+    /// Appends the given [`char`] to the end of this `StringView`.
+    #[inline]
+    pub fn push(&mut self, c: char) -> Result<(), CapacityError<char>> {
+        match c.len_utf8() {
+            1 => self.vec.push(c as u8).map_err(|_| CapacityError::new(c)),
+            _ => self
+                .vec
+                .extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes())
+                .map_err(|_| CapacityError::new(c)),
+        }
+    }
+
+    /// Shortens this `StringView` to the specified length.
+    ///
+    /// If `new_len` is greater than the string's current length, this has no
+    /// effect.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_len` does not lie on a [`char`] boundary.
+    #[inline]
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len <= self.len() {
+            assert!(self.is_char_boundary(new_len));
+            self.vec.truncate(new_len)
+        }
+    }
+
+    /// Removes the last character from the string buffer and returns it.
+    ///
+    /// Returns [`None`] if this `StringView` is empty.
+    pub fn pop(&mut self) -> Option<char> {
+        let ch = self.chars().next_back()?;
+
+        // pop bytes that correspond to `ch`
+        for _ in 0..ch.len_utf8() {
+            unsafe {
+                self.vec.pop_unchecked();
+            }
+        }
+
+        Some(ch)
+    }
+
+    /// Inserts a character into this `StringView` at a byte position.
+    ///
+    /// Returns back the character, and leaves the string untouched, if it doesn't fit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is larger than the string's length, or if it does not lie on a [`char`]
+    /// boundary.
+    pub fn insert(&mut self, idx: usize, ch: char) -> Result<(), CapacityError<char>> {
+        assert!(self.is_char_boundary(idx));
+
+        self.splice_bytes(idx, idx, ch.encode_utf8(&mut [0; 4]).as_bytes())
+            .map_err(|_| CapacityError::new(ch))
+    }
+
+    /// Inserts a string slice into this `StringView` at a byte position.
+    ///
+    /// Returns an error, and leaves the string untouched, if `string` doesn't fit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is larger than the string's length, or if it does not lie on a [`char`]
+    /// boundary.
+    pub fn insert_str(&mut self, idx: usize, string: &str) -> Result<(), CapacityError> {
+        assert!(self.is_char_boundary(idx));
+
+        self.splice_bytes(idx, idx, string.as_bytes())
+    }
+
+    /// Removes a [`char`] from this `StringView` at a byte position and returns it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is larger than or equal to the string's length, or if it does not lie on a
+    /// [`char`] boundary.
+    pub fn remove(&mut self, idx: usize) -> char {
+        let ch = match self[idx..].chars().next() {
+            Some(ch) => ch,
+            None => panic!("cannot remove a char from the end of a string"),
+        };
+
+        // NOTE(unwrap) removing bytes can't run out of capacity
+        self.splice_bytes(idx, idx + ch.len_utf8(), &[]).unwrap();
+        ch
+    }
+
+    /// Retains only the characters specified by the predicate.
+    ///
+    /// In other words, removes all characters `c` such that `f(c)` returns `false`. This method
+    /// operates in place, visiting each character exactly once in the original order, and
+    /// preserves the order of the retained characters.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(char) -> bool,
+    {
+        // if `f` panics the string is cut short to the characters that have been kept so far, so
+        // that the half-moved tail never leaves the string with invalid UTF-8
+        struct SetLenOnDrop<'a> {
+            s: &'a mut StringView,
+            idx: usize,
+            del_bytes: usize,
+        }
+
+        impl Drop for SetLenOnDrop<'_> {
+            fn drop(&mut self) {
+                self.s.vec.truncate(self.idx - self.del_bytes);
+            }
+        }
+
+        let len = self.len();
+        let mut guard = SetLenOnDrop {
+            s: self,
+            idx: 0,
+            del_bytes: 0,
+        };
+
+        while guard.idx < len {
+            // NOTE(unwrap) `idx` is always on a char boundary, before the end of the string
+            let ch = guard.s[guard.idx..].chars().next().unwrap();
+            let ch_len = ch.len_utf8();
+
+            if !f(ch) {
+                guard.del_bytes += ch_len;
+            } else if guard.del_bytes > 0 {
+                let start = guard.idx;
+                guard
+                    .s
+                    .vec
+                    .copy_within(start..start + ch_len, start - guard.del_bytes);
+            }
+
+            guard.idx += ch_len;
+        }
+    }
+
+    /// Removes the specified range from the string in bulk, returning all removed characters as
+    /// an iterator.
+    ///
+    /// The range is removed even if the iterator is not consumed until the end.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point or end point do not lie on a [`char`] boundary, or if they're
+    /// out of bounds.
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_>
+    where
+        R: RangeBounds<usize>,
+    {
+        let ops::Range { start, end } = crate::vec::range_of(range, self.len());
+        assert!(self.is_char_boundary(start));
+        assert!(self.is_char_boundary(end));
+
+        // the iterator borrows the string through `string` so that `Drain::drop` can still remove
+        // the range afterwards
+        let string = self as *mut StringView;
+        // SAFETY: `range_of` and `is_char_boundary` did the bounds checks
+        let iter = unsafe { (*string).get_unchecked(start..end) }.chars();
+
+        Drain {
+            start,
+            end,
+            iter,
+            string,
+        }
+    }
+
+    /// Replaces the specified range in the string with the given string slice.
+    ///
+    /// Returns an error, and leaves the string untouched, if the result doesn't fit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point or end point do not lie on a [`char`] boundary, or if they're
+    /// out of bounds.
+    pub fn replace_range<R>(&mut self, range: R, replace_with: &str) -> Result<(), CapacityError>
+    where
+        R: RangeBounds<usize>,
+    {
+        let ops::Range { start, end } = crate::vec::range_of(range, self.len());
+        assert!(self.is_char_boundary(start));
+        assert!(self.is_char_boundary(end));
+
+        self.splice_bytes(start, end, replace_with.as_bytes())
+    }
+
+    /// Splits the string into two at the given byte index.
+    ///
+    /// Returns a new string containing the bytes `[at, len)`. After the call the original string
+    /// is left containing the bytes `[0, at)`.
+    ///
+    /// Returns an error, and leaves `self` untouched, if the split off part doesn't fit in a
+    /// `String` of capacity `M`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is not on a [`char`] boundary, or if it is beyond the end of the string.
+    pub fn split_off<const M: usize>(&mut self, at: usize) -> Result<String<M>, CapacityError> {
+        assert!(self.is_char_boundary(at));
+
+        Ok(String {
+            vec: self.vec.split_off(at)?,
+        })
+    }
+
+    /// Replaces all matches of the string slice `from` with the string slice `to`, returning the
+    /// result as a new `String` of capacity `M`.
+    ///
+    /// Returns an error if the result doesn't fit in `M` bytes.
+    pub fn replace<const M: usize>(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<String<M>, CapacityError> {
+        let mut result = String::new();
+        let mut last_end = 0;
+        for (start, part) in self.match_indices(from) {
+            // SAFETY: `match_indices` returns ranges that lie on char boundaries
+            result.push_str(unsafe { self.get_unchecked(last_end..start) })?;
+            result.push_str(to)?;
+            last_end = start + part.len();
+        }
+        // SAFETY: `last_end` is either 0 or the end of a match
+        result.push_str(unsafe { self.get_unchecked(last_end..self.len()) })?;
+        Ok(result)
+    }
+
+    /// Replaces the bytes in `start..end` with `bytes`, shifting the tail of the string as needed
+    ///
+    /// Leaves the string untouched if the result doesn't fit. The callers are responsible for
+    /// keeping the contents valid UTF-8.
+    fn splice_bytes(
+        &mut self,
+        start: usize,
+        end: usize,
+        bytes: &[u8],
+    ) -> Result<(), CapacityError> {
+        let len = self.vec.len();
+        let new_len = len - (end - start) + bytes.len();
+        if new_len > self.capacity() {
+            return Err(CapacityError::new(()));
+        }
+
+        if new_len > len {
+            self.vec.resize(new_len, 0)?;
+        }
+        self.vec.copy_within(end..len, start + bytes.len());
+        self.vec[start..start + bytes.len()].copy_from_slice(bytes);
+        self.vec.truncate(new_len);
+
+        Ok(())
+    }
+
+    /// Truncates this `StringView`, removing all contents.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.vec.clear()
+    }
+}
+
 impl<const N: usize> Default for String<N> {
     fn default() -> Self {
         Self::new()
@@ -298,7 +959,7 @@ impl<'a, const N: usize> From<&'a str> for String<N> {
 }
 
 impl<const N: usize> str::FromStr for String<N> {
-    type Err = ();
+    type Err = CapacityError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut new = String::new();
@@ -307,6 +968,45 @@ impl<const N: usize> str::FromStr for String<N> {
     }
 }
 
+impl<const N: usize> TryFromIterator<char> for String<N> {
+    fn try_from_iter<I>(iter: I) -> Result<Self, CapacityError<char>>
+    where
+        I: IntoIterator<Item = char>,
+    {
+        let mut new = String::new();
+        for c in iter {
+            new.push(c)?;
+        }
+        Ok(new)
+    }
+}
+
+impl<'a, const N: usize> TryFromIterator<&'a char> for String<N> {
+    fn try_from_iter<I>(iter: I) -> Result<Self, CapacityError<&'a char>>
+    where
+        I: IntoIterator<Item = &'a char>,
+    {
+        let mut new = String::new();
+        for c in iter {
+            new.push(*c).map_err(|_| CapacityError::new(c))?;
+        }
+        Ok(new)
+    }
+}
+
+impl<'a, const N: usize> TryFromIterator<&'a str> for String<N> {
+    fn try_from_iter<I>(iter: I) -> Result<Self, CapacityError<&'a str>>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut new = String::new();
+        for s in iter {
+            new.push_str(s).map_err(|_| CapacityError::new(s))?;
+        }
+        Ok(new)
+    }
+}
+
 impl<const N: usize> Clone for String<N> {
     fn clone(&self) -> Self {
         Self {
@@ -439,6 +1139,243 @@ impl<const N: usize> PartialEq<String<N>> for &str {
 
 impl<const N: usize> Eq for String<N> {}
 
+impl fmt::Debug for StringView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <str as fmt::Debug>::fmt(self, f)
+    }
+}
+
+impl fmt::Display for StringView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <str as fmt::Display>::fmt(self, f)
+    }
+}
+
+impl hash::Hash for StringView {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, hasher: &mut H) {
+        <str as hash::Hash>::hash(self, hasher)
+    }
+}
+
+impl hash32::Hash for StringView {
+    #[inline]
+    fn hash<H: hash32::Hasher>(&self, hasher: &mut H) {
+        <str as hash32::Hash>::hash(self, hasher)
+    }
+}
+
+impl fmt::Write for StringView {
+    fn write_str(&mut self, s: &str) -> Result<(), fmt::Error> {
+        self.push_str(s).map_err(|_| fmt::Error)
+    }
+
+    fn write_char(&mut self, c: char) -> Result<(), fmt::Error> {
+        self.push(c).map_err(|_| fmt::Error)
+    }
+}
+
+impl ops::Deref for StringView {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl ops::DerefMut for StringView {
+    fn deref_mut(&mut self) -> &mut str {
+        self.as_mut_str()
+    }
+}
+
+impl AsRef<str> for StringView {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl AsRef<[u8]> for StringView {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl PartialEq for StringView {
+    fn eq(&self, rhs: &StringView) -> bool {
+        str::eq(&**self, &**rhs)
+    }
+}
+
+// StringView == String<N>
+impl<const N: usize> PartialEq<String<N>> for StringView {
+    fn eq(&self, rhs: &String<N>) -> bool {
+        str::eq(&**self, &**rhs)
+    }
+}
+
+// String<N> == StringView
+impl<const N: usize> PartialEq<StringView> for String<N> {
+    fn eq(&self, rhs: &StringView) -> bool {
+        str::eq(&**self, &**rhs)
+    }
+}
+
+// StringView == str
+impl PartialEq<str> for StringView {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        str::eq(&self[..], other)
+    }
+}
+
+// StringView == &'str
+impl PartialEq<&str> for StringView {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        str::eq(&self[..], &other[..])
+    }
+}
+
+// str == StringView
+impl PartialEq<StringView> for str {
+    #[inline]
+    fn eq(&self, other: &StringView) -> bool {
+        str::eq(self, &other[..])
+    }
+}
+
+// &'str == StringView
+impl PartialEq<StringView> for &str {
+    #[inline]
+    fn eq(&self, other: &StringView) -> bool {
+        str::eq(&self[..], &other[..])
+    }
+}
+
+impl Eq for StringView {}
+
+/// A possible error value when converting a [`String`] from a UTF-8 byte vector.
+///
+/// This type is the error type for [`String::from_utf8`]. It holds on to the bytes that were
+/// passed in, so they can be recovered with [`into_bytes`](FromUtf8Error::into_bytes).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FromUtf8Error<const N: usize> {
+    bytes: Vec<u8, N>,
+    error: str::Utf8Error,
+}
+
+impl<const N: usize> FromUtf8Error<N> {
+    /// Returns a slice of the bytes that were attempted to convert to a `String`.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns the bytes that were attempted to convert to a `String`.
+    pub fn into_bytes(self) -> Vec<u8, N> {
+        self.bytes
+    }
+
+    /// Returns the [`Utf8Error`](core::str::Utf8Error) with the details of the conversion
+    /// failure.
+    pub fn utf8_error(&self) -> str::Utf8Error {
+        self.error
+    }
+}
+
+impl<const N: usize> fmt::Display for FromUtf8Error<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const N: usize> std::error::Error for FromUtf8Error<N> {}
+
+/// A draining iterator for `String`.
+///
+/// This struct is created by the [`drain`](String::drain) method on [`String`] and
+/// [`StringView`].
+pub struct Drain<'a> {
+    /// Will be used as `&'a mut StringView` in the destructor
+    string: *mut StringView,
+    /// Start of part to remove
+    start: usize,
+    /// End of part to remove
+    end: usize,
+    /// Current remaining range to remove
+    iter: str::Chars<'a>,
+}
+
+// SAFETY: `Drain` only hands out `char`s and `&str`s, like the `&mut StringView` it was created
+// from
+unsafe impl Send for Drain<'_> {}
+unsafe impl Sync for Drain<'_> {}
+
+impl Drain<'_> {
+    /// Returns the remaining (sub)string of this iterator as a slice.
+    pub fn as_str(&self) -> &str {
+        self.iter.as_str()
+    }
+}
+
+impl fmt::Debug for Drain<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Drain").field(&self.as_str()).finish()
+    }
+}
+
+impl AsRef<str> for Drain<'_> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<[u8]> for Drain<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_str().as_bytes()
+    }
+}
+
+impl Iterator for Drain<'_> {
+    type Item = char;
+
+    #[inline]
+    fn next(&mut self) -> Option<char> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<char> {
+        self.next_back()
+    }
+}
+
+impl DoubleEndedIterator for Drain<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<char> {
+        self.iter.next_back()
+    }
+}
+
+impl FusedIterator for Drain<'_> {}
+
+impl Drop for Drain<'_> {
+    fn drop(&mut self) {
+        // SAFETY: `string` comes from the `&'a mut StringView` that created this iterator and the
+        // range lies on char boundaries
+        unsafe {
+            (*self.string).vec.drain(self.start..self.end);
+        }
+    }
+}
+
 macro_rules! impl_from_num {
     ($num:ty, $size:expr) => {
         impl<const N: usize> From<$num> for String<N> {
@@ -461,9 +1398,145 @@ impl_from_num!(u16, 5);
 impl_from_num!(u32, 10);
 impl_from_num!(u64, 20);
 
+/// Formats `args` into a new `String<N>`
+///
+/// Returns an error if the formatted text doesn't fit in the capacity of the string or if one of
+/// the formatting trait implementations returns an error. The [`format!`](crate::format!) macro is
+/// usually more convenient.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::String;
+///
+/// let s: String<16> = heapless::format(format_args!("{}-{}", 1, 2)).unwrap();
+/// assert_eq!(s, "1-2");
+///
+/// assert!(heapless::format::<4>(format_args!("{}", 12345)).is_err());
+/// ```
+pub fn format<const N: usize>(args: fmt::Arguments<'_>) -> Result<String<N>, fmt::Error> {
+    let mut s = String::new();
+    s.write_fmt(args)?;
+    Ok(s)
+}
+
+/// The marker that [`format_truncated`] appends to output that didn't fit
+pub const TRUNCATION_MARKER: &str = "\u{2026}";
+
+/// Formats `args` into a new `String<N>`, cutting the output short if it doesn't fit
+///
+/// When the formatted text is longer than `N` bytes it is truncated, on a `char` boundary, and
+/// [`TRUNCATION_MARKER`] is appended in place of the text that didn't fit. The marker is left out
+/// if `N` is too small to hold it. Errors returned by formatting trait implementations stop the
+/// formatting early without adding the marker. The
+/// [`format_truncated!`](crate::format_truncated!) macro is usually more convenient.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::String;
+///
+/// let s: String<8> = heapless::format_truncated(format_args!("{}", "abc"));
+/// assert_eq!(s, "abc");
+///
+/// let s: String<8> = heapless::format_truncated(format_args!("{}", "abcdefghij"));
+/// assert_eq!(s, "abcde\u{2026}");
+/// ```
+pub fn format_truncated<const N: usize>(args: fmt::Arguments<'_>) -> String<N> {
+    struct Truncating<'a, const N: usize> {
+        s: &'a mut String<N>,
+        truncated: bool,
+    }
+
+    impl<const N: usize> fmt::Write for Truncating<'_, N> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            if self.s.push_str(s).is_ok() {
+                return Ok(());
+            }
+
+            // fill the remaining capacity and stop formatting
+            for c in s.chars() {
+                if self.s.push(c).is_err() {
+                    break;
+                }
+            }
+            self.truncated = true;
+            Err(fmt::Error)
+        }
+    }
+
+    let mut s = String::new();
+    let mut w = Truncating {
+        s: &mut s,
+        truncated: false,
+    };
+    let truncated = w.write_fmt(args).is_err() && w.truncated;
+
+    if truncated && N >= TRUNCATION_MARKER.len() {
+        while s.len() + TRUNCATION_MARKER.len() > N {
+            s.pop();
+        }
+        // NOTE(unwrap) we just made room for the marker
+        s.push_str(TRUNCATION_MARKER).unwrap();
+    }
+
+    s
+}
+
+/// Creates a `String<N>` using interpolation of runtime expressions
+///
+/// The capacity of the string can be given before the format string, separated by a `;`, or be
+/// inferred from the context. Returns `Result<String<N>, core::fmt::Error>`; see
+/// [`format`](crate::format()) for when it fails.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::String;
+///
+/// let s = heapless::format!(16; "{}-{}", 1, 2).unwrap();
+/// assert_eq!(s, "1-2");
+///
+/// let s: String<16> = heapless::format!("{:?}", (1, 2)).unwrap();
+/// assert_eq!(s, "(1, 2)");
+///
+/// assert!(heapless::format!(4; "{}", 12345).is_err());
+/// ```
+#[macro_export]
+macro_rules! format {
+    ($max:expr; $($arg:tt)*) => {
+        $crate::format::<{ $max }>(::core::format_args!($($arg)*))
+    };
+    ($($arg:tt)*) => {
+        $crate::format(::core::format_args!($($arg)*))
+    };
+}
+
+/// Creates a `String<N>` using interpolation of runtime expressions, truncating the output if it
+/// doesn't fit
+///
+/// Takes the same arguments as [`format!`](crate::format!) but never fails; see
+/// [`format_truncated`](crate::format_truncated()) for how the output is cut short.
+///
+/// # Examples
+///
+/// ```
+/// let s = heapless::format_truncated!(9; "temp={}", 1234.5);
+/// assert_eq!(s, "temp=1\u{2026}");
+/// ```
+#[macro_export]
+macro_rules! format_truncated {
+    ($max:expr; $($arg:tt)*) => {
+        $crate::format_truncated::<{ $max }>(::core::format_args!($($arg)*))
+    };
+    ($($arg:tt)*) => {
+        $crate::format_truncated(::core::format_args!($($arg)*))
+    };
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{String, Vec};
+    use crate::{CapacityError, String, StringView, Vec};
 
     #[test]
     fn static_new() {
@@ -524,8 +1597,8 @@ mod tests {
         assert!(s.len() == 3);
         assert_eq!(s, "123");
 
-        let e: () = String::<2>::from_str("123").unwrap_err();
-        assert_eq!(e, ());
+        let e: CapacityError = String::<2>::from_str("123").unwrap_err();
+        assert_eq!(e, CapacityError::new(()));
     }
 
     #[test]
@@ -643,4 +1716,241 @@ mod tests {
         assert_eq!(0, s.len());
         assert_eq!(8, s.capacity());
     }
+
+    #[test]
+    fn view() {
+        use core::fmt::Write;
+
+        fn shout(s: &mut StringView) {
+            s.make_ascii_uppercase();
+            let _ = s.push('!');
+        }
+
+        let mut a: String<4> = String::from("abc");
+        let mut b: String<3> = String::from("abc");
+        shout(a.as_mut_view());
+        shout(b.as_mut_view());
+        assert_eq!(a, "ABC!");
+        assert_eq!(b, "ABC");
+        assert_eq!(*a.as_view(), "ABC!");
+        assert_eq!(b.as_view().capacity(), 3);
+
+        let view = b.as_mut_view();
+        assert_eq!(view.pop(), Some('C'));
+        view.truncate(1);
+        write!(view, "{}", 1).unwrap();
+        assert_eq!(b, "A1");
+    }
+
+    #[test]
+    fn try_from_iter() {
+        use crate::{TryCollect, TryFromIterator};
+
+        let s: String<4> = "abcd".chars().try_collect().unwrap();
+        assert_eq!(s, "abcd");
+        assert_eq!(
+            String::<3>::try_from_iter("abcd".chars()),
+            Err(CapacityError::new('d'))
+        );
+
+        let s: String<8> = ["ab", "cd"].iter().cloned().try_collect().unwrap();
+        assert_eq!(s, "abcd");
+        assert_eq!(
+            String::<3>::try_from_iter(["ab", "cd"].iter().cloned()),
+            Err(CapacityError::new("cd"))
+        );
+    }
+
+    #[test]
+    fn format() {
+        let s = crate::format!(8; "{}{}", "abc", 123).unwrap();
+        assert_eq!(s, "abc123");
+
+        let s: String<4> = crate::format!("{}", 'a').unwrap();
+        assert_eq!(s, "a");
+
+        assert!(crate::format!(4; "{}", "abcde").is_err());
+    }
+
+    #[test]
+    fn format_truncated() {
+        let s = crate::format_truncated!(8; "{}", "abc");
+        assert_eq!(s, "abc");
+
+        // fits exactly, no marker
+        let s = crate::format_truncated!(8; "{}", "abcdefgh");
+        assert_eq!(s, "abcdefgh");
+
+        let s = crate::format_truncated!(8; "{}{}", "abcd", "efghi");
+        assert_eq!(s, "abcde\u{2026}");
+
+        // multi-byte chars are never split
+        let s = crate::format_truncated!(6; "{}", "\u{e9}\u{e9}\u{e9}\u{e9}");
+        assert_eq!(s, "\u{e9}\u{2026}");
+
+        // too small for the marker
+        let s = crate::format_truncated!(2; "{}", "abc");
+        assert_eq!(s, "ab");
+    }
+
+    #[test]
+    fn insert() {
+        let mut s: String<8> = String::from("a\u{e9}");
+        s.insert(3, 'z').unwrap();
+        s.insert(1, '\u{2192}').unwrap();
+        assert_eq!(s, "a\u{2192}\u{e9}z");
+
+        assert_eq!(s.insert(0, '\u{e9}'), Err(CapacityError::new('\u{e9}')));
+        s.insert_str(7, "!").unwrap();
+        assert_eq!(s, "a\u{2192}\u{e9}z!");
+        assert!(s.insert_str(0, "x").is_err());
+        assert_eq!(s, "a\u{2192}\u{e9}z!");
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_not_char_boundary() {
+        let mut s: String<8> = String::from("\u{e9}");
+        let _ = s.insert(1, 'a');
+    }
+
+    #[test]
+    fn remove() {
+        let mut s: String<8> = String::from("a\u{e9}b");
+        assert_eq!(s.remove(1), '\u{e9}');
+        assert_eq!(s, "ab");
+        assert_eq!(s.remove(1), 'b');
+        assert_eq!(s.remove(0), 'a');
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn remove_end() {
+        let mut s: String<8> = String::from("ab");
+        s.remove(2);
+    }
+
+    #[test]
+    fn retain() {
+        let mut s: String<16> = String::from("\u{e9}a\u{e9}b\u{2192}c");
+        s.retain(|c| c.is_ascii());
+        assert_eq!(s, "abc");
+
+        let mut s: String<16> = String::from("\u{e9}a\u{e9}b");
+        s.retain(|c| !c.is_ascii());
+        assert_eq!(s, "\u{e9}\u{e9}");
+    }
+
+    #[test]
+    fn retain_panic() {
+        let mut s: String<16> = String::from("a\u{e9}b\u{e9}c");
+
+        let r = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            s.retain(|c| match c {
+                'a' => false,
+                'c' => panic!(),
+                _ => true,
+            })
+        }));
+        assert!(r.is_err());
+
+        // the string is cut short but still valid UTF-8
+        assert_eq!(s, "\u{e9}b\u{e9}");
+    }
+
+    #[test]
+    fn drain() {
+        let mut s: String<16> = String::from("a\u{e9}b\u{2192}c");
+
+        {
+            let mut d = s.drain(1..7);
+            assert_eq!(d.as_str(), "\u{e9}b\u{2192}");
+            assert_eq!(d.next(), Some('\u{e9}'));
+            assert_eq!(d.next_back(), Some('\u{2192}'));
+            // the rest is removed on drop
+        }
+        assert_eq!(s, "ac");
+
+        assert!(s.drain(..).eq("ac".chars()));
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn drain_not_char_boundary() {
+        let mut s: String<8> = String::from("\u{e9}");
+        s.drain(..1);
+    }
+
+    #[test]
+    fn replace_range() {
+        let mut s: String<8> = String::from("abc");
+        s.replace_range(1..2, "\u{e9}\u{e9}").unwrap();
+        assert_eq!(s, "a\u{e9}\u{e9}c");
+        s.replace_range(1..=4, "").unwrap();
+        assert_eq!(s, "ac");
+        s.replace_range(..0, "12345").unwrap();
+        assert_eq!(s, "12345ac");
+        assert!(s.replace_range(7.., "\u{e9}").is_err());
+        assert_eq!(s, "12345ac");
+    }
+
+    #[test]
+    fn split_off() {
+        let mut s: String<8> = String::from("ab\u{e9}cd");
+        let t: String<4> = s.split_off(2).unwrap();
+        assert_eq!(s, "ab");
+        assert_eq!(t, "\u{e9}cd");
+
+        let mut t = t;
+        assert!(t.split_off::<2>(0).is_err());
+        assert_eq!(t, "\u{e9}cd");
+    }
+
+    #[test]
+    fn replace() {
+        let s: String<8> = String::from("a-b-c");
+        let r: String<8> = s.replace("-", "").unwrap();
+        assert_eq!(r, "abc");
+        let r: String<8> = s.replace("-", "--").unwrap();
+        assert_eq!(r, "a--b--c");
+        let r: String<8> = s.replace("x", "y").unwrap();
+        assert_eq!(r, "a-b-c");
+        assert!(s.replace::<8>("-", "---").is_err());
+
+        // the empty pattern matches around every char, as with `str::replace`
+        let s: String<8> = String::from("ab");
+        let r: String<8> = s.replace("", "_").unwrap();
+        assert_eq!(r, "_a_b_");
+    }
+
+    #[test]
+    fn from_utf8() {
+        let v: Vec<u8, 4> = Vec::from_slice("\u{e9}a".as_bytes()).unwrap();
+        let s = String::from_utf8(v).unwrap();
+        assert_eq!(s, "\u{e9}a");
+
+        let v: Vec<u8, 4> = Vec::from_slice(&[b'a', 0xe9, b'b']).unwrap();
+        let e = String::from_utf8(v.clone()).unwrap_err();
+        assert_eq!(e.as_bytes(), &v[..]);
+        assert_eq!(e.utf8_error().valid_up_to(), 1);
+        assert_eq!(e.into_bytes(), v);
+    }
+
+    #[test]
+    fn from_utf8_lossy() {
+        let s: String<8> = String::from_utf8_lossy(b"ab").unwrap();
+        assert_eq!(s, "ab");
+
+        // invalid byte in the middle, truncated sequence at the end
+        let s: String<16> = String::from_utf8_lossy(b"a\xffb\xe2\x82").unwrap();
+        assert_eq!(s, "a\u{fffd}b\u{fffd}");
+
+        // the input fits but the replacement character doesn't
+        assert_eq!(
+            String::<3>::from_utf8_lossy(b"a\xff"),
+            Err(CapacityError::new(()))
+        );
+    }
 }
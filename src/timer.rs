@@ -0,0 +1,319 @@
+//! A timer queue with cancellable timers
+//!
+//! [`TimerQueue`] keeps items ordered by their deadline, the earliest first, and hands out a
+//! [`Handle`] for each of them to cancel it before it expires.
+//!
+//! Deadlines are [`Instant`]s, which compare with wrap-around: a free running `u32` tick counter
+//! can overflow without a timer scheduled just before the overflow expiring after one scheduled
+//! just after it.
+
+use core::{cmp::Ordering, fmt, iter::FusedIterator};
+
+use crate::binary_heap::{Compare, IndexedBinaryHeap};
+
+pub use crate::binary_heap::Handle;
+
+/// A point in time, as counted by a free running timer
+///
+/// The integer implementations compare with wrap-around: `a` is before `b` when `b - a`, computed
+/// with wrapping arithmetic, is less than half the range of the type. Thus the deadlines in a
+/// queue, and the current time, must never be more than half the range apart, e.g. about 24 days
+/// for a `u32` counter of milliseconds.
+///
+/// Types that don't wrap, like a `u64` of microseconds since boot, can implement this trait with
+/// their `Ord` implementation.
+pub trait Instant: Copy {
+    /// Compares two instants; `Ordering::Less` means that `self` comes before `other`
+    fn compare(&self, other: &Self) -> Ordering;
+}
+
+macro_rules! impl_instant {
+    ($($unsigned:ty => $signed:ty,)*) => {
+        $(
+            impl Instant for $unsigned {
+                fn compare(&self, other: &Self) -> Ordering {
+                    (self.wrapping_sub(*other) as $signed).cmp(&0)
+                }
+            }
+        )*
+    };
+}
+
+impl_instant! {
+    u8 => i8,
+    u16 => i16,
+    u32 => i32,
+    u64 => i64,
+    usize => isize,
+}
+
+struct Timer<I, T> {
+    deadline: I,
+    item: T,
+}
+
+// the earliest deadline first
+struct Earliest;
+
+impl<I, T> Compare<Timer<I, T>> for Earliest
+where
+    I: Instant,
+{
+    fn compare(&self, a: &Timer<I, T>, b: &Timer<I, T>) -> Ordering {
+        b.deadline.compare(&a.deadline)
+    }
+}
+
+/// A queue of items, each to be taken out once its deadline has expired
+///
+/// It is an [`IndexedBinaryHeap`] ordered by deadline, so scheduling and cancelling a timer are
+/// `O(log n)`, and finding the next deadline is `O(1)`. Timers with the same deadline expire in an
+/// unspecified order.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::timer::TimerQueue;
+///
+/// static mut TIMERS: TimerQueue<u32, &str, 8> = TimerQueue::new();
+///
+/// let timers = unsafe { &mut TIMERS };
+///
+/// // the tick counter is about to overflow
+/// let now = u32::MAX - 5;
+/// timers.schedule(now.wrapping_add(10), "retransmit").unwrap();
+/// let timeout = timers.schedule(now.wrapping_add(20), "timeout").unwrap();
+/// timers.schedule(now + 1, "blink").unwrap();
+///
+/// // the reply arrived
+/// assert_eq!(timers.cancel(timeout), Some("timeout"));
+///
+/// assert_eq!(timers.next_deadline(), Some(now + 1));
+///
+/// let now = now.wrapping_add(15);
+/// let expired: Vec<_> = timers.pop_expired(now).map(|(_, item)| item).collect();
+/// assert_eq!(expired, ["blink", "retransmit"]);
+/// assert!(timers.is_empty());
+/// ```
+pub struct TimerQueue<I, T, const N: usize> {
+    heap: IndexedBinaryHeap<Timer<I, T>, Earliest, N>,
+}
+
+impl<I, T, const N: usize> TimerQueue<I, T, N> {
+    /// Creates an empty timer queue
+    pub const fn new() -> Self {
+        Self {
+            heap: IndexedBinaryHeap::with_cmp(Earliest),
+        }
+    }
+
+    /// Returns the capacity of the queue
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of scheduled timers
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Checks if no timer is scheduled
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Cancels all the timers
+    pub fn clear(&mut self) {
+        self.heap.clear()
+    }
+
+    /// Returns `true` if the timer `handle` refers to is still scheduled
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.heap.contains(handle)
+    }
+
+    /// Returns the deadline and the item of the timer `handle` refers to, or `None` if it has
+    /// expired or has been cancelled
+    pub fn get(&self, handle: Handle) -> Option<(I, &T)>
+    where
+        I: Copy,
+    {
+        self.heap
+            .get(handle)
+            .map(|timer| (timer.deadline, &timer.item))
+    }
+
+    /// Returns an iterator over the deadlines and items of the scheduled timers, in arbitrary
+    /// order
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (I, &T)> + '_
+    where
+        I: Copy,
+    {
+        self.heap
+            .iter()
+            .map(|(_, timer)| (timer.deadline, &timer.item))
+    }
+}
+
+impl<I, T, const N: usize> TimerQueue<I, T, N>
+where
+    I: Instant,
+{
+    /// Schedules `item` to expire at `deadline` and returns a handle to cancel it
+    ///
+    /// Returns back the item if the queue is full.
+    pub fn schedule(&mut self, deadline: I, item: T) -> Result<Handle, T> {
+        self.heap
+            .push(Timer { deadline, item })
+            .map_err(|timer| timer.item)
+    }
+
+    /// Cancels the timer `handle` refers to and returns its item, or `None` if it has already
+    /// expired or has been cancelled
+    pub fn cancel(&mut self, handle: Handle) -> Option<T> {
+        self.heap.remove(handle).map(|timer| timer.item)
+    }
+
+    /// Returns the earliest deadline, or `None` if no timer is scheduled
+    pub fn next_deadline(&self) -> Option<I> {
+        self.heap.peek().map(|timer| timer.deadline)
+    }
+
+    /// Removes the timer with the earliest deadline if it has expired at `now`, and returns its
+    /// deadline and item
+    pub fn pop_if_expired(&mut self, now: I) -> Option<(I, T)> {
+        let expired = self.heap.peek()?.deadline.compare(&now) != Ordering::Greater;
+        if !expired {
+            return None;
+        }
+
+        self.heap.pop().map(|timer| (timer.deadline, timer.item))
+    }
+
+    /// Returns an iterator that removes the timers that have expired at `now`, the earliest
+    /// first, and yields their deadlines and items
+    ///
+    /// A timer expires when its deadline is not after `now`. Timers that aren't yielded, because
+    /// the iterator is dropped early, stay scheduled.
+    pub fn pop_expired(&mut self, now: I) -> PopExpired<'_, I, T, N> {
+        PopExpired { queue: self, now }
+    }
+}
+
+impl<I, T, const N: usize> Default for TimerQueue<I, T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I, T, const N: usize> fmt::Debug for TimerQueue<I, T, N>
+where
+    I: Copy + fmt::Debug,
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// An iterator over the expired timers of a [`TimerQueue`]
+///
+/// This `struct` is created by [`TimerQueue::pop_expired`]. See its documentation for more.
+pub struct PopExpired<'a, I, T, const N: usize>
+where
+    I: Instant,
+{
+    queue: &'a mut TimerQueue<I, T, N>,
+    now: I,
+}
+
+impl<I, T, const N: usize> Iterator for PopExpired<'_, I, T, N>
+where
+    I: Instant,
+{
+    type Item = (I, T);
+
+    fn next(&mut self) -> Option<(I, T)> {
+        self.queue.pop_if_expired(self.now)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.queue.len()))
+    }
+}
+
+impl<I, T, const N: usize> FusedIterator for PopExpired<'_, I, T, N> where I: Instant {}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use super::{Instant, TimerQueue};
+    use core::cmp::Ordering;
+
+    #[test]
+    fn static_new() {
+        static mut _T: TimerQueue<u32, u8, 16> = TimerQueue::new();
+    }
+
+    #[test]
+    fn wrapping_compare() {
+        assert_eq!(1u32.compare(&2), Ordering::Less);
+        assert_eq!(2u32.compare(&2), Ordering::Equal);
+        assert_eq!(0u32.compare(&u32::MAX), Ordering::Greater);
+        assert_eq!(u32::MAX.compare(&5), Ordering::Less);
+        assert_eq!(250u8.compare(&3), Ordering::Less);
+        assert_eq!(3u8.compare(&200), Ordering::Greater);
+    }
+
+    #[test]
+    fn expire_in_order() {
+        let mut timers = TimerQueue::<u16, char, 8>::new();
+        let start = u16::MAX - 100;
+        for (delay, c) in [(150, 'd'), (50, 'b'), (10, 'a'), (120, 'c'), (300, 'e')] {
+            timers.schedule(start.wrapping_add(delay), c).unwrap();
+        }
+
+        assert!(timers.pop_expired(start).next().is_none());
+        assert_eq!(timers.next_deadline(), Some(start + 10));
+
+        let expired: Vec<_> = timers.pop_expired(start + 50).collect();
+        assert_eq!(expired, [(start + 10, 'a'), (start + 50, 'b')]);
+
+        // across the overflow
+        let now = start.wrapping_add(200);
+        let expired: Vec<_> = timers.pop_expired(now).map(|(_, c)| c).collect();
+        assert_eq!(expired, ['c', 'd']);
+        assert_eq!(timers.next_deadline(), Some(start.wrapping_add(300)));
+    }
+
+    #[test]
+    fn cancel() {
+        let mut timers = TimerQueue::<u32, u8, 2>::new();
+        let a = timers.schedule(10, 0).unwrap();
+        let b = timers.schedule(5, 1).unwrap();
+        assert_eq!(timers.schedule(1, 2), Err(2));
+
+        assert_eq!(timers.cancel(b), Some(1));
+        assert_eq!(timers.cancel(b), None);
+        assert!(!timers.contains(b));
+        assert_eq!(timers.get(a), Some((10, &0)));
+        assert_eq!(timers.next_deadline(), Some(10));
+
+        assert_eq!(timers.pop_if_expired(10), Some((10, 0)));
+        // expired handles can't cancel a newer timer in the same slot
+        let c = timers.schedule(20, 3).unwrap();
+        assert_eq!(timers.cancel(a), None);
+        assert_eq!(timers.get(c), Some((20, &3)));
+    }
+
+    #[test]
+    fn partial_iteration() {
+        let mut timers = TimerQueue::<u32, u8, 4>::new();
+        timers.schedule(1, 1).unwrap();
+        timers.schedule(2, 2).unwrap();
+
+        assert_eq!(timers.pop_expired(5).next(), Some((1, 1)));
+        assert_eq!(timers.len(), 1);
+    }
+}
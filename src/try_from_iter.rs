@@ -0,0 +1,53 @@
+//! Fallible conversion from iterators
+
+use crate::CapacityError;
+
+/// Conversion from an [`Iterator`] that fails, instead of panicking, when the collection runs out
+/// of capacity
+///
+/// This is the fallible counterpart of [`FromIterator`](core::iter::FromIterator). On overflow the
+/// item that could not be stored is handed back inside a [`CapacityError`]; the rest of the
+/// iterator is left unconsumed.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::{CapacityError, TryFromIterator, Vec};
+///
+/// let v: Vec<u8, 4> = Vec::try_from_iter(0..3).unwrap();
+/// assert_eq!(v, [0, 1, 2]);
+///
+/// // the fifth item doesn't fit
+/// assert_eq!(Vec::<u8, 4>::try_from_iter(0..8), Err(CapacityError::new(4)));
+/// ```
+pub trait TryFromIterator<A>: Sized {
+    /// Creates a value from an iterator, returning the first item that didn't fit on overflow
+    fn try_from_iter<I>(iter: I) -> Result<Self, CapacityError<A>>
+    where
+        I: IntoIterator<Item = A>;
+}
+
+/// Extension trait that adds [`try_collect`](TryCollect::try_collect) to all iterators
+///
+/// # Examples
+///
+/// ```
+/// use heapless::{CapacityError, TryCollect, Vec};
+///
+/// let squares = (1..4).map(|x| x * x).try_collect::<Vec<_, 8>>().unwrap();
+/// assert_eq!(squares, [1, 4, 9]);
+///
+/// assert_eq!((0..9).try_collect::<Vec<_, 8>>(), Err(CapacityError::new(8)));
+/// ```
+pub trait TryCollect: Iterator + Sized {
+    /// Transforms an iterator into a collection, returning the first item that didn't fit on
+    /// overflow
+    fn try_collect<C>(self) -> Result<C, CapacityError<Self::Item>>
+    where
+        C: TryFromIterator<Self::Item>,
+    {
+        C::try_from_iter(self)
+    }
+}
+
+impl<I> TryCollect for I where I: Iterator {}
@@ -1,15 +1,33 @@
-use crate::{string::String, vec::Vec};
+use crate::{
+    string::{String, StringView},
+    vec::{Vec, VecView},
+    CapacityError,
+};
 use ufmt_write::uWrite;
 
 impl<const N: usize> uWrite for String<N> {
-    type Error = ();
+    type Error = CapacityError;
     fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
         self.push_str(s)
     }
 }
 
 impl<const N: usize> uWrite for Vec<u8, N> {
-    type Error = ();
+    type Error = CapacityError;
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.extend_from_slice(s.as_bytes())
+    }
+}
+
+impl uWrite for StringView {
+    type Error = CapacityError;
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.push_str(s)
+    }
+}
+
+impl uWrite for VecView<u8> {
+    type Error = CapacityError;
     fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
         self.extend_from_slice(s.as_bytes())
     }
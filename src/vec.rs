@@ -1,6 +1,15 @@
-use core::{fmt, hash, iter::FromIterator, mem::MaybeUninit, ops, ptr, slice};
+use core::{
+    fmt, hash,
+    iter::{FromIterator, FusedIterator},
+    mem::{self, MaybeUninit},
+    ops::{self, Bound, RangeBounds},
+    ptr::{self, NonNull},
+    slice,
+};
 use hash32;
 
+use crate::{CapacityError, TryFromIterator};
+
 /// A fixed capacity [`Vec`](https://doc.rust-lang.org/std/vec/struct.Vec.html)
 ///
 /// # Examples
@@ -30,9 +39,41 @@ use hash32;
 /// }
 /// assert_eq!(*vec, [7, 1, 2, 3]);
 /// ```
+#[repr(C)]
 pub struct Vec<T, const N: usize> {
+    // NOTE `len` must come first and the layout must stay in sync with `VecView`
+    len: usize,
     buffer: [MaybeUninit<T>; N],
+}
+
+/// A [`Vec`] with a capacity that is not part of its type
+///
+/// `VecView<T>` is to `Vec<T, N>` what `[T]` is to `[T; N]`: it's `!Sized` so it can only be used
+/// behind a reference, which is obtained with [`Vec::as_view`] or [`Vec::as_mut_view`]. This lets
+/// you write functions that work with vectors of any capacity without being generic over `N`,
+/// which avoids a monomorphized copy of the function per capacity.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::{Vec, VecView};
+///
+/// fn fill(buf: &mut VecView<u8>) {
+///     while buf.push(0xff).is_ok() {}
+/// }
+///
+/// let mut small: Vec<u8, 2> = Vec::new();
+/// let mut large: Vec<u8, 4> = Vec::new();
+/// fill(small.as_mut_view());
+/// fill(large.as_mut_view());
+///
+/// assert_eq!(small, [0xff; 2]);
+/// assert_eq!(large, [0xff; 4]);
+/// ```
+#[repr(C)]
+pub struct VecView<T> {
     len: usize,
+    buffer: [MaybeUninit<T>],
 }
 
 impl<T, const N: usize> Vec<T, N> {
@@ -57,8 +98,8 @@ impl<T, const N: usize> Vec<T, N> {
         crate::sealed::greater_than_0::<N>();
 
         Self {
-            buffer: [Self::INIT; N],
             len: 0,
+            buffer: [Self::INIT; N],
         }
     }
 
@@ -74,7 +115,7 @@ impl<T, const N: usize> Vec<T, N> {
     /// v.extend_from_slice(&[1, 2, 3]).unwrap();
     /// ```
     #[inline]
-    pub fn from_slice(other: &[T]) -> Result<Self, ()>
+    pub fn from_slice(other: &[T]) -> Result<Self, CapacityError>
     where
         T: Clone,
     {
@@ -83,6 +124,50 @@ impl<T, const N: usize> Vec<T, N> {
         Ok(v)
     }
 
+    /// Returns a view of this vector that doesn't carry its capacity in its type
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{Vec, VecView};
+    ///
+    /// let vec: Vec<u8, 8> = Vec::from_slice(&[1, 2, 3]).unwrap();
+    /// let view: &VecView<u8> = vec.as_view();
+    /// assert_eq!(view, &[1, 2, 3]);
+    /// assert_eq!(view.capacity(), 8);
+    /// ```
+    #[inline]
+    pub fn as_view(&self) -> &VecView<T> {
+        // SAFETY: `Vec` and `VecView` are `#[repr(C)]` with the same fields, in the same order,
+        // and the `N` elements of the buffer become the length of the trailing slice
+        unsafe {
+            &*(ptr::slice_from_raw_parts(self as *const Self as *const MaybeUninit<T>, N)
+                as *const VecView<T>)
+        }
+    }
+
+    /// Returns a mutable view of this vector that doesn't carry its capacity in its type
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{Vec, VecView};
+    ///
+    /// let mut vec: Vec<u8, 8> = Vec::new();
+    /// let view: &mut VecView<u8> = vec.as_mut_view();
+    /// view.push(1).unwrap();
+    /// view.extend_from_slice(&[2, 3]).unwrap();
+    /// assert_eq!(vec, [1, 2, 3]);
+    /// ```
+    #[inline]
+    pub fn as_mut_view(&mut self) -> &mut VecView<T> {
+        // SAFETY: see `as_view`
+        unsafe {
+            &mut *(ptr::slice_from_raw_parts_mut(self as *mut Self as *mut MaybeUninit<T>, N)
+                as *mut VecView<T>)
+        }
+    }
+
     /// Clones a vec into a new vec
     pub(crate) fn clone(&self) -> Self
     where
@@ -105,9 +190,7 @@ impl<T, const N: usize> Vec<T, N> {
     /// assert_eq!(buffer.as_slice(), &[1, 2, 3, 5, 8]);
     /// ```
     pub fn as_slice(&self) -> &[T] {
-        // NOTE(unsafe) avoid bound checks in the slicing operation
-        // &buffer[..self.len]
-        unsafe { slice::from_raw_parts(self.buffer.as_ptr() as *const T, self.len) }
+        self.as_view().as_slice()
     }
 
     /// Returns the contents of the vector as an array of length `M` if the length
@@ -149,9 +232,7 @@ impl<T, const N: usize> Vec<T, N> {
     /// assert_eq!(buffer.as_slice(), &[9, 2, 3, 5, 8]);
     /// ```
     pub(crate) fn as_mut_slice(&mut self) -> &mut [T] {
-        // NOTE(unsafe) avoid bound checks in the slicing operation
-        // &mut buffer[..self.len]
-        unsafe { slice::from_raw_parts_mut(self.buffer.as_mut_ptr() as *mut T, self.len) }
+        self.as_mut_view().as_mut_slice()
     }
 
     /// Returns the maximum number of elements the vector can hold.
@@ -161,7 +242,7 @@ impl<T, const N: usize> Vec<T, N> {
 
     /// Clears the vector, removing all values.
     pub fn clear(&mut self) {
-        self.truncate(0);
+        self.as_mut_view().clear()
     }
 
     /// Extends the vec from an iterator.
@@ -173,9 +254,7 @@ impl<T, const N: usize> Vec<T, N> {
     where
         I: IntoIterator<Item = T>,
     {
-        for elem in iter {
-            self.push(elem).ok().unwrap()
-        }
+        self.as_mut_view().extend(iter)
     }
 
     /// Clones and appends all elements in a slice to the `Vec`.
@@ -193,42 +272,23 @@ impl<T, const N: usize> Vec<T, N> {
     /// vec.extend_from_slice(&[2, 3, 4]).unwrap();
     /// assert_eq!(*vec, [1, 2, 3, 4]);
     /// ```
-    pub fn extend_from_slice(&mut self, other: &[T]) -> Result<(), ()>
+    pub fn extend_from_slice(&mut self, other: &[T]) -> Result<(), CapacityError>
     where
         T: Clone,
     {
-        if self.len + other.len() > self.capacity() {
-            // won't fit in the `Vec`; don't modify anything and return an error
-            Err(())
-        } else {
-            for elem in other {
-                unsafe {
-                    self.push_unchecked(elem.clone());
-                }
-            }
-            Ok(())
-        }
+        self.as_mut_view().extend_from_slice(other)
     }
 
     /// Removes the last element from a vector and returns it, or `None` if it's empty
     pub fn pop(&mut self) -> Option<T> {
-        if self.len != 0 {
-            Some(unsafe { self.pop_unchecked() })
-        } else {
-            None
-        }
+        self.as_mut_view().pop()
     }
 
     /// Appends an `item` to the back of the collection
     ///
     /// Returns back the `item` if the vector is full
     pub fn push(&mut self, item: T) -> Result<(), T> {
-        if self.len < self.capacity() {
-            unsafe { self.push_unchecked(item) }
-            Ok(())
-        } else {
-            Err(item)
-        }
+        self.as_mut_view().push(item)
     }
 
     /// Removes the last element from a vector and returns it
@@ -237,10 +297,7 @@ impl<T, const N: usize> Vec<T, N> {
     ///
     /// This assumes the vec to have at least one element.
     pub(crate) unsafe fn pop_unchecked(&mut self) -> T {
-        debug_assert!(!self.as_slice().is_empty());
-
-        self.len -= 1;
-        (self.buffer.get_unchecked_mut(self.len).as_ptr() as *const T).read()
+        self.as_mut_view().pop_unchecked()
     }
 
     /// Appends an `item` to the back of the collection
@@ -249,25 +306,12 @@ impl<T, const N: usize> Vec<T, N> {
     ///
     /// This assumes the vec is not full.
     pub unsafe fn push_unchecked(&mut self, item: T) {
-        // NOTE(ptr::write) the memory slot that we are about to write to is uninitialized. We
-        // use `ptr::write` to avoid running `T`'s destructor on the uninitialized memory
-        debug_assert!(!self.is_full());
-
-        *self.buffer.get_unchecked_mut(self.len) = MaybeUninit::new(item);
-
-        self.len += 1;
+        self.as_mut_view().push_unchecked(item)
     }
 
     /// Shortens the vector, keeping the first `len` elements and dropping the rest.
     pub fn truncate(&mut self, len: usize) {
-        // drop any extra elements
-        while len < self.len {
-            // decrement len before the drop_in_place(), so a panic on Drop
-            // doesn't re-drop the just-failed value.
-            self.len -= 1;
-            let len = self.len;
-            unsafe { ptr::drop_in_place(self.as_mut_slice().get_unchecked_mut(len)) };
-        }
+        self.as_mut_view().truncate(len)
     }
 
     /// Resizes the Vec in-place so that len is equal to new_len.
@@ -277,23 +321,11 @@ impl<T, const N: usize> Vec<T, N> {
     /// new_len is less than len, the Vec is simply truncated.
     ///
     /// See also [`resize_default`](struct.Vec.html#method.resize_default).
-    pub fn resize(&mut self, new_len: usize, value: T) -> Result<(), ()>
+    pub fn resize(&mut self, new_len: usize, value: T) -> Result<(), CapacityError>
     where
         T: Clone,
     {
-        if new_len > self.capacity() {
-            return Err(());
-        }
-
-        if new_len > self.len {
-            while self.len < new_len {
-                self.push(value.clone()).ok();
-            }
-        } else {
-            self.truncate(new_len);
-        }
-
-        Ok(())
+        self.as_mut_view().resize(new_len, value)
     }
 
     /// Resizes the `Vec` in-place so that `len` is equal to `new_len`.
@@ -303,11 +335,11 @@ impl<T, const N: usize> Vec<T, N> {
     /// If `new_len` is less than `len`, the `Vec` is simply truncated.
     ///
     /// See also [`resize`](struct.Vec.html#method.resize).
-    pub fn resize_default(&mut self, new_len: usize) -> Result<(), ()>
+    pub fn resize_default(&mut self, new_len: usize) -> Result<(), CapacityError>
     where
         T: Clone + Default,
     {
-        self.resize(new_len, T::default())
+        self.as_mut_view().resize_default(new_len)
     }
 
     /// Forces the length of the vector to `new_len`.
@@ -400,9 +432,7 @@ impl<T, const N: usize> Vec<T, N> {
     /// Normally, here, one would use [`clear`] instead to correctly drop
     /// the contents and thus not leak memory.
     pub unsafe fn set_len(&mut self, new_len: usize) {
-        debug_assert!(new_len <= self.capacity());
-
-        self.len = new_len
+        self.as_mut_view().set_len(new_len)
     }
 
     /// Removes an element from the vector and returns it.
@@ -434,8 +464,7 @@ impl<T, const N: usize> Vec<T, N> {
     /// assert_eq!(&*v, ["baz", "qux"]);
     /// ```
     pub fn swap_remove(&mut self, index: usize) -> T {
-        assert!(index < self.len);
-        unsafe { self.swap_remove_unchecked(index) }
+        self.as_mut_view().swap_remove(index)
     }
 
     /// Removes an element from the vector and returns it.
@@ -466,125 +495,1104 @@ impl<T, const N: usize> Vec<T, N> {
     /// assert_eq!(&*v, ["baz", "qux"]);
     /// ```
     pub unsafe fn swap_remove_unchecked(&mut self, index: usize) -> T {
-        let length = self.len();
-        debug_assert!(index < length);
-        ptr::swap(
-            self.as_mut_slice().get_unchecked_mut(index),
-            self.as_mut_slice().get_unchecked_mut(length - 1),
-        );
-        self.pop_unchecked()
+        self.as_mut_view().swap_remove_unchecked(index)
     }
 
-    /// Returns true if the vec is full
-    #[inline]
-    pub fn is_full(&self) -> bool {
-        self.len == self.capacity()
+    /// Inserts an element at position `index` within the vector, shifting all
+    /// elements after it to the right.
+    ///
+    /// Returns back the `element` if the vector is full.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<u8, 5> = Vec::from_slice(&[1, 2, 3]).unwrap();
+    /// vec.insert(1, 4).unwrap();
+    /// assert_eq!(vec, [1, 4, 2, 3]);
+    /// vec.insert(4, 5).unwrap();
+    /// assert_eq!(vec, [1, 4, 2, 3, 5]);
+    /// assert_eq!(vec.insert(0, 6), Err(6));
+    /// ```
+    pub fn insert(&mut self, index: usize, element: T) -> Result<(), T> {
+        self.as_mut_view().insert(index, element)
     }
 
-    /// Returns `true` if `needle` is a prefix of the Vec.
+    /// Removes and returns the element at position `index` within the vector,
+    /// shifting all elements after it to the left.
     ///
-    /// Always returns `true` if `needle` is an empty slice.
+    /// Note: Because this shifts over the remaining elements, it has a
+    /// worst-case performance of *O*(n). If you don't need the order of
+    /// elements to be preserved, use [`swap_remove`] instead.
+    ///
+    /// [`swap_remove`]: Vec::swap_remove
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
     ///
     /// # Examples
     ///
     /// ```
     /// use heapless::Vec;
     ///
-    /// let v: Vec<_, 8> = Vec::from_slice(b"abc").unwrap();
-    /// assert_eq!(v.starts_with(b""), true);
-    /// assert_eq!(v.starts_with(b"ab"), true);
-    /// assert_eq!(v.starts_with(b"bc"), false);
+    /// let mut v: Vec<u8, 8> = Vec::from_slice(&[1, 2, 3]).unwrap();
+    /// assert_eq!(v.remove(1), 2);
+    /// assert_eq!(v, [1, 3]);
     /// ```
-    #[inline]
-    pub fn starts_with(&self, needle: &[T]) -> bool
-    where
-        T: PartialEq,
-    {
-        let n = needle.len();
-        self.len >= n && needle == &self[..n]
+    pub fn remove(&mut self, index: usize) -> T {
+        self.as_mut_view().remove(index)
     }
 
-    /// Returns `true` if `needle` is a suffix of the Vec.
+    /// Retains only the elements specified by the predicate.
     ///
-    /// Always returns `true` if `needle` is an empty slice.
+    /// In other words, remove all elements `e` for which `f(&e)` returns `false`.
+    /// This method operates in place, visiting each element exactly once in the
+    /// original order, and preserves the order of the retained elements.
     ///
     /// # Examples
     ///
     /// ```
     /// use heapless::Vec;
     ///
-    /// let v: Vec<_, 8> = Vec::from_slice(b"abc").unwrap();
-    /// assert_eq!(v.ends_with(b""), true);
-    /// assert_eq!(v.ends_with(b"ab"), false);
-    /// assert_eq!(v.ends_with(b"bc"), true);
+    /// let mut vec: Vec<_, 8> = Vec::from_slice(&[1, 2, 3, 4]).unwrap();
+    /// vec.retain(|&x| x % 2 == 0);
+    /// assert_eq!(vec, [2, 4]);
     /// ```
-    #[inline]
-    pub fn ends_with(&self, needle: &[T]) -> bool
+    ///
+    /// Because the elements are visited exactly once in the original order,
+    /// external state may be used to decide which elements to keep.
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<_, 8> = Vec::from_slice(&[1, 2, 3, 4, 5]).unwrap();
+    /// let keep = [false, true, true, false, true];
+    /// let mut iter = keep.iter();
+    /// vec.retain(|_| *iter.next().unwrap());
+    /// assert_eq!(vec, [2, 3, 5]);
+    /// ```
+    pub fn retain<F>(&mut self, f: F)
     where
-        T: PartialEq,
+        F: FnMut(&T) -> bool,
     {
-        let (v, n) = (self.len(), needle.len());
-        v >= n && needle == &self[v - n..]
-    }
-}
-
-// Trait implementations
-
-impl<T, const N: usize> Default for Vec<T, N> {
-    fn default() -> Self {
-        Self::new()
+        self.as_mut_view().retain(f)
     }
-}
 
-impl<T, const N: usize> fmt::Debug for Vec<T, N>
-where
-    T: fmt::Debug,
-{
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        <[T] as fmt::Debug>::fmt(self, f)
+    /// Retains only the elements specified by the predicate, passing a mutable reference to it.
+    ///
+    /// In other words, remove all elements `e` such that `f(&mut e)` returns `false`.
+    /// This method operates in place, visiting each element exactly once in the
+    /// original order, and preserves the order of the retained elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<_, 8> = Vec::from_slice(&[1, 2, 3, 4]).unwrap();
+    /// vec.retain_mut(|x| if *x <= 3 {
+    ///     *x += 1;
+    ///     true
+    /// } else {
+    ///     false
+    /// });
+    /// assert_eq!(vec, [2, 3, 4]);
+    /// ```
+    pub fn retain_mut<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        self.as_mut_view().retain_mut(f)
     }
-}
 
-impl<const N: usize> fmt::Write for Vec<u8, N> {
-    fn write_str(&mut self, s: &str) -> fmt::Result {
-        match self.extend_from_slice(s.as_bytes()) {
-            Ok(()) => Ok(()),
-            Err(_) => Err(fmt::Error),
-        }
+    /// Removes the specified range from the vector in bulk, returning all
+    /// removed elements as an iterator. If the iterator is dropped before
+    /// being fully consumed, it drops the remaining removed elements.
+    ///
+    /// The returned iterator keeps a mutable borrow on the vector to optimize
+    /// its implementation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    ///
+    /// # Leaking
+    ///
+    /// If the returned iterator goes out of scope without being dropped (due to
+    /// [`mem::forget`](core::mem::forget), for example), the vector may have lost and leaked
+    /// elements arbitrarily, including elements outside the range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut v: Vec<_, 8> = Vec::from_slice(&[1, 2, 3]).unwrap();
+    /// let u: Vec<_, 8> = v.drain(1..).collect();
+    /// assert_eq!(v, &[1]);
+    /// assert_eq!(u, &[2, 3]);
+    ///
+    /// // A full range clears the vector, like `clear()` does.
+    /// v.drain(..);
+    /// assert_eq!(v, &[]);
+    /// ```
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.as_mut_view().drain(range)
     }
-}
 
-impl<T, const N: usize> Drop for Vec<T, N> {
-    fn drop(&mut self) {
-        // We drop each element used in the vector by turning into a &mut[T]
-        unsafe {
-            ptr::drop_in_place(self.as_mut_slice());
-        }
+    /// Removes consecutive repeated elements in the vector according to the
+    /// [`PartialEq`] trait implementation.
+    ///
+    /// If the vector is sorted, this removes all duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<_, 8> = Vec::from_slice(&[1, 2, 2, 3, 2]).unwrap();
+    /// vec.dedup();
+    /// assert_eq!(vec, [1, 2, 3, 2]);
+    /// ```
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.as_mut_view().dedup()
     }
-}
 
-impl<T, const N: usize> Extend<T> for Vec<T, N> {
-    fn extend<I>(&mut self, iter: I)
+    /// Removes all but the first of consecutive elements in the vector that resolve to the same
+    /// key.
+    ///
+    /// If the vector is sorted, this removes all duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<_, 8> = Vec::from_slice(&[10, 20, 21, 30, 20]).unwrap();
+    /// vec.dedup_by_key(|i| *i / 10);
+    /// assert_eq!(vec, [10, 20, 30, 20]);
+    /// ```
+    pub fn dedup_by_key<F, K>(&mut self, key: F)
     where
-        I: IntoIterator<Item = T>,
+        F: FnMut(&mut T) -> K,
+        K: PartialEq,
     {
-        self.extend(iter)
+        self.as_mut_view().dedup_by_key(key)
     }
-}
 
-impl<'a, T, const N: usize> Extend<&'a T> for Vec<T, N>
-where
-    T: 'a + Copy,
-{
-    fn extend<I>(&mut self, iter: I)
+    /// Removes all but the first of consecutive elements in the vector satisfying a given equality
+    /// relation.
+    ///
+    /// The `same_bucket` function is passed references to two elements from the vector and
+    /// must determine if the elements compare equal. The elements are passed in opposite order
+    /// from their order in the slice, so if `same_bucket(a, b)` returns `true`, `a` is removed.
+    ///
+    /// If the vector is sorted, this removes all duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<_, 8> = Vec::from_slice(&["foo", "bar", "Bar", "baz", "bar"]).unwrap();
+    /// vec.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    /// assert_eq!(vec, ["foo", "bar", "baz", "bar"]);
+    /// ```
+    pub fn dedup_by<F>(&mut self, same_bucket: F)
     where
-        I: IntoIterator<Item = &'a T>,
+        F: FnMut(&mut T, &mut T) -> bool,
     {
-        self.extend(iter.into_iter().cloned())
+        self.as_mut_view().dedup_by(same_bucket)
     }
-}
 
-impl<T, const N: usize> hash::Hash for Vec<T, N>
+    /// Splits the collection into two at the given index.
+    ///
+    /// Returns a new vector containing the elements in the range `[at, len)`. After
+    /// the call, the original vector will be left containing the elements `[0, at)`.
+    ///
+    /// Returns a [`CapacityError`], and leaves `self` untouched, if the split off elements don't fit
+    /// in a `Vec` of capacity `M`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<_, 8> = Vec::from_slice(&[1, 2, 3]).unwrap();
+    /// let vec2: Vec<_, 4> = vec.split_off(1).unwrap();
+    /// assert_eq!(vec, [1]);
+    /// assert_eq!(vec2, [2, 3]);
+    ///
+    /// let mut vec: Vec<_, 8> = Vec::from_slice(&[1, 2, 3]).unwrap();
+    /// assert!(vec.split_off::<1>(1).is_err());
+    /// assert_eq!(vec, [1, 2, 3]);
+    /// ```
+    pub fn split_off<const M: usize>(&mut self, at: usize) -> Result<Vec<T, M>, CapacityError> {
+        self.as_mut_view().split_off::<M>(at)
+    }
+
+    /// Copies elements from `src` range to the end of the vector.
+    ///
+    /// Returns a [`CapacityError`], and leaves the vector untouched, if the copied elements don't
+    /// fit in the remaining capacity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<_, 10> = Vec::from_slice(&[0, 1, 2, 3, 4]).unwrap();
+    ///
+    /// vec.extend_from_within(2..).unwrap();
+    /// assert_eq!(vec, [0, 1, 2, 3, 4, 2, 3, 4]);
+    ///
+    /// vec.extend_from_within(..2).unwrap();
+    /// assert_eq!(vec, [0, 1, 2, 3, 4, 2, 3, 4, 0, 1]);
+    ///
+    /// assert!(vec.extend_from_within(4..5).is_err());
+    /// ```
+    pub fn extend_from_within<R>(&mut self, src: R) -> Result<(), CapacityError>
+    where
+        R: RangeBounds<usize>,
+        T: Clone,
+    {
+        self.as_mut_view().extend_from_within(src)
+    }
+
+    /// Returns true if the vec is full
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.as_view().is_full()
+    }
+
+    /// Returns `true` if `needle` is a prefix of the Vec.
+    ///
+    /// Always returns `true` if `needle` is an empty slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let v: Vec<_, 8> = Vec::from_slice(b"abc").unwrap();
+    /// assert_eq!(v.starts_with(b""), true);
+    /// assert_eq!(v.starts_with(b"ab"), true);
+    /// assert_eq!(v.starts_with(b"bc"), false);
+    /// ```
+    #[inline]
+    pub fn starts_with(&self, needle: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        self.as_view().starts_with(needle)
+    }
+
+    /// Returns `true` if `needle` is a suffix of the Vec.
+    ///
+    /// Always returns `true` if `needle` is an empty slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let v: Vec<_, 8> = Vec::from_slice(b"abc").unwrap();
+    /// assert_eq!(v.ends_with(b""), true);
+    /// assert_eq!(v.ends_with(b"ab"), false);
+    /// assert_eq!(v.ends_with(b"bc"), true);
+    /// ```
+    #[inline]
+    pub fn ends_with(&self, needle: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        self.as_view().ends_with(needle)
+    }
+}
+
+impl<T> VecView<T> {
+    /// Returns the maximum number of elements the vector can hold.
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Extracts a slice containing the entire vector.
+    ///
+    /// Equivalent to `&s[..]`.
+    pub fn as_slice(&self) -> &[T] {
+        // NOTE(unsafe) avoid bound checks in the slicing operation
+        // &buffer[..self.len]
+        unsafe { slice::from_raw_parts(self.buffer.as_ptr() as *const T, self.len) }
+    }
+
+    /// Returns a raw pointer to the start of the buffer, valid for the whole capacity.
+    fn buffer_ptr(&self) -> *const T {
+        self.buffer.as_ptr() as *const T
+    }
+
+    /// Returns a raw mutable pointer to the start of the buffer, valid for the whole capacity.
+    fn buffer_mut_ptr(&mut self) -> *mut T {
+        self.buffer.as_mut_ptr() as *mut T
+    }
+
+    /// Extracts a mutable slice containing the entire vector.
+    ///
+    /// Equivalent to `&s[..]`.
+    pub(crate) fn as_mut_slice(&mut self) -> &mut [T] {
+        // NOTE(unsafe) avoid bound checks in the slicing operation
+        // &mut buffer[..self.len]
+        unsafe { slice::from_raw_parts_mut(self.buffer.as_mut_ptr() as *mut T, self.len) }
+    }
+
+    /// Clears the vector, removing all values.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Extends the vec from an iterator.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the vec cannot hold all elements of the iterator.
+    pub fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for elem in iter {
+            self.push(elem).ok().unwrap()
+        }
+    }
+
+    /// Clones and appends all elements in a slice to the `Vec`.
+    ///
+    /// Iterates over the slice `other`, clones each element, and then appends
+    /// it to this `Vec`. The `other` vector is traversed in-order.
+    pub fn extend_from_slice(&mut self, other: &[T]) -> Result<(), CapacityError>
+    where
+        T: Clone,
+    {
+        if self.len + other.len() > self.capacity() {
+            // won't fit in the `Vec`; don't modify anything and return an error
+            Err(CapacityError::new(()))
+        } else {
+            for elem in other {
+                unsafe {
+                    self.push_unchecked(elem.clone());
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// Removes the last element from a vector and returns it, or `None` if it's empty
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len != 0 {
+            Some(unsafe { self.pop_unchecked() })
+        } else {
+            None
+        }
+    }
+
+    /// Appends an `item` to the back of the collection
+    ///
+    /// Returns back the `item` if the vector is full
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        if self.len < self.capacity() {
+            unsafe { self.push_unchecked(item) }
+            Ok(())
+        } else {
+            Err(item)
+        }
+    }
+
+    /// Removes the last element from a vector and returns it
+    ///
+    /// # Safety
+    ///
+    /// This assumes the vec to have at least one element.
+    pub(crate) unsafe fn pop_unchecked(&mut self) -> T {
+        debug_assert!(!self.as_slice().is_empty());
+
+        self.len -= 1;
+        self.buffer.get_unchecked_mut(self.len).as_ptr().read()
+    }
+
+    /// Appends an `item` to the back of the collection
+    ///
+    /// # Safety
+    ///
+    /// This assumes the vec is not full.
+    pub unsafe fn push_unchecked(&mut self, item: T) {
+        // NOTE(ptr::write) the memory slot that we are about to write to is uninitialized. We
+        // use `ptr::write` to avoid running `T`'s destructor on the uninitialized memory
+        debug_assert!(!self.is_full());
+
+        *self.buffer.get_unchecked_mut(self.len) = MaybeUninit::new(item);
+
+        self.len += 1;
+    }
+
+    /// Shortens the vector, keeping the first `len` elements and dropping the rest.
+    pub fn truncate(&mut self, len: usize) {
+        // drop any extra elements
+        while len < self.len {
+            // decrement len before the drop_in_place(), so a panic on Drop
+            // doesn't re-drop the just-failed value.
+            self.len -= 1;
+            let len = self.len;
+            unsafe { ptr::drop_in_place(self.buffer_mut_ptr().add(len)) };
+        }
+    }
+
+    /// Resizes the Vec in-place so that len is equal to new_len.
+    ///
+    /// If new_len is greater than len, the Vec is extended by the
+    /// difference, with each additional slot filled with value. If
+    /// new_len is less than len, the Vec is simply truncated.
+    ///
+    /// See also [`resize_default`](struct.Vec.html#method.resize_default).
+    pub fn resize(&mut self, new_len: usize, value: T) -> Result<(), CapacityError>
+    where
+        T: Clone,
+    {
+        if new_len > self.capacity() {
+            return Err(CapacityError::new(()));
+        }
+
+        if new_len > self.len {
+            while self.len < new_len {
+                self.push(value.clone()).ok();
+            }
+        } else {
+            self.truncate(new_len);
+        }
+
+        Ok(())
+    }
+
+    /// Resizes the `Vec` in-place so that `len` is equal to `new_len`.
+    ///
+    /// If `new_len` is greater than `len`, the `Vec` is extended by the
+    /// difference, with each additional slot filled with `Default::default()`.
+    /// If `new_len` is less than `len`, the `Vec` is simply truncated.
+    ///
+    /// See also [`resize`](struct.Vec.html#method.resize).
+    pub fn resize_default(&mut self, new_len: usize) -> Result<(), CapacityError>
+    where
+        T: Clone + Default,
+    {
+        self.resize(new_len, T::default())
+    }
+
+    /// Forces the length of the vector to `new_len`.
+    ///
+    /// This is a low-level operation that maintains none of the normal
+    /// invariants of the type. Normally changing the length of a vector
+    /// is done using one of the safe operations instead, such as
+    /// [`truncate`], [`resize`], [`extend`], or [`clear`].
+    ///
+    /// [`truncate`]: #method.truncate
+    /// [`resize`]: #method.resize
+    /// [`extend`]: https://doc.rust-lang.org/stable/core/iter/trait.Extend.html#tymethod.extend
+    /// [`clear`]: #method.clear
+    ///
+    /// # Safety
+    ///
+    /// - `new_len` must be less than or equal to [`capacity()`].
+    /// - The elements at `old_len..new_len` must be initialized.
+    ///
+    /// [`capacity()`]: #method.capacity
+    pub unsafe fn set_len(&mut self, new_len: usize) {
+        debug_assert!(new_len <= self.capacity());
+
+        self.len = new_len
+    }
+
+    /// Removes an element from the vector and returns it.
+    ///
+    /// The removed element is replaced by the last element of the vector.
+    ///
+    /// This does not preserve ordering, but is O(1).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        assert!(index < self.len);
+        unsafe { self.swap_remove_unchecked(index) }
+    }
+
+    /// Removes an element from the vector and returns it.
+    ///
+    /// The removed element is replaced by the last element of the vector.
+    ///
+    /// This does not preserve ordering, but is O(1).
+    ///
+    /// # Safety
+    ///
+    ///  Assumes `index` within bounds.
+    pub unsafe fn swap_remove_unchecked(&mut self, index: usize) -> T {
+        let length = self.len();
+        debug_assert!(index < length);
+        // both pointers must derive from the same borrow of the buffer
+        let ptr = self.as_mut_ptr();
+        ptr::swap(ptr.add(index), ptr.add(length - 1));
+        self.pop_unchecked()
+    }
+
+    /// Inserts an element at position `index` within the vector, shifting all
+    /// elements after it to the right.
+    ///
+    /// Returns back the `element` if the vector is full.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, element: T) -> Result<(), T> {
+        let len = self.len();
+        if index > len {
+            panic!(
+                "insertion index (is {}) should be <= len (is {})",
+                index, len
+            );
+        }
+
+        // check there's space for the new element
+        if self.is_full() {
+            return Err(element);
+        }
+
+        unsafe {
+            // infallible
+            // The spot to put the new value
+            {
+                let p = self.buffer_mut_ptr().add(index);
+                // Shift everything over to make space. (Duplicating the
+                // `index`th element into two consecutive places.)
+                ptr::copy(p, p.offset(1), len - index);
+                // Write it in, overwriting the first copy of the `index`th
+                // element.
+                ptr::write(p, element);
+            }
+            self.set_len(len + 1);
+        }
+
+        Ok(())
+    }
+
+    /// Removes and returns the element at position `index` within the vector,
+    /// shifting all elements after it to the left.
+    ///
+    /// Note: Because this shifts over the remaining elements, it has a
+    /// worst-case performance of *O*(n). If you don't need the order of
+    /// elements to be preserved, use [`swap_remove`] instead.
+    ///
+    /// [`swap_remove`]: Vec::swap_remove
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        let len = self.len();
+        if index >= len {
+            panic!("removal index (is {}) should be < len (is {})", index, len);
+        }
+        unsafe {
+            // infallible
+            let ret;
+            {
+                // the place we are taking from.
+                let ptr = self.buffer_mut_ptr().add(index);
+                // copy it out, unsafely having a copy of the value on
+                // the stack and in the vector at the same time.
+                ret = ptr::read(ptr);
+
+                // Shift everything down to fill in that spot.
+                ptr::copy(ptr.offset(1), ptr, len - index - 1);
+            }
+            self.set_len(len - 1);
+            ret
+        }
+    }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// In other words, remove all elements `e` for which `f(&e)` returns `false`.
+    /// This method operates in place, visiting each element exactly once in the
+    /// original order, and preserves the order of the retained elements.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.retain_mut(|elem| f(elem));
+    }
+
+    /// Retains only the elements specified by the predicate, passing a mutable reference to it.
+    ///
+    /// In other words, remove all elements `e` such that `f(&mut e)` returns `false`.
+    /// This method operates in place, visiting each element exactly once in the
+    /// original order, and preserves the order of the retained elements.
+    pub fn retain_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let original_len = self.len();
+        // Avoid double drop if the drop guard is not executed,
+        // since we may make some holes during the process.
+        unsafe { self.set_len(0) };
+
+        // Vec: [Kept, Kept, Hole, Hole, Hole, Hole, Unchecked, Unchecked]
+        //      |<-              processed len   ->| ^- next to check
+        //                  |<-  deleted cnt     ->|
+        //      |<-              original_len                          ->|
+        // Kept: Elements which predicate returns true on.
+        // Hole: Moved or dropped element slot.
+        // Unchecked: Unchecked valid elements.
+        //
+        // This drop guard will be invoked when predicate or `drop` of element panicked.
+        // It shifts unchecked elements to cover holes and `set_len` to the correct length.
+        // In cases when predicate and `drop` never panick, it will be optimized out.
+        struct BackshiftOnDrop<'a, T> {
+            v: &'a mut VecView<T>,
+            processed_len: usize,
+            deleted_cnt: usize,
+            original_len: usize,
+        }
+
+        impl<T> Drop for BackshiftOnDrop<'_, T> {
+            fn drop(&mut self) {
+                if self.deleted_cnt > 0 {
+                    // SAFETY: Trailing unchecked items must be valid since we never touch them.
+                    unsafe {
+                        // both pointers must derive from the same borrow of the buffer
+                        let p = self.v.buffer_mut_ptr();
+                        ptr::copy(
+                            p.add(self.processed_len),
+                            p.add(self.processed_len - self.deleted_cnt),
+                            self.original_len - self.processed_len,
+                        );
+                    }
+                }
+                // SAFETY: After filling holes, all items are in contiguous memory.
+                unsafe {
+                    self.v.set_len(self.original_len - self.deleted_cnt);
+                }
+            }
+        }
+
+        let mut g = BackshiftOnDrop {
+            v: self,
+            processed_len: 0,
+            deleted_cnt: 0,
+            original_len,
+        };
+
+        fn process_loop<F, T, const DELETED: bool>(
+            original_len: usize,
+            f: &mut F,
+            g: &mut BackshiftOnDrop<'_, T>,
+        ) where
+            F: FnMut(&mut T) -> bool,
+        {
+            while g.processed_len != original_len {
+                let p = g.v.buffer_mut_ptr();
+                // SAFETY: Unchecked element must be valid.
+                let cur = unsafe { &mut *p.add(g.processed_len) };
+                if !f(cur) {
+                    // Advance early to avoid double drop if `drop_in_place` panicked.
+                    g.processed_len += 1;
+                    g.deleted_cnt += 1;
+                    // SAFETY: We never touch this element again after dropped.
+                    unsafe { ptr::drop_in_place(cur) };
+                    // We already advanced the counter.
+                    if DELETED {
+                        continue;
+                    } else {
+                        break;
+                    }
+                }
+                if DELETED {
+                    // SAFETY: `deleted_cnt` > 0, so the hole slot must not overlap with current element.
+                    // We use copy for move, and never touch this element again.
+                    unsafe {
+                        let hole_slot = p.add(g.processed_len - g.deleted_cnt);
+                        ptr::copy_nonoverlapping(cur, hole_slot, 1);
+                    }
+                }
+                g.processed_len += 1;
+            }
+        }
+
+        // Stage 1: Nothing was deleted.
+        process_loop::<F, T, false>(original_len, &mut f, &mut g);
+
+        // Stage 2: Some elements were deleted.
+        process_loop::<F, T, true>(original_len, &mut f, &mut g);
+
+        // All item are processed. This can be optimized to `set_len` by LLVM.
+        drop(g);
+    }
+
+    /// Removes the specified range from the vector in bulk, returning all
+    /// removed elements as an iterator. If the iterator is dropped before
+    /// being fully consumed, it drops the remaining removed elements.
+    ///
+    /// The returned iterator keeps a mutable borrow on the vector to optimize
+    /// its implementation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    ///
+    /// # Leaking
+    ///
+    /// If the returned iterator goes out of scope without being dropped (due to
+    /// [`mem::forget`](core::mem::forget), for example), the vector may have lost and leaked
+    /// elements arbitrarily, including elements outside the range.
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T>
+    where
+        R: RangeBounds<usize>,
+    {
+        // Memory safety
+        //
+        // When the Drain is first created, it shortens the length of
+        // the source vector to make sure no uninitialized or moved-from elements
+        // are accessible at all if the Drain's destructor never gets to run.
+        //
+        // Drain will ptr::read out the values to remove.
+        // When finished, remaining tail of the vec is copied back to cover
+        // the hole, and the vector length is restored to the new length.
+        let len = self.len();
+        let ops::Range { start, end } = range_of(range, len);
+
+        unsafe {
+            // set self.vec length's to start, to be safe in case Drain is leaked
+            self.set_len(start);
+            let vec = NonNull::from(self);
+            // NOTE derive the slice from `vec` so that the `Drain` can later access the whole
+            // vector through `vec` without invalidating the iterator
+            let buffer = ptr::addr_of!((*vec.as_ptr()).buffer) as *const T;
+            let range_slice = slice::from_raw_parts(buffer.add(start), end - start);
+            Drain {
+                tail_start: end,
+                tail_len: len - end,
+                iter: range_slice.iter(),
+                vec,
+            }
+        }
+    }
+
+    /// Removes consecutive repeated elements in the vector according to the
+    /// [`PartialEq`] trait implementation.
+    ///
+    /// If the vector is sorted, this removes all duplicates.
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b)
+    }
+
+    /// Removes all but the first of consecutive elements in the vector that resolve to the same
+    /// key.
+    ///
+    /// If the vector is sorted, this removes all duplicates.
+    pub fn dedup_by_key<F, K>(&mut self, mut key: F)
+    where
+        F: FnMut(&mut T) -> K,
+        K: PartialEq,
+    {
+        self.dedup_by(|a, b| key(a) == key(b))
+    }
+
+    /// Removes all but the first of consecutive elements in the vector satisfying a given equality
+    /// relation.
+    ///
+    /// The `same_bucket` function is passed references to two elements from the vector and
+    /// must determine if the elements compare equal. The elements are passed in opposite order
+    /// from their order in the slice, so if `same_bucket(a, b)` returns `true`, `a` is removed.
+    ///
+    /// If the vector is sorted, this removes all duplicates.
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        let len = self.len();
+        if len <= 1 {
+            return;
+        }
+
+        /* INVARIANT: vec.len() > read >= write > write-1 >= 0 */
+        struct FillGapOnDrop<'a, T> {
+            /* Offset of the element we want to check if it is duplicate */
+            read: usize,
+
+            /* Offset of the place where we want to place the non-duplicate
+             * when we find it. */
+            write: usize,
+
+            /* The Vec that would need correction if `same_bucket` panicked */
+            vec: &'a mut VecView<T>,
+        }
+
+        impl<'a, T> Drop for FillGapOnDrop<'a, T> {
+            fn drop(&mut self) {
+                /* This code gets executed when `same_bucket` panics */
+
+                /* SAFETY: invariant guarantees that `read - write`
+                 * and `len - read` never overflow and that the copy is always
+                 * in-bounds. */
+                unsafe {
+                    let ptr = self.vec.buffer_mut_ptr();
+                    let len = self.vec.len();
+
+                    /* How many items were left when `same_bucket` panicked.
+                     * Basically vec[read..].len() */
+                    let items_left = len.wrapping_sub(self.read);
+
+                    /* Pointer to first item in vec[write..write+items_left] slice */
+                    let dropped_ptr = ptr.add(self.write);
+                    /* Pointer to first item in vec[read..] slice */
+                    let valid_ptr = ptr.add(self.read);
+
+                    /* Copy `vec[read..]` to `vec[write..write+items_left]`.
+                     * The slices can overlap, so `copy_nonoverlapping` cannot be used */
+                    ptr::copy(valid_ptr, dropped_ptr, items_left);
+
+                    /* How many items have been already dropped
+                     * Basically vec[read..write].len() */
+                    let dropped = self.read.wrapping_sub(self.write);
+
+                    self.vec.set_len(len - dropped);
+                }
+            }
+        }
+
+        let mut gap = FillGapOnDrop {
+            read: 1,
+            write: 1,
+            vec: self,
+        };
+        let ptr = gap.vec.buffer_mut_ptr();
+
+        /* Drop items while going through Vec, it should be more efficient than
+         * doing slice partition_dedup + truncate */
+
+        /* SAFETY: Because of the invariant, read_ptr, prev_ptr and write_ptr
+         * are always in-bounds and read_ptr never aliases prev_ptr */
+        unsafe {
+            while gap.read < len {
+                let read_ptr = ptr.add(gap.read);
+                let prev_ptr = ptr.add(gap.write.wrapping_sub(1));
+
+                if same_bucket(&mut *read_ptr, &mut *prev_ptr) {
+                    // Increase `gap.read` now since the drop may panic.
+                    gap.read += 1;
+                    /* We have found duplicate, drop it in-place */
+                    ptr::drop_in_place(read_ptr);
+                } else {
+                    let write_ptr = ptr.add(gap.write);
+
+                    /* Because `read_ptr` can be equal to `write_ptr`, we either
+                     * have to use `copy` or conditional `copy_nonoverlapping`.
+                     * Looks like the first option is faster. */
+                    ptr::copy(read_ptr, write_ptr, 1);
+
+                    /* We have filled that place, so go further */
+                    gap.write += 1;
+                    gap.read += 1;
+                }
+            }
+
+            /* Technically we could let `gap` clean up with its Drop, but
+             * when `same_bucket` is guaranteed to not panic, this bloats a little
+             * the codegen, so we just do it manually */
+            gap.vec.set_len(gap.write);
+            mem::forget(gap);
+        }
+    }
+
+    /// Splits the collection into two at the given index.
+    ///
+    /// Returns a new vector containing the elements in the range `[at, len)`. After
+    /// the call, the original vector will be left containing the elements `[0, at)`.
+    ///
+    /// Returns a [`CapacityError`], and leaves `self` untouched, if the split off elements don't fit
+    /// in a `Vec` of capacity `M`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    pub fn split_off<const M: usize>(&mut self, at: usize) -> Result<Vec<T, M>, CapacityError> {
+        let len = self.len();
+        if at > len {
+            panic!("`at` split index (is {}) should be <= len (is {})", at, len);
+        }
+
+        let other_len = len - at;
+        if other_len > M {
+            return Err(CapacityError::new(()));
+        }
+
+        let mut other = Vec::new();
+
+        // SAFETY: the `other_len` elements are moved (not copied) into `other`, which has room
+        // for them; `self` forgets about them by shrinking its length.
+        unsafe {
+            self.set_len(at);
+            ptr::copy_nonoverlapping(
+                self.buffer_ptr().add(at),
+                other.as_mut_view().buffer_mut_ptr(),
+                other_len,
+            );
+            other.set_len(other_len);
+        }
+
+        Ok(other)
+    }
+
+    /// Copies elements from `src` range to the end of the vector.
+    ///
+    /// Returns a [`CapacityError`], and leaves the vector untouched, if the copied elements don't
+    /// fit in the remaining capacity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    pub fn extend_from_within<R>(&mut self, src: R) -> Result<(), CapacityError>
+    where
+        R: RangeBounds<usize>,
+        T: Clone,
+    {
+        let ops::Range { start, end } = range_of(src, self.len());
+
+        if self.len() + (end - start) > self.capacity() {
+            // won't fit in the `Vec`; don't modify anything and return an error
+            return Err(CapacityError::new(()));
+        }
+
+        for i in start..end {
+            // NOTE(unsafe) `i` is in bounds and there's room for one more element; a panic in
+            // `clone` leaves the vector with the elements pushed so far
+            unsafe {
+                let elem = self.get_unchecked(i).clone();
+                self.push_unchecked(elem);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if the vec is full
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    /// Returns `true` if `needle` is a prefix of the Vec.
+    ///
+    /// Always returns `true` if `needle` is an empty slice.
+    pub fn starts_with(&self, needle: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        let n = needle.len();
+        self.len >= n && needle == &self[..n]
+    }
+
+    /// Returns `true` if `needle` is a suffix of the Vec.
+    ///
+    /// Always returns `true` if `needle` is an empty slice.
+    pub fn ends_with(&self, needle: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        let (v, n) = (self.len(), needle.len());
+        v >= n && needle == &self[v - n..]
+    }
+}
+
+// Trait implementations
+
+impl<T, const N: usize> Default for Vec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for Vec<T, N>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <[T] as fmt::Debug>::fmt(self, f)
+    }
+}
+
+impl<const N: usize> fmt::Write for Vec<u8, N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        match self.extend_from_slice(s.as_bytes()) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for Vec<T, N> {
+    fn drop(&mut self) {
+        // We drop each element used in the vector by turning into a &mut[T]
+        unsafe {
+            ptr::drop_in_place(self.as_mut_slice());
+        }
+    }
+}
+
+impl<T, const N: usize> Extend<T> for Vec<T, N> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.extend(iter)
+    }
+}
+
+impl<'a, T, const N: usize> Extend<&'a T> for Vec<T, N>
+where
+    T: 'a + Copy,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend(iter.into_iter().cloned())
+    }
+}
+
+impl<T, const N: usize> hash::Hash for Vec<T, N>
 where
     T: core::hash::Hash,
 {
@@ -633,6 +1641,19 @@ impl<T, const N: usize> FromIterator<T> for Vec<T, N> {
     }
 }
 
+impl<T, const N: usize> TryFromIterator<T> for Vec<T, N> {
+    fn try_from_iter<I>(iter: I) -> Result<Self, CapacityError<T>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut vec = Vec::new();
+        for i in iter {
+            vec.push(i).map_err(CapacityError::new)?;
+        }
+        Ok(vec)
+    }
+}
+
 /// An iterator that moves out of an [`Vec`][`Vec`].
 ///
 /// This struct is created by calling the `into_iter` method on [`Vec`][`Vec`].
@@ -657,8 +1678,15 @@ impl<T, const N: usize> Iterator for IntoIter<T, N> {
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.vec.len() - self.next;
+        (len, Some(len))
+    }
 }
 
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
+
 impl<T, const N: usize> Clone for IntoIter<T, N>
 where
     T: Clone,
@@ -700,6 +1728,181 @@ impl<T, const N: usize> IntoIterator for Vec<T, N> {
     }
 }
 
+/// Converts `range` into a `start..end` range over a sequence of length `len`
+///
+/// # Panics
+///
+/// Panics if the start of the range is greater than its end or if its end is greater than `len`
+pub(crate) fn range_of<R>(range: R, len: usize) -> ops::Range<usize>
+where
+    R: RangeBounds<usize>,
+{
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(start) => start
+            .checked_add(1)
+            .unwrap_or_else(|| panic!("attempted to index slice from after maximum usize")),
+        Bound::Unbounded => 0,
+    };
+
+    let end = match range.end_bound() {
+        Bound::Included(end) => end
+            .checked_add(1)
+            .unwrap_or_else(|| panic!("attempted to index slice up to maximum usize")),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+
+    if start > end {
+        panic!("slice index starts at {} but ends at {}", start, end);
+    }
+    if end > len {
+        panic!(
+            "range end index {} out of range for slice of length {}",
+            end, len
+        );
+    }
+
+    start..end
+}
+
+/// A draining iterator for [`Vec`].
+///
+/// This struct is created by [`Vec::drain`] and [`VecView::drain`].
+pub struct Drain<'a, T> {
+    /// Index of tail to preserve
+    tail_start: usize,
+    /// Length of tail
+    tail_len: usize,
+    /// Current remaining range to remove
+    iter: slice::Iter<'a, T>,
+    vec: NonNull<VecView<T>>,
+}
+
+// SAFETY: `Drain` owns the drained `T`s and otherwise acts like the `&mut VecView<T>` it was
+// created from
+unsafe impl<T: Send> Send for Drain<'_, T> {}
+unsafe impl<T: Sync> Sync for Drain<'_, T> {}
+
+impl<T: fmt::Debug> fmt::Debug for Drain<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Drain").field(&self.iter.as_slice()).finish()
+    }
+}
+
+impl<'a, T> Drain<'a, T> {
+    /// Returns the remaining items of this iterator as a slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<_, 3> = Vec::from_slice(&['a', 'b', 'c']).unwrap();
+    /// let mut drain = vec.drain(..);
+    /// assert_eq!(drain.as_slice(), &['a', 'b', 'c']);
+    /// let _ = drain.next().unwrap();
+    /// assert_eq!(drain.as_slice(), &['b', 'c']);
+    /// ```
+    pub fn as_slice(&self) -> &[T] {
+        self.iter.as_slice()
+    }
+}
+
+impl<T> AsRef<[T]> for Drain<'_, T> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        // NOTE(unsafe) the drained elements are no longer part of the vector; each one is read
+        // out exactly once
+        self.iter
+            .next()
+            .map(|elt| unsafe { ptr::read(elt as *const _) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Drain<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.iter
+            .next_back()
+            .map(|elt| unsafe { ptr::read(elt as *const _) })
+    }
+}
+
+impl<T> Drop for Drain<'_, T> {
+    fn drop(&mut self) {
+        /// Moves back the un-`Drain`ed elements to restore the original `Vec`.
+        struct DropGuard<'r, 'a, T>(&'r mut Drain<'a, T>);
+
+        impl<'r, 'a, T> Drop for DropGuard<'r, 'a, T> {
+            fn drop(&mut self) {
+                if self.0.tail_len > 0 {
+                    unsafe {
+                        let source_vec = self.0.vec.as_mut();
+                        // memmove back untouched tail, update to new length
+                        let start = source_vec.len();
+                        let tail = self.0.tail_start;
+                        if tail != start {
+                            let ptr = source_vec.buffer_mut_ptr();
+                            let src = ptr.add(tail);
+                            let dst = ptr.add(start);
+                            ptr::copy(src, dst, self.0.tail_len);
+                        }
+                        source_vec.set_len(start + self.0.tail_len);
+                    }
+                }
+            }
+        }
+
+        let iter = mem::replace(&mut self.iter, [].iter());
+        let drop_len = iter.len();
+
+        let mut vec = self.vec;
+
+        // ensure elements are moved back into their appropriate places, even when drop_in_place
+        // panics
+        let _guard = DropGuard(self);
+
+        if drop_len == 0 {
+            return;
+        }
+
+        let drop_ptr = iter.as_slice().as_ptr();
+
+        unsafe {
+            // drop_ptr comes from a slice::Iter which only gives us a &[T] but for drop_in_place
+            // a pointer with mutable provenance is necessary. Therefore we must reconstruct
+            // it from the original vec but also avoid creating a &mut to the front since that could
+            // invalidate raw pointers to it which some unsafe code might rely on.
+            let vec_ptr = vec.as_mut().buffer_mut_ptr();
+            // zero-sized elements all live at the same address so any offset will do
+            let drop_offset = if mem::size_of::<T>() == 0 {
+                0
+            } else {
+                drop_ptr.offset_from(vec_ptr) as usize
+            };
+            let to_drop = ptr::slice_from_raw_parts_mut(vec_ptr.add(drop_offset), drop_len);
+            ptr::drop_in_place(to_drop);
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+
+impl<T> FusedIterator for Drain<'_, T> {}
+
 impl<A, B, const N1: usize, const N2: usize> PartialEq<Vec<B, N2>> for Vec<A, N1>
 where
     A: PartialEq<B>,
@@ -815,9 +2018,163 @@ where
     }
 }
 
+impl<T> fmt::Debug for VecView<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <[T] as fmt::Debug>::fmt(self, f)
+    }
+}
+
+impl fmt::Write for VecView<u8> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        match self.extend_from_slice(s.as_bytes()) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
+
+impl<T> Extend<T> for VecView<T> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.extend(iter)
+    }
+}
+
+impl<'a, T> Extend<&'a T> for VecView<T>
+where
+    T: 'a + Copy,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend(iter.into_iter().cloned())
+    }
+}
+
+impl<T> hash::Hash for VecView<T>
+where
+    T: core::hash::Hash,
+{
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        <[T] as hash::Hash>::hash(self, state)
+    }
+}
+
+impl<T> hash32::Hash for VecView<T>
+where
+    T: hash32::Hash,
+{
+    fn hash<H: hash32::Hasher>(&self, state: &mut H) {
+        <[T] as hash32::Hash>::hash(self, state)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a VecView<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut VecView<T> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<A, B> PartialEq<VecView<B>> for VecView<A>
+where
+    A: PartialEq<B>,
+{
+    fn eq(&self, other: &VecView<B>) -> bool {
+        <[A]>::eq(self, &**other)
+    }
+}
+
+// VecView<A> == Vec<B, N>
+impl<A, B, const N: usize> PartialEq<Vec<B, N>> for VecView<A>
+where
+    A: PartialEq<B>,
+{
+    fn eq(&self, other: &Vec<B, N>) -> bool {
+        <[A]>::eq(self, &**other)
+    }
+}
+
+// Vec<A, N> == VecView<B>
+impl<A, B, const N: usize> PartialEq<VecView<B>> for Vec<A, N>
+where
+    A: PartialEq<B>,
+{
+    fn eq(&self, other: &VecView<B>) -> bool {
+        <[A]>::eq(self, &**other)
+    }
+}
+
+// VecView<A> == [B]
+impl<A, B> PartialEq<[B]> for VecView<A>
+where
+    A: PartialEq<B>,
+{
+    fn eq(&self, other: &[B]) -> bool {
+        <[A]>::eq(self, other)
+    }
+}
+
+// VecView<A> == [B; M]
+impl<A, B, const M: usize> PartialEq<[B; M]> for VecView<A>
+where
+    A: PartialEq<B>,
+{
+    fn eq(&self, other: &[B; M]) -> bool {
+        <[A]>::eq(self, &other[..])
+    }
+}
+
+impl<T> Eq for VecView<T> where T: Eq {}
+
+impl<T> ops::Deref for VecView<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T> ops::DerefMut for VecView<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T> AsRef<[T]> for VecView<T> {
+    #[inline]
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T> AsMut<[T]> for VecView<T> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::Vec;
+    use crate::{Vec, VecView};
     use core::fmt::Write;
 
     #[test]
@@ -875,6 +2232,30 @@ mod tests {
         assert_eq!(unsafe { COUNT }, 0);
     }
 
+    // regression test: `truncate` used to drop each removed item through `get_unchecked_mut` on a
+    // slice that had already been shortened past it, i.e. an out of bounds reference
+    #[test]
+    fn truncate_out_of_bounds_reference() {
+        droppable!();
+
+        let mut v: Vec<Droppable, 3> = Vec::new();
+        v.push(Droppable::new()).ok().unwrap();
+        v.push(Droppable::new()).ok().unwrap();
+        v.push(Droppable::new()).ok().unwrap();
+
+        v.truncate(4);
+        assert_eq!(v.len(), 3);
+        assert_eq!(unsafe { COUNT }, 3);
+
+        v.truncate(1);
+        assert_eq!(v.len(), 1);
+        assert_eq!(unsafe { COUNT }, 1);
+
+        v.truncate(0);
+        assert!(v.is_empty());
+        assert_eq!(unsafe { COUNT }, 0);
+    }
+
     #[test]
     fn eq() {
         let mut xs: Vec<i32, 4> = Vec::new();
@@ -950,6 +2331,30 @@ mod tests {
         let _vec = slice.iter().cloned().collect::<Vec<_, 2>>();
     }
 
+    #[test]
+    fn try_collect() {
+        use crate::{CapacityError, TryCollect, TryFromIterator};
+
+        let slice = &[1, 2, 3];
+        let vec = slice.iter().cloned().try_collect::<Vec<i32, 4>>().unwrap();
+        assert_eq!(&vec, slice);
+
+        // the overflowing item is handed back
+        assert_eq!(
+            slice.iter().cloned().try_collect::<Vec<_, 2>>(),
+            Err(CapacityError::new(3))
+        );
+
+        droppable!();
+
+        // the partially filled vector is dropped, only the rejected item is left alive
+        let r = Vec::<Droppable, 2>::try_from_iter((0..3).map(|_| Droppable::new()));
+        assert!(r.is_err());
+        assert_eq!(unsafe { COUNT }, 1);
+        core::mem::drop(r);
+        assert_eq!(unsafe { COUNT }, 0);
+    }
+
     #[test]
     fn iter_move() {
         let mut v: Vec<i32, 4> = Vec::new();
@@ -959,9 +2364,11 @@ mod tests {
         v.push(3).unwrap();
 
         let mut items = v.into_iter();
+        assert_eq!(items.len(), 4);
 
         assert_eq!(items.next(), Some(0));
         assert_eq!(items.next(), Some(1));
+        assert_eq!(items.len(), 2);
         assert_eq!(items.next(), Some(2));
         assert_eq!(items.next(), Some(3));
         assert_eq!(items.next(), None);
@@ -1147,4 +2554,253 @@ mod tests {
         assert!(!v.ends_with(b"ba"));
         assert!(!v.ends_with(b"a"));
     }
+
+    #[test]
+    fn insert() {
+        let mut v: Vec<i32, 4> = Vec::new();
+        v.insert(0, 1).unwrap();
+        v.insert(0, 0).unwrap();
+        v.insert(2, 3).unwrap();
+        v.insert(2, 2).unwrap();
+        assert_eq!(v, [0, 1, 2, 3]);
+
+        assert_eq!(v.insert(1, 4), Err(4));
+        assert_eq!(v, [0, 1, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_out_of_bounds() {
+        let mut v: Vec<i32, 4> = Vec::new();
+        v.insert(1, 0).unwrap();
+    }
+
+    #[test]
+    fn remove() {
+        let mut v: Vec<i32, 4> = Vec::from_slice(&[0, 1, 2, 3]).unwrap();
+        assert_eq!(v.remove(3), 3);
+        assert_eq!(v.remove(0), 0);
+        assert_eq!(v, [1, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn remove_out_of_bounds() {
+        let mut v: Vec<i32, 4> = Vec::from_slice(&[0, 1]).unwrap();
+        v.remove(2);
+    }
+
+    // regression test: `swap_remove` used to swap through two overlapping `&mut` borrows of the
+    // buffer, the second of which invalidated the first under Stacked Borrows (`cargo miri test`)
+    #[test]
+    fn swap_remove_stacked_borrows() {
+        let mut v: Vec<i32, 4> = Vec::from_slice(&[0, 1, 2, 3]).unwrap();
+        assert_eq!(v.swap_remove(0), 0);
+        assert_eq!(v, [3, 1, 2]);
+        assert_eq!(v.swap_remove(2), 2);
+        assert_eq!(v, [3, 1]);
+    }
+
+    #[test]
+    fn retain() {
+        droppable!();
+
+        let mut v: Vec<(i32, Droppable), 8> = Vec::new();
+        for i in 0..8 {
+            v.push((i, Droppable::new())).ok().unwrap();
+        }
+
+        v.retain(|&(i, _)| i % 3 != 0);
+        assert_eq!(unsafe { COUNT }, 5);
+        assert_eq!(
+            v.iter().map(|&(i, _)| i).collect::<Vec<_, 8>>(),
+            [1, 2, 4, 5, 7]
+        );
+
+        v.retain_mut(|(i, _)| {
+            *i *= 10;
+            *i > 20
+        });
+        assert_eq!(unsafe { COUNT }, 3);
+        assert_eq!(
+            v.iter().map(|&(i, _)| i).collect::<Vec<_, 8>>(),
+            [40, 50, 70]
+        );
+
+        core::mem::drop(v);
+        assert_eq!(unsafe { COUNT }, 0);
+    }
+
+    // regression test: on a panic in the predicate, `retain_mut`'s drop guard used to back shift
+    // the unchecked items with a source pointer that the borrow for the destination pointer had
+    // invalidated under Stacked Borrows (`cargo miri test`)
+    #[test]
+    fn retain_panic() {
+        let mut v: Vec<i32, 8> = Vec::from_slice(&[0, 1, 2, 3, 4, 5]).unwrap();
+
+        let r = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            v.retain(|&i| {
+                if i == 3 {
+                    panic!();
+                }
+                i % 2 == 0
+            })
+        }));
+        assert!(r.is_err());
+
+        // the items checked before the panic are filtered, the rest are kept as they were
+        assert_eq!(v, [0, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn drain() {
+        let mut v: Vec<i32, 8> = Vec::from_slice(&[0, 1, 2, 3, 4, 5]).unwrap();
+
+        let mut drain = v.drain(1..4);
+        assert_eq!(drain.len(), 3);
+        assert_eq!(drain.next(), Some(1));
+        assert_eq!(drain.next_back(), Some(3));
+        core::mem::drop(drain);
+        assert_eq!(v, [0, 4, 5]);
+
+        assert_eq!(v.drain(..=0).collect::<Vec<_, 8>>(), [0]);
+        assert_eq!(v, [4, 5]);
+
+        assert_eq!(v.drain(2..).count(), 0);
+        assert_eq!(v, [4, 5]);
+
+        v.drain(..);
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn drain_drop() {
+        droppable!();
+
+        {
+            let mut v: Vec<Droppable, 4> = Vec::new();
+            for _ in 0..4 {
+                v.push(Droppable::new()).ok().unwrap();
+            }
+
+            let mut drain = v.drain(1..3);
+            drain.next().unwrap();
+            core::mem::drop(drain);
+            assert_eq!(unsafe { COUNT }, 2);
+            assert_eq!(v.len(), 2);
+        }
+
+        assert_eq!(unsafe { COUNT }, 0);
+    }
+
+    #[test]
+    fn drain_leak() {
+        let mut v: Vec<i32, 8> = Vec::from_slice(&[0, 1, 2, 3, 4, 5]).unwrap();
+        core::mem::forget(v.drain(2..4));
+
+        // the drained range and the tail are leaked, but the vector is still valid
+        assert_eq!(v, [0, 1]);
+        v.push(2).unwrap();
+        assert_eq!(v, [0, 1, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn drain_out_of_bounds() {
+        let mut v: Vec<i32, 8> = Vec::from_slice(&[0, 1, 2]).unwrap();
+        v.drain(1..4);
+    }
+
+    #[test]
+    fn dedup() {
+        let mut v: Vec<i32, 8> = Vec::from_slice(&[1, 1, 2, 3, 3, 3, 1]).unwrap();
+        v.dedup();
+        assert_eq!(v, [1, 2, 3, 1]);
+
+        let mut v: Vec<i32, 8> = Vec::from_slice(&[1, 2, 4, 5, 7]).unwrap();
+        v.dedup_by_key(|i| *i / 2);
+        assert_eq!(v, [1, 2, 4, 7]);
+
+        let mut v: Vec<i32, 8> = Vec::new();
+        v.dedup();
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn dedup_drop() {
+        droppable!();
+
+        {
+            let mut v: Vec<(i32, Droppable), 8> = Vec::new();
+            for &i in &[0, 0, 1, 1, 1, 2] {
+                v.push((i, Droppable::new())).ok().unwrap();
+            }
+
+            v.dedup_by(|a, b| a.0 == b.0);
+            assert_eq!(v.len(), 3);
+            assert_eq!(unsafe { COUNT }, 3);
+        }
+
+        assert_eq!(unsafe { COUNT }, 0);
+    }
+
+    #[test]
+    fn split_off() {
+        let mut v: Vec<i32, 8> = Vec::from_slice(&[0, 1, 2, 3, 4]).unwrap();
+
+        assert!(v.split_off::<2>(1).is_err());
+        assert_eq!(v, [0, 1, 2, 3, 4]);
+
+        let tail: Vec<i32, 3> = v.split_off(2).unwrap();
+        assert_eq!(v, [0, 1]);
+        assert_eq!(tail, [2, 3, 4]);
+
+        let tail: Vec<i32, 1> = v.split_off(2).unwrap();
+        assert!(tail.is_empty());
+        assert_eq!(v, [0, 1]);
+    }
+
+    #[test]
+    fn extend_from_within() {
+        let mut v: Vec<i32, 6> = Vec::from_slice(&[0, 1, 2]).unwrap();
+
+        v.extend_from_within(1..).unwrap();
+        assert_eq!(v, [0, 1, 2, 1, 2]);
+
+        assert!(v.extend_from_within(..2).is_err());
+        assert_eq!(v, [0, 1, 2, 1, 2]);
+
+        v.extend_from_within(..=0).unwrap();
+        assert_eq!(v, [0, 1, 2, 1, 2, 0]);
+    }
+
+    #[test]
+    fn view() {
+        fn fill(v: &mut VecView<u8>) {
+            let mut i = 0;
+            while v.push(i).is_ok() {
+                i += 1;
+            }
+        }
+
+        let mut small: Vec<u8, 2> = Vec::new();
+        let mut large: Vec<u8, 5> = Vec::new();
+        fill(small.as_mut_view());
+        fill(large.as_mut_view());
+        assert_eq!(small, [0, 1]);
+        assert_eq!(large, [0, 1, 2, 3, 4]);
+
+        let view = large.as_mut_view();
+        assert_eq!(view.capacity(), 5);
+        assert!(view.is_full());
+        view.retain(|x| x % 2 == 0);
+        view.insert(1, 10).unwrap();
+        assert_eq!(view.drain(..1).collect::<Vec<_, 5>>(), [0]);
+        assert_eq!(*view, [10, 2, 4]);
+        assert_eq!(large.as_view(), &large);
+
+        let tail: Vec<u8, 3> = large.as_mut_view().split_off(1).unwrap();
+        assert_eq!(tail, [2, 4]);
+        assert_eq!(large, [10]);
+    }
 }
@@ -2,7 +2,7 @@
 
 use heapless::{
     spsc::{Consumer, Producer, Queue},
-    HistoryBuffer, Vec,
+    Deque, HistoryBuffer, Vec,
 };
 
 #[test]
@@ -17,9 +17,21 @@ fn send() {
     {
     }
 
+    fn is_send_val<T>(_: &T)
+    where
+        T: Send,
+    {
+    }
+
     is_send::<Consumer<IsSend, 4>>();
     is_send::<Producer<IsSend, 4>>();
     is_send::<Queue<IsSend, 4>>();
     is_send::<Vec<IsSend, 4>>();
     is_send::<HistoryBuffer<IsSend, 4>>();
+
+    let mut vec: Vec<IsSend, 4> = Vec::new();
+    is_send_val(&vec.drain(..));
+
+    let mut deque: Deque<IsSend, 4> = Deque::new();
+    is_send_val(&deque.iter_mut());
 }
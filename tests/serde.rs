@@ -0,0 +1,37 @@
+//! Serialization round trips
+//!
+//! These live in an integration test so that `serde_json`'s `PartialEq` impls don't make
+//! comparisons in the unit tests ambiguous.
+#![cfg(feature = "serde")]
+
+use heapless::{
+    binary_heap::{BinaryHeap, Max},
+    SortedLinearMap,
+};
+
+#[test]
+fn binary_heap() {
+    let mut heap = BinaryHeap::<_, Max, 8>::new();
+    heap.extend(&[3, 1, 4, 1, 5]);
+
+    let json = serde_json::to_string(&heap).unwrap();
+    let de: BinaryHeap<i32, Max, 8> = serde_json::from_str(&json).unwrap();
+    assert_eq!(de, heap);
+    assert_eq!(de.into_sorted_vec(), [1, 1, 3, 4, 5]);
+
+    // too many items
+    assert!(serde_json::from_str::<BinaryHeap<i32, Max, 4>>(&json).is_err());
+}
+
+#[test]
+fn sorted_linear_map() {
+    let map: SortedLinearMap<_, _, 4> = [(2, 'b'), (1, 'a')].iter().copied().collect();
+
+    let json = serde_json::to_string(&map).unwrap();
+    assert_eq!(json, r#"{"1":"a","2":"b"}"#);
+    let de: SortedLinearMap<u8, char, 4> = serde_json::from_str(r#"{"2":"b","1":"a"}"#).unwrap();
+    assert_eq!(de, map);
+
+    // too many entries
+    assert!(serde_json::from_str::<SortedLinearMap<u8, char, 1>>(&json).is_err());
+}